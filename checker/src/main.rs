@@ -0,0 +1,48 @@
+//! Checks recorded traces of debug sessions for protocol conformance.
+//!
+//! Usage: `cargo run -p checker -- <trace>...`, where a trace is a JSONL file
+//! of messages or a raw stream of Content-Length frames, and `-` is the
+//! standard input. See `dapts::conformance` for the checks.
+
+use std::io::Read;
+use std::process::ExitCode;
+
+use dapts::conformance::check_trace;
+
+fn main() -> ExitCode {
+    let paths: Vec<String> = std::env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("usage: checker <trace>...");
+        return ExitCode::from(2);
+    }
+
+    let mut ok = true;
+    for path in &paths {
+        let input = if path == "-" {
+            let mut input = Vec::new();
+            std::io::stdin().read_to_end(&mut input).map(|_| input)
+        } else {
+            std::fs::read(path)
+        };
+        let input = match input {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("{path}: {err}");
+                ok = false;
+                continue;
+            }
+        };
+
+        let report = check_trace(&input);
+        for issue in &report.issues {
+            println!("{path}:{}: {}", issue.line, issue.kind);
+        }
+        println!("{path}: {} messages, {} issues", report.messages, report.issues.len());
+        ok &= report.is_ok();
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
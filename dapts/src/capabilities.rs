@@ -0,0 +1,98 @@
+//! Checks of the capabilities required by requests, negotiated by the
+//! `initialize` request.
+
+use crate::{AdapterToClient, Capabilities, ClientToAdapter, Error, IRequest, InitializeRequestArguments};
+
+impl Capabilities {
+    /// Checks that the debug adapter declared the capability required by
+    /// requests of type `R`, so that they can be sent.
+    pub fn check<R: ClientToAdapter>(&self) -> Result<(), Error> {
+        check::<R>(|capability| self.supports(capability))
+    }
+}
+
+impl InitializeRequestArguments {
+    /// Checks that the client declared the capability required by reverse
+    /// requests of type `R`, so that they can be sent.
+    pub fn check<R: AdapterToClient>(&self) -> Result<(), Error> {
+        check::<R>(|capability| self.supports(capability))
+    }
+}
+
+fn check<R: IRequest>(supports: impl FnOnce(&str) -> bool) -> Result<(), Error> {
+    match R::REQUIRED_CAPABILITY {
+        Some(capability) if !supports(capability) => Err(Error::Unsupported {
+            command: R::COMMAND,
+            capability,
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request;
+
+    #[test]
+    fn test_check_capabilities() {
+        let capabilities = Capabilities {
+            supports_step_back: Some(true),
+            supports_restart_frame: Some(false),
+            ..Default::default()
+        };
+        assert!(capabilities.check::<request::StepBack>().is_ok());
+        assert!(capabilities.check::<request::Threads>().is_ok());
+        let err = capabilities.check::<request::RestartFrame>().unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Unsupported {
+                command: "restartFrame",
+                capability: "supportsRestartFrame"
+            }
+        ));
+        assert!(capabilities.check::<request::Modules>().is_err());
+    }
+
+    #[test]
+    fn test_check_client_capabilities() {
+        let mut args = InitializeRequestArguments {
+            adapter_id: "test".to_owned(),
+            ..Default::default()
+        };
+        assert!(args.check::<request::RunInTerminal>().is_err());
+        args.supports_run_in_terminal_request = Some(true);
+        assert!(args.check::<request::RunInTerminal>().is_ok());
+    }
+
+    #[test]
+    fn test_required_capability() {
+        use crate::IRequest;
+
+        assert_eq!(request::Goto::REQUIRED_CAPABILITY, Some("supportsGotoTargetsRequest"));
+        assert_eq!(
+            request::Terminate::REQUIRED_CAPABILITY,
+            Some("supportsTerminateRequest")
+        );
+        assert_eq!(request::SetExceptionBreakpoints::REQUIRED_CAPABILITY, None);
+    }
+
+    #[test]
+    fn test_custom_request_defaults() {
+        use crate::{Direction, IRequest};
+
+        enum VendorStats {}
+
+        impl IRequest for VendorStats {
+            const COMMAND: &'static str = "vendor/stats";
+            type Arguments = ();
+            type Response = serde_json::Value;
+        }
+
+        impl ClientToAdapter for VendorStats {}
+
+        assert_eq!(VendorStats::DIRECTION, Direction::ClientToAdapter);
+        assert_eq!(VendorStats::REQUIRED_CAPABILITY, None);
+        assert!(Capabilities::default().check::<VendorStats>().is_ok());
+    }
+}
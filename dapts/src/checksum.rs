@@ -0,0 +1,242 @@
+//! Checksums of the contents of sources, to tell whether a file is the one a
+//! debug adapter refers to.
+//!
+//! ```
+//! use dapts::checksum::Verification;
+//! use dapts::{Checksum, ChecksumAlgorithm, Source};
+//!
+//! let contents = b"fn main() {}\n";
+//! let source = Source {
+//!     checksums: Some(vec![Checksum::compute(ChecksumAlgorithm::Sha256, contents).unwrap()]),
+//!     ..Default::default()
+//! };
+//! assert_eq!(source.verify(contents), Verification::Matches);
+//! assert!(matches!(source.verify(b"fn main() { }\n"), Verification::Differs { .. }));
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::{Checksum, ChecksumAlgorithm, Source};
+
+impl Checksum {
+    /// Computes the checksum of `contents` with `algorithm`.
+    ///
+    /// Returns `None` for [`ChecksumAlgorithm::Timestamp`], which is not
+    /// computed from contents, see [`Checksum::timestamp`].
+    pub fn compute(algorithm: ChecksumAlgorithm, contents: &[u8]) -> Option<Checksum> {
+        let digest = match algorithm {
+            ChecksumAlgorithm::Md5 => hex(&Md5::digest(contents)),
+            ChecksumAlgorithm::Sha1 => hex(&Sha1::digest(contents)),
+            ChecksumAlgorithm::Sha256 => hex(&Sha256::digest(contents)),
+            ChecksumAlgorithm::Timestamp => return None,
+        };
+        Some(Checksum {
+            algorithm,
+            checksum: digest,
+        })
+    }
+
+    /// Returns the timestamp checksum of a file modified at `modified`.
+    ///
+    /// The specification leaves the format of timestamps open. Here, the
+    /// checksum is the number of milliseconds since the Unix epoch, in
+    /// hexadecimal like other checksums, after a `unix-ms:` prefix telling it
+    /// apart from the timestamps of other adapters and clients.
+    pub fn timestamp(modified: SystemTime) -> Checksum {
+        Checksum {
+            algorithm: ChecksumAlgorithm::Timestamp,
+            checksum: format!("{TIMESTAMP_PREFIX}{:x}", millis(modified)),
+        }
+    }
+
+    /// Whether the checksum is the one of `contents`, or `None` for timestamps.
+    pub fn matches(&self, contents: &[u8]) -> Option<bool> {
+        let actual = Checksum::compute(self.algorithm, contents)?;
+        Some(actual.checksum.eq_ignore_ascii_case(&self.checksum))
+    }
+}
+
+/// The result of verifying a [`Source`] against the contents of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// All the checksums of the source match.
+    Matches,
+    /// A checksum of the source doesn't match.
+    Differs {
+        /// The checksum of the source.
+        expected: Checksum,
+        /// The checksum of the contents, with the same algorithm.
+        actual: Checksum,
+    },
+    /// The source has no checksum which could be verified.
+    Unverified,
+}
+
+impl Source {
+    /// Verifies the checksums of the source against `contents`.
+    ///
+    /// Timestamps are not verified, see [`Source::verify_file`].
+    pub fn verify(&self, contents: &[u8]) -> Verification {
+        self.verify_with(contents, None)
+    }
+
+    /// Verifies the checksums of the source against the file at `path`,
+    /// including timestamps against its modification time.
+    pub fn verify_file(&self, path: impl AsRef<Path>) -> io::Result<Verification> {
+        let path = path.as_ref();
+        let contents = fs::read(path)?;
+        // Modification times are not available on all platforms.
+        let modified = fs::metadata(path)?.modified().ok();
+        Ok(self.verify_with(&contents, modified))
+    }
+
+    fn verify_with(&self, contents: &[u8], modified: Option<SystemTime>) -> Verification {
+        let mut verified = false;
+        for expected in self.checksums.iter().flatten() {
+            let actual = match expected.algorithm {
+                ChecksumAlgorithm::Timestamp => {
+                    let Some(modified) = modified else { continue };
+                    // Timestamps in another format can't be compared.
+                    let Some(expected_millis) = parse_timestamp(&expected.checksum) else {
+                        continue;
+                    };
+                    (expected_millis != millis(modified)).then(|| Checksum::timestamp(modified))
+                }
+                algorithm => {
+                    let actual = Checksum::compute(algorithm, contents).expect("not a timestamp");
+                    (!actual.checksum.eq_ignore_ascii_case(&expected.checksum)).then_some(actual)
+                }
+            };
+            if let Some(actual) = actual {
+                return Verification::Differs {
+                    expected: expected.clone(),
+                    actual,
+                };
+            }
+            verified = true;
+        }
+        if verified {
+            Verification::Matches
+        } else {
+            Verification::Unverified
+        }
+    }
+}
+
+/// The prefix of the timestamps of [`Checksum::timestamp`].
+const TIMESTAMP_PREFIX: &str = "unix-ms:";
+
+/// Parses a timestamp of [`Checksum::timestamp`] into milliseconds.
+fn parse_timestamp(checksum: &str) -> Option<u128> {
+    let hex = checksum.strip_prefix(TIMESTAMP_PREFIX)?;
+    // `from_str_radix` accepts a sign, which is not a hex digit.
+    if hex.is_empty() || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u128::from_str_radix(hex, 16).ok()
+}
+
+fn millis(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_millis())
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn checksum(algorithm: ChecksumAlgorithm, checksum: &str) -> Checksum {
+        Checksum {
+            algorithm,
+            checksum: checksum.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_compute() {
+        let expected = [
+            (ChecksumAlgorithm::Md5, "900150983cd24fb0d6963f7d28e17f72"),
+            (ChecksumAlgorithm::Sha1, "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            (
+                ChecksumAlgorithm::Sha256,
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+        ];
+        for (algorithm, digest) in expected {
+            assert_eq!(Checksum::compute(algorithm, b"abc"), Some(checksum(algorithm, digest)));
+        }
+        assert_eq!(Checksum::compute(ChecksumAlgorithm::Timestamp, b"abc"), None);
+
+        let upper = checksum(ChecksumAlgorithm::Md5, "900150983CD24FB0D6963F7D28E17F72");
+        assert_eq!(upper.matches(b"abc"), Some(true));
+        assert_eq!(upper.matches(b"abd"), Some(false));
+    }
+
+    #[test]
+    fn test_verify() {
+        let mut source = Source::default();
+        assert_eq!(source.verify(b"abc"), Verification::Unverified);
+
+        let modified = UNIX_EPOCH + Duration::from_millis(0x1234);
+        source.checksums = Some(vec![Checksum::timestamp(modified)]);
+        assert_eq!(source.checksums.as_ref().unwrap()[0].checksum, "unix-ms:1234");
+        assert_eq!(source.verify(b"abc"), Verification::Unverified);
+        assert_eq!(source.verify_with(b"abc", Some(modified)), Verification::Matches);
+        let later = modified + Duration::from_secs(1);
+        let Verification::Differs { actual, .. } = source.verify_with(b"abc", Some(later)) else {
+            panic!("expected a different timestamp");
+        };
+        assert_eq!(actual, Checksum::timestamp(later));
+        for other in [
+            "2024-01-01T00:00:00Z",
+            "1700000000000",
+            "1234",
+            "unix-ms:+1234",
+            "unix-ms:",
+        ] {
+            source.checksums = Some(vec![checksum(ChecksumAlgorithm::Timestamp, other)]);
+            assert_eq!(source.verify_with(b"abc", Some(modified)), Verification::Unverified);
+        }
+
+        let sha1 = Checksum::compute(ChecksumAlgorithm::Sha1, b"abc").unwrap();
+        source.checksums = Some(vec![sha1.clone()]);
+        assert_eq!(source.verify(b"abc"), Verification::Matches);
+        assert_eq!(
+            source.verify(b"abd"),
+            Verification::Differs {
+                expected: sha1,
+                actual: Checksum::compute(ChecksumAlgorithm::Sha1, b"abd").unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_file() {
+        let path = std::env::temp_dir().join(format!("dapts-checksum-{}", std::process::id()));
+        fs::write(&path, "abc").unwrap();
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+        let source = Source {
+            checksums: Some(vec![
+                Checksum::compute(ChecksumAlgorithm::Md5, b"abc").unwrap(),
+                Checksum::timestamp(modified),
+            ]),
+            ..Default::default()
+        };
+        let verification = source.verify_file(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(verification.unwrap(), Verification::Matches);
+        assert_eq!(source.verify_file(&path).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,338 @@
+//! A client, which sends requests to a debug adapter and correlates them with
+//! their responses.
+//!
+//! [`Client::new`] splits a connection into a [`Client`] issuing requests, a
+//! channel of the events sent by the adapter, and a future reading from the
+//! connection, which must be polled for responses and events to arrive:
+//!
+//! ```no_run
+//! use dapts::client::Client;
+//! use dapts::request::Threads;
+//!
+//! # async fn example(reader: tokio::io::DuplexStream, writer: tokio::io::DuplexStream) -> Result<(), dapts::Error> {
+//! let (client, mut events, connection) = Client::new(reader, writer);
+//! tokio::spawn(connection);
+//!
+//! let threads = client.send::<Threads>(()).await?;
+//! println!("{} threads", threads.threads.len());
+//! while let Some(event) = events.recv().await {
+//!     println!("{}", event.event);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Reverse requests sent by the adapter are handled by a
+//! [`ReverseRequestHandler`], given to [`Client::with_handler`].
+
+mod handler;
+
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc;
+
+use self::handler::Dispatcher;
+pub use self::handler::ReverseRequestHandler;
+use crate::connection::Connection;
+use crate::{ClientToAdapter, Error, Event, Response};
+
+/// Sends requests to a debug adapter.
+///
+/// Clones share the same connection, so requests can be sent concurrently.
+#[derive(Clone)]
+pub struct Client {
+    connection: Arc<Connection>,
+}
+
+impl Client {
+    /// Creates a client sending requests to `writer` and reading responses
+    /// and events from `reader`.
+    ///
+    /// Returns the client, the events sent by the adapter, and a future
+    /// reading from `reader`, which must be polled, e.g. spawned, for requests
+    /// to complete. The future resolves when `reader` is closed or fails; the
+    /// requests still waiting for a response then fail with
+    /// [`Error::ConnectionClosed`].
+    ///
+    /// Reverse requests from the adapter are answered with a failure.
+    pub fn new<R, W>(
+        reader: R,
+        writer: W,
+    ) -> (
+        Client,
+        mpsc::UnboundedReceiver<Event>,
+        impl Future<Output = Result<(), Error>> + Send + 'static,
+    )
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        Client::with_handler(reader, writer, Unsupported)
+    }
+
+    /// Creates a client like [`Client::new`], whose reverse requests are
+    /// handled by `handler`.
+    ///
+    /// Reverse requests are handled one at a time, while responses and
+    /// events keep being read. Past [`MAX_QUEUED_REQUESTS`] queued reverse
+    /// requests, more are answered with a failure.
+    ///
+    /// [`MAX_QUEUED_REQUESTS`]: crate::server::MAX_QUEUED_REQUESTS
+    pub fn with_handler<R, W, H>(
+        reader: R,
+        writer: W,
+        handler: H,
+    ) -> (
+        Client,
+        mpsc::UnboundedReceiver<Event>,
+        impl Future<Output = Result<(), Error>> + Send + 'static,
+    )
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+        H: ReverseRequestHandler + 'static,
+    {
+        let connection = Arc::new(Connection::new(writer));
+        let (events, events_rx) = mpsc::unbounded_channel();
+        let run = run(connection.clone(), reader, Dispatcher(handler), events);
+        (Client { connection }, events_rx, run)
+    }
+
+    /// Sends a request of type `R` and waits for its response.
+    ///
+    /// A failed response is returned as [`Error::Response`], carrying the
+    /// structured error message if the adapter sent one.
+    pub async fn send<R: ClientToAdapter>(&self, arguments: R::Arguments) -> Result<R::Response, Error> {
+        self.connection.typed_request::<R>(arguments).await
+    }
+
+    /// Sets the function called with the errors of the malformed messages
+    /// which can't be answered or passed to a request, e.g. events.
+    pub fn on_malformed(&self, on_malformed: impl FnMut(Error) + Send + 'static) {
+        self.connection.set_on_malformed(on_malformed);
+    }
+
+    /// Sends a request and waits for its response, successful or not.
+    pub async fn send_request(&self, command: String, arguments: serde_json::Value) -> Result<Response, Error> {
+        self.connection.request(command, arguments).await
+    }
+}
+
+/// Refuses every reverse request.
+struct Unsupported;
+
+impl ReverseRequestHandler for Unsupported {}
+
+async fn run<R: AsyncRead + Unpin, H: ReverseRequestHandler>(
+    connection: Arc<Connection>,
+    reader: R,
+    mut dispatcher: Dispatcher<H>,
+    events: mpsc::UnboundedSender<Event>,
+) -> Result<(), Error> {
+    let on_event = |event| {
+        let _ = events.send(event);
+    };
+    connection.serve(reader, &mut dispatcher, on_event).await
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::io::DuplexStream;
+    use tokio_util::codec::Framed;
+
+    use super::*;
+    use crate::codec::tokio::{framed, DapCodec};
+    use crate::{request, Message, ProtocolMessage, Request, RequestError, ThreadsResponse};
+    use crate::{RunInTerminalRequestArguments, RunInTerminalResponse};
+
+    /// The adapter side of a connection.
+    type Adapter = Framed<DuplexStream, DapCodec>;
+
+    fn connect() -> (Client, mpsc::UnboundedReceiver<Event>, Adapter) {
+        let (client, adapter) = tokio::io::duplex(4096);
+        let (reader, writer) = tokio::io::split(client);
+        let (client, events, connection) = Client::new(reader, writer);
+        tokio::spawn(connection);
+        (client, events, framed(adapter))
+    }
+
+    async fn recv_request(adapter: &mut Adapter) -> Request {
+        match adapter.next().await.unwrap().unwrap() {
+            ProtocolMessage::Request(request) => request,
+            _ => panic!("expected a request"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send() {
+        let (client, _events, mut adapter) = connect();
+        let adapter = tokio::spawn(async move {
+            let request = recv_request(&mut adapter).await;
+            assert_eq!((request.seq, request.command.as_str()), (1, "threads"));
+            let body = serde_json::json!({ "threads": [{ "id": 1, "name": "main" }] });
+            let response = Response::new(1, request.seq, request.command, true, None, Some(body));
+            adapter.send(ProtocolMessage::Response(response)).await.unwrap();
+        });
+
+        let response = client.send::<request::Threads>(()).await.unwrap();
+        assert_eq!(response.threads[0].name, "main");
+        adapter.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_responses_out_of_order() {
+        let (client, _events, mut adapter) = connect();
+        let adapter = tokio::spawn(async move {
+            let first = recv_request(&mut adapter).await;
+            let second = recv_request(&mut adapter).await;
+            for (seq, request) in [(1, second), (2, first)] {
+                let body = ThreadsResponse::default();
+                let response = Response::success::<request::Threads>(seq, request.seq, body);
+                adapter.send(ProtocolMessage::Response(response)).await.unwrap();
+            }
+        });
+
+        let (first, second) = tokio::join!(
+            client.send::<request::Threads>(()),
+            client.send_request("pause".to_owned(), serde_json::json!({ "threadId": 1 })),
+        );
+        first.unwrap();
+        let second = second.unwrap();
+        assert!(second.success);
+        adapter.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_response() {
+        let (client, _events, mut adapter) = connect();
+        let adapter = tokio::spawn(async move {
+            let request = recv_request(&mut adapter).await;
+            let detail = Message::new(1, "no frame {_id}").with_variable("_id", "7");
+            let response =
+                Response::error::<request::Scopes>(1, request.seq, Some("badFrame".to_owned()), Some(detail));
+            adapter.send(ProtocolMessage::Response(response)).await.unwrap();
+        });
+
+        let args = crate::ScopesArguments {
+            frame_id: crate::FrameId(7),
+        };
+        let Err(Error::Response(err)) = client.send::<request::Scopes>(args).await else {
+            panic!("expected a failed response");
+        };
+        assert_eq!(err.message.as_deref(), Some("badFrame"));
+        assert_eq!(err.detail.unwrap().render(), "no frame 7");
+        adapter.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_events() {
+        let (_client, mut events, mut adapter) = connect();
+        let event = Event::from_typed::<crate::event::Initialized>(1, None);
+        adapter.send(ProtocolMessage::Event(event)).await.unwrap();
+        assert_eq!(events.recv().await.unwrap().event, "initialized");
+    }
+
+    #[tokio::test]
+    async fn test_reverse_request_is_refused() {
+        let (_client, _events, mut adapter) = connect();
+        let request = Request::new(1, "runInTerminal".to_owned(), serde_json::json!({ "args": ["ls"] }));
+        adapter.send(ProtocolMessage::Request(request)).await.unwrap();
+        let Some(Ok(ProtocolMessage::Response(response))) = adapter.next().await else {
+            panic!("expected a response");
+        };
+        assert_eq!((response.request_seq, response.success), (1, false));
+    }
+
+    #[tokio::test]
+    async fn test_connection_closed() {
+        let (client, _events, mut adapter) = connect();
+        let adapter = tokio::spawn(async move {
+            recv_request(&mut adapter).await;
+        });
+
+        let result = client.send::<request::Threads>(()).await;
+        assert!(matches!(result, Err(Error::ConnectionClosed)));
+        adapter.await.unwrap();
+
+        let result = client.send::<request::Threads>(()).await;
+        assert!(matches!(result, Err(Error::ConnectionClosed)));
+    }
+
+    struct Terminal;
+
+    impl ReverseRequestHandler for Terminal {
+        async fn run_in_terminal(
+            &mut self,
+            args: RunInTerminalRequestArguments,
+        ) -> Result<RunInTerminalResponse, RequestError> {
+            assert_eq!(args.args, ["ls"]);
+            Ok(RunInTerminalResponse {
+                process_id: Some(42),
+                shell_process_id: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reverse_request_handler() {
+        let (client, adapter) = tokio::io::duplex(4096);
+        let (reader, writer) = tokio::io::split(client);
+        let (client, _events, connection) = Client::with_handler(reader, writer, Terminal);
+        tokio::spawn(connection);
+        let mut adapter = framed(adapter);
+        let adapter = tokio::spawn(async move {
+            let request = Request::new(
+                1,
+                "runInTerminal".to_owned(),
+                serde_json::json!({ "args": ["ls"], "cwd": "/" }),
+            );
+            adapter.send(ProtocolMessage::Request(request)).await.unwrap();
+            let threads = recv_request(&mut adapter).await;
+            let Some(Ok(ProtocolMessage::Response(response))) = adapter.next().await else {
+                panic!("expected a response");
+            };
+            assert_eq!((response.seq, response.request_seq, response.success), (2, 1, true));
+            assert_eq!(response.body.unwrap()["processId"], 42);
+            let response = Response::success::<request::Threads>(2, threads.seq, ThreadsResponse::default());
+            adapter.send(ProtocolMessage::Response(response)).await.unwrap();
+        });
+
+        client.send::<request::Threads>(()).await.unwrap();
+        adapter.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_malformed_response_fails_request() {
+        use tokio::io::AsyncWriteExt;
+
+        let (client, _events, mut adapter) = connect();
+        let (tx, mut malformed) = mpsc::unbounded_channel();
+        client.on_malformed(move |err| {
+            let _ = tx.send(err);
+        });
+        let adapter = tokio::spawn(async move {
+            let request = recv_request(&mut adapter).await;
+            let mut input = Vec::new();
+            for body in [
+                r#"{"seq":1,"type":"event"}"#.to_owned(),
+                format!(
+                    r#"{{"seq":2,"type":"response","request_seq":{},"success":true}}"#,
+                    request.seq
+                ),
+            ] {
+                input.extend_from_slice(format!("Content-Length: {}\r\n\r\n{body}", body.len()).as_bytes());
+            }
+            adapter.get_mut().write_all(&input).await.unwrap();
+            adapter
+        });
+
+        let err = client.send::<request::Threads>(()).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidMessage(_)));
+        assert!(matches!(malformed.recv().await, Some(Error::InvalidMessage(_))));
+        assert!(malformed.try_recv().is_err());
+        drop(adapter.await.unwrap());
+    }
+}
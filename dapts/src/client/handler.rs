@@ -0,0 +1,50 @@
+// This file is autogenerated. Do not edit by hand.
+// To regenerate from schema, run `cargo run -p generator`.
+
+use std::future::Future;
+
+use crate::connection::{arguments, respond, Dispatch};
+use crate::request::*;
+use crate::RequestError;
+
+/// A handler of the reverse requests, sent by a debug adapter to the client.
+///
+/// Each request is handled by a method returning the response body, or a [`RequestError`] to answer with an error response. Requests whose method is not implemented fail with [`RequestError::unsupported`].
+pub trait ReverseRequestHandler: Send {
+    /// Handles the [`RunInTerminal`] request.
+    fn run_in_terminal(
+        &mut self,
+        args: crate::RunInTerminalRequestArguments,
+    ) -> impl Future<Output = Result<crate::RunInTerminalResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`StartDebugging`] request.
+    fn start_debugging(
+        &mut self,
+        args: crate::StartDebuggingRequestArguments,
+    ) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+}
+
+/// Dispatches requests to the methods of a [`ReverseRequestHandler`].
+pub(crate) struct Dispatcher<H>(pub(crate) H);
+
+impl<H: ReverseRequestHandler> Dispatch for Dispatcher<H> {
+    async fn dispatch(&mut self, request: &crate::Request) -> Result<serde_json::Value, RequestError> {
+        match request.command.as_str() {
+            RunInTerminal::COMMAND => {
+                let args = arguments::<RunInTerminal>(request)?;
+                respond::<RunInTerminal>(self.0.run_in_terminal(args).await)
+            }
+            StartDebugging::COMMAND => {
+                let args = arguments::<StartDebugging>(request)?;
+                respond::<StartDebugging>(self.0.start_debugging(args).await)
+            }
+            _ => Err(RequestError::unsupported()),
+        }
+    }
+}
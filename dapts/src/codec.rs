@@ -0,0 +1,364 @@
+//! Content-Length framing of the DAP base protocol.
+//!
+//! Every message is sent as a header part followed by a JSON body:
+//!
+//! ```text
+//! Content-Length: 72\r\n
+//! \r\n
+//! {"seq":153,"type":"request","command":"next","arguments":{"threadId":3}}
+//! ```
+//!
+//! See [Base Protocol](https://microsoft.github.io/debug-adapter-protocol/overview#base-protocol).
+//!
+//! [`MessageReader`] and [`MessageWriter`] work over blocking
+//! [`Read`]/[`Write`] streams. With the `tokio` feature enabled, the
+//! [`tokio`](self::tokio) module provides the same framing for async streams.
+
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::iter::FusedIterator;
+
+use crate::{Error, ProtocolMessage};
+
+/// The default upper bound of a message body, in bytes.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 64 * 1024 * 1024;
+
+/// The upper bound of the header part of a message, in bytes.
+pub const MAX_HEADER_SIZE: usize = 8 * 1024;
+
+const CONTENT_LENGTH: &str = "Content-Length";
+
+/// The error of a header part longer than [`MAX_HEADER_SIZE`].
+pub(crate) fn header_too_large() -> Error {
+    Error::InvalidHeader(format!("header part longer than {MAX_HEADER_SIZE} bytes"))
+}
+
+/// Whether reading can go on after `err`, because the frame was read as a
+/// whole and only its body is invalid.
+pub(crate) fn is_recoverable(err: &Error) -> bool {
+    matches!(err, Error::InvalidUtf8(_) | Error::InvalidMessage(_))
+}
+
+/// Parses a single header line, without its trailing `\r\n`, and returns the
+/// content length if the line carries it.
+pub(crate) fn parse_header_line(line: &[u8]) -> Result<Option<usize>, Error> {
+    let invalid = || Error::InvalidHeader(String::from_utf8_lossy(line).into_owned());
+    let line = std::str::from_utf8(line).map_err(|_| invalid())?;
+    let (name, value) = line.split_once(':').ok_or_else(invalid)?;
+    if !name.trim().eq_ignore_ascii_case(CONTENT_LENGTH) {
+        return Ok(None);
+    }
+    let value = value.trim();
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| Error::InvalidContentLength(value.to_owned()))
+}
+
+/// Checks the announced body length against `limit`.
+pub(crate) fn check_body_size(length: usize, limit: usize) -> Result<(), Error> {
+    if length > limit {
+        return Err(Error::BodyTooLarge { length, limit });
+    }
+    Ok(())
+}
+
+/// Parses a message body.
+pub(crate) fn parse_body(body: &[u8]) -> Result<ProtocolMessage, Error> {
+    let body = std::str::from_utf8(body).map_err(Error::InvalidUtf8)?;
+    serde_json::from_str(body).map_err(Error::InvalidMessage)
+}
+
+/// Serializes a message together with its header.
+pub(crate) fn encode_message(msg: &ProtocolMessage, dst: &mut Vec<u8>) -> Result<(), Error> {
+    let body = serde_json::to_vec(msg).map_err(Error::Serialize)?;
+    dst.extend_from_slice(format!("{CONTENT_LENGTH}: {}\r\n\r\n", body.len()).as_bytes());
+    dst.extend_from_slice(&body);
+    Ok(())
+}
+
+/// Reads framed messages from a byte stream.
+///
+/// As an iterator, it yields messages until the stream ends or a frame can't
+/// be read. Messages whose body is invalid are yielded as errors, and reading
+/// goes on with the next frame.
+pub struct MessageReader<R> {
+    reader: BufReader<R>,
+    max_body_size: usize,
+    done: bool,
+}
+
+impl<R: Read> MessageReader<R> {
+    /// Creates a new reader over `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            done: false,
+        }
+    }
+
+    /// Sets the upper bound of a message body, in bytes.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Reads the body of the next frame.
+    ///
+    /// Returns `Ok(None)` if the stream ends cleanly between two frames, and
+    /// [`Error::InvalidHeader`] if the header part is longer than
+    /// [`MAX_HEADER_SIZE`].
+    pub fn read_frame(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        let mut content_length = None;
+        let mut line = Vec::new();
+        let mut header_size = 0;
+        loop {
+            line.clear();
+            let limit = (MAX_HEADER_SIZE - header_size) as u64;
+            let read = (&mut self.reader).take(limit).read_until(b'\n', &mut line)?;
+            if read == 0 && header_size == 0 {
+                return Ok(None);
+            }
+            header_size += read;
+            let Some(header) = line.strip_suffix(b"\r\n") else {
+                if line.ends_with(b"\n") {
+                    return Err(Error::InvalidHeader(String::from_utf8_lossy(&line).into_owned()));
+                }
+                if header_size == MAX_HEADER_SIZE {
+                    return Err(header_too_large());
+                }
+                return Err(Error::UnexpectedEof);
+            };
+            if header.is_empty() {
+                break;
+            }
+            if let Some(length) = parse_header_line(header)? {
+                content_length = Some(length);
+            }
+        }
+
+        let length = content_length.ok_or(Error::MissingContentLength)?;
+        check_body_size(length, self.max_body_size)?;
+        let mut body = vec![0; length];
+        self.reader.read_exact(&mut body)?;
+        Ok(Some(body))
+    }
+
+    /// Reads and parses the next message.
+    ///
+    /// Returns `Ok(None)` if the stream ends cleanly between two messages.
+    pub fn read_message(&mut self) -> Result<Option<ProtocolMessage>, Error> {
+        match self.read_frame()? {
+            Some(body) => parse_body(&body).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<R: Read> Iterator for MessageReader<R> {
+    type Item = Result<ProtocolMessage, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let msg = self.read_message().transpose();
+        self.done = match &msg {
+            Some(Err(err)) => !is_recoverable(err),
+            Some(Ok(_)) => false,
+            None => true,
+        };
+        msg
+    }
+}
+
+impl<R: Read> FusedIterator for MessageReader<R> {}
+
+/// Writes framed messages to a byte stream.
+pub struct MessageWriter<W> {
+    writer: W,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> MessageWriter<W> {
+    /// Creates a new writer over `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Writes a message and flushes the underlying stream.
+    pub fn write_message(&mut self, msg: &ProtocolMessage) -> Result<(), Error> {
+        self.buf.clear();
+        encode_message(msg, &mut self.buf)?;
+        self.writer.write_all(&self.buf)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns the underlying stream.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, Request};
+
+    fn read_all(input: &[u8]) -> Vec<Result<ProtocolMessage, Error>> {
+        MessageReader::new(input).collect()
+    }
+
+    #[test]
+    fn test_read_single_message() {
+        let input =
+            b"Content-Length: 55\r\n\r\n{\"seq\":1,\"type\":\"request\",\"command\":\"threads\",\"x\":\"\xc3\xa9\"}";
+        let mut reader = MessageReader::new(&input[..]);
+        let msg = reader.read_message().unwrap().unwrap();
+        let ProtocolMessage::Request(req) = msg else {
+            panic!("expected a request");
+        };
+        assert_eq!(req.command, "threads");
+        assert!(reader.read_message().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_read_consecutive_messages() {
+        let input = b"Content-Length: 46\r\n\r\n{\"seq\":1,\"type\":\"event\",\"event\":\"initialized\"}\
+Content-Length: 42\r\n\r\n{\"seq\":2,\"type\":\"event\",\"event\":\"stopped\"}";
+        let msgs = read_all(input);
+        assert_eq!(msgs.len(), 2);
+        let seqs: Vec<_> = msgs.into_iter().map(|m| m.unwrap().seq()).collect();
+        assert_eq!(seqs, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_read_extra_headers() {
+        let input = b"Content-Type: application/vscode-jsonrpc; charset=utf-8\r\n\
+content-length:   42  \r\n\r\n{\"seq\":2,\"type\":\"event\",\"event\":\"stopped\"}";
+        let msgs = read_all(input);
+        assert_eq!(msgs.len(), 1);
+        assert!(msgs[0].is_ok());
+    }
+
+    #[test]
+    fn test_read_partial_header() {
+        let mut reader = MessageReader::new(&b"Content-Len"[..]);
+        assert!(matches!(reader.read_frame(), Err(Error::UnexpectedEof)));
+
+        let mut reader = MessageReader::new(&b"Content-Length: 10\r\n"[..]);
+        assert!(matches!(reader.read_frame(), Err(Error::UnexpectedEof)));
+    }
+
+    #[test]
+    fn test_read_partial_body() {
+        let mut reader = MessageReader::new(&b"Content-Length: 10\r\n\r\n{}"[..]);
+        assert!(matches!(reader.read_frame(), Err(Error::UnexpectedEof)));
+    }
+
+    #[test]
+    fn test_read_malformed_header() {
+        let mut reader = MessageReader::new(&b"Content-Length 10\r\n\r\n"[..]);
+        assert!(matches!(reader.read_frame(), Err(Error::InvalidHeader(_))));
+
+        let mut reader = MessageReader::new(&b"Content-Length: 10\n\n"[..]);
+        assert!(matches!(reader.read_frame(), Err(Error::InvalidHeader(_))));
+    }
+
+    #[test]
+    fn test_read_oversized_header() {
+        let input = vec![b'a'; MAX_HEADER_SIZE * 2];
+        let mut reader = MessageReader::new(&input[..]);
+        assert!(matches!(reader.read_frame(), Err(Error::InvalidHeader(_))));
+
+        let mut input = b"Content-Length: 2\r\n".repeat(MAX_HEADER_SIZE / 19 + 1);
+        input.extend_from_slice(b"\r\n{}");
+        let mut reader = MessageReader::new(&input[..]);
+        assert!(matches!(reader.read_frame(), Err(Error::InvalidHeader(_))));
+    }
+
+    #[test]
+    fn test_iterator_stops_after_fatal_error() {
+        let input = b"Content-Length: 2\r\n\r\n{]Content-Length: 2\r\n\r\n{}Content-Length: x\r\n\r\n";
+        let mut reader = MessageReader::new(&input[..]);
+        assert!(matches!(reader.next(), Some(Err(Error::InvalidMessage(_)))));
+        assert!(matches!(reader.next(), Some(Err(Error::InvalidMessage(_)))));
+        assert!(matches!(reader.next(), Some(Err(Error::InvalidContentLength(_)))));
+        assert!(reader.next().is_none());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_read_missing_content_length() {
+        let mut reader = MessageReader::new(&b"Content-Type: json\r\n\r\n{}"[..]);
+        assert!(matches!(reader.read_frame(), Err(Error::MissingContentLength)));
+    }
+
+    #[test]
+    fn test_read_invalid_content_length() {
+        let mut reader = MessageReader::new(&b"Content-Length: -1\r\n\r\n"[..]);
+        assert!(matches!(
+            reader.read_frame(),
+            Err(Error::InvalidContentLength(value)) if value == "-1"
+        ));
+    }
+
+    #[test]
+    fn test_read_oversized_body() {
+        let mut reader = MessageReader::new(&b"Content-Length: 1025\r\n\r\n"[..]).with_max_body_size(1024);
+        assert!(matches!(
+            reader.read_frame(),
+            Err(Error::BodyTooLarge {
+                length: 1025,
+                limit: 1024
+            })
+        ));
+    }
+
+    #[test]
+    fn test_read_malformed_utf8() {
+        let mut reader = MessageReader::new(&b"Content-Length: 4\r\n\r\n\"\xff\xfe\""[..]);
+        assert!(matches!(reader.read_message(), Err(Error::InvalidUtf8(_))));
+    }
+
+    #[test]
+    fn test_read_invalid_json() {
+        let mut reader = MessageReader::new(&b"Content-Length: 2\r\n\r\n{]"[..]);
+        assert!(matches!(reader.read_message(), Err(Error::InvalidMessage(_))));
+    }
+
+    #[test]
+    fn test_write_message() {
+        let mut writer = MessageWriter::new(Vec::new());
+        let msg = ProtocolMessage::Request(Request::new(1, "threads".to_owned(), ()));
+        writer.write_message(&msg).unwrap();
+        assert_eq!(
+            writer.into_inner(),
+            b"Content-Length: 46\r\n\r\n{\"type\":\"request\",\"seq\":1,\"command\":\"threads\"}"
+        );
+    }
+
+    #[test]
+    fn test_write_then_read() {
+        let mut writer = MessageWriter::new(Vec::new());
+        let body = serde_json::json!({ "category": "stdout", "output": "h\u{e9}llo\n" });
+        let event = Event::new(3, "output".to_owned(), body.clone());
+        writer.write_message(&ProtocolMessage::Event(event)).unwrap();
+        let bytes = writer.into_inner();
+
+        let msgs = read_all(&bytes);
+        assert_eq!(msgs.len(), 1);
+        let Ok(ProtocolMessage::Event(event)) = &msgs[0] else {
+            panic!("expected an event");
+        };
+        assert_eq!(event.event, "output");
+        assert_eq!(event.body, body);
+    }
+}
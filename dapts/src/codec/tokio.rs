@@ -0,0 +1,311 @@
+//! Asynchronous framing over tokio's [`AsyncRead`]/[`AsyncWrite`].
+//!
+//! [`DapCodec`] implements tokio-util's [`Decoder`] and [`Encoder`], so a
+//! transport can be turned into a `Stream` of [`ProtocolMessage`] and a `Sink`
+//! accepting them.
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::codec::{Decoder, Encoder, Framed, FramedRead, FramedWrite};
+
+use super::{check_body_size, encode_message, header_too_large, parse_body, parse_header_line};
+use super::{DEFAULT_MAX_BODY_SIZE, MAX_HEADER_SIZE};
+use crate::{Error, ProtocolMessage};
+
+/// A tokio-util codec for Content-Length framed messages.
+#[derive(Debug, Clone)]
+pub struct DapCodec {
+    max_body_size: usize,
+    /// The content length of a frame whose header is already consumed.
+    pending: Option<usize>,
+    /// The length of the start of the buffer known not to hold the end of
+    /// the header part.
+    scanned: usize,
+}
+
+impl Default for DapCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DapCodec {
+    /// Creates a new codec.
+    pub fn new() -> Self {
+        Self {
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            pending: None,
+            scanned: 0,
+        }
+    }
+
+    /// Sets the upper bound of a message body, in bytes.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Consumes a complete header part from `src`, returning the content
+    /// length, or `None` if the header part is not complete yet.
+    ///
+    /// The header part is scanned once, however slowly it arrives, and must
+    /// not be longer than [`MAX_HEADER_SIZE`].
+    fn decode_header(&mut self, src: &mut BytesMut) -> Result<Option<usize>, Error> {
+        let found = if src.starts_with(b"\r\n") {
+            Some((0, 2))
+        } else {
+            // The separator may straddle the scanned part and the new bytes.
+            let start = self.scanned.saturating_sub(3);
+            let found = src[start..].windows(4).position(|w| w == b"\r\n\r\n");
+            found.map(|end| (start + end, 4))
+        };
+        let Some((end, sep)) = found else {
+            self.scanned = src.len();
+            if src.len() > MAX_HEADER_SIZE {
+                return Err(header_too_large());
+            }
+            return Ok(None);
+        };
+        self.scanned = 0;
+        if end + sep > MAX_HEADER_SIZE {
+            return Err(header_too_large());
+        }
+
+        let header = src.split_to(end + sep);
+        let mut content_length = None;
+        if end > 0 {
+            for line in header[..end].split(|&b| b == b'\n') {
+                let line = match line.strip_suffix(b"\r") {
+                    Some(line) => line,
+                    // The last line has its `\r\n` cut off by the separator.
+                    None if line.as_ptr_range().end == header[..end].as_ptr_range().end => line,
+                    None => return Err(Error::InvalidHeader(String::from_utf8_lossy(line).into_owned())),
+                };
+                if let Some(length) = parse_header_line(line)? {
+                    content_length = Some(length);
+                }
+            }
+        }
+
+        let length = content_length.ok_or(Error::MissingContentLength)?;
+        check_body_size(length, self.max_body_size)?;
+        Ok(Some(length))
+    }
+}
+
+impl DapCodec {
+    /// Consumes a complete frame from `src`, returning its body, or `None` if
+    /// the frame is not complete yet.
+    pub(crate) fn decode_body(&mut self, src: &mut BytesMut) -> Result<Option<BytesMut>, Error> {
+        let length = match self.pending {
+            Some(length) => length,
+            None => match self.decode_header(src)? {
+                Some(length) => {
+                    self.pending = Some(length);
+                    length
+                }
+                None => return Ok(None),
+            },
+        };
+
+        if src.len() < length {
+            src.reserve(length - src.len());
+            return Ok(None);
+        }
+        self.pending = None;
+        Ok(Some(src.split_to(length)))
+    }
+
+    /// Consumes the last frame from `buf` once the input is closed, failing
+    /// if it is incomplete.
+    pub(crate) fn decode_body_eof(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, Error> {
+        match self.decode_body(buf)? {
+            Some(body) => Ok(Some(body)),
+            None if buf.is_empty() && self.pending.is_none() => Ok(None),
+            None => {
+                buf.advance(buf.len());
+                self.pending = None;
+                self.scanned = 0;
+                Err(Error::UnexpectedEof)
+            }
+        }
+    }
+}
+
+impl Decoder for DapCodec {
+    type Item = ProtocolMessage;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_body(src)?.map(|body| parse_body(&body)).transpose()
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_body_eof(buf)?.map(|body| parse_body(&body)).transpose()
+    }
+}
+
+impl Encoder<ProtocolMessage> for DapCodec {
+    type Error = Error;
+
+    fn encode(&mut self, item: ProtocolMessage, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        Encoder::<&ProtocolMessage>::encode(self, &item, dst)
+    }
+}
+
+impl Encoder<&ProtocolMessage> for DapCodec {
+    type Error = Error;
+
+    fn encode(&mut self, item: &ProtocolMessage, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let mut buf = Vec::new();
+        encode_message(item, &mut buf)?;
+        dst.put_slice(&buf);
+        Ok(())
+    }
+}
+
+/// Creates a stream of messages read from `reader`.
+pub fn message_stream<R: AsyncRead>(reader: R) -> FramedRead<R, DapCodec> {
+    FramedRead::new(reader, DapCodec::new())
+}
+
+/// Creates a sink of messages written to `writer`.
+pub fn message_sink<W: AsyncWrite>(writer: W) -> FramedWrite<W, DapCodec> {
+    FramedWrite::new(writer, DapCodec::new())
+}
+
+/// Creates a combined stream and sink of messages over `io`.
+pub fn framed<T: AsyncRead + AsyncWrite>(io: T) -> Framed<T, DapCodec> {
+    Framed::new(io, DapCodec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{SinkExt, StreamExt};
+
+    use super::*;
+    use crate::{Event, Request};
+
+    fn decode_all(input: &[u8]) -> Vec<Result<ProtocolMessage, Error>> {
+        let mut codec = DapCodec::new();
+        let mut buf = BytesMut::from(input);
+        let mut out = Vec::new();
+        loop {
+            match codec.decode_eof(&mut buf) {
+                Ok(Some(msg)) => out.push(Ok(msg)),
+                Ok(None) => break,
+                Err(err) => {
+                    out.push(Err(err));
+                    break;
+                }
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_decode_byte_by_byte() {
+        let input =
+            b"Content-Type: json\r\nContent-Length: 42\r\n\r\n{\"seq\":2,\"type\":\"event\",\"event\":\"stopped\"}";
+        let mut codec = DapCodec::new();
+        let mut buf = BytesMut::new();
+        let mut out = Vec::new();
+        for &b in input {
+            buf.put_u8(b);
+            if let Some(msg) = codec.decode(&mut buf).unwrap() {
+                out.push(msg);
+            }
+        }
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].seq(), 2);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_decode_oversized_header() {
+        let mut codec = DapCodec::new();
+        let mut buf = BytesMut::new();
+        let mut result = Ok(None);
+        for _ in 0..MAX_HEADER_SIZE {
+            buf.put_slice(b"X: 1\r\n");
+            result = codec.decode(&mut buf);
+            if result.is_err() {
+                break;
+            }
+        }
+        assert!(matches!(result, Err(Error::InvalidHeader(_))));
+        assert!(buf.len() <= MAX_HEADER_SIZE + 6);
+
+        let mut input = b"X: 1\r\n".repeat(MAX_HEADER_SIZE / 6);
+        input.extend_from_slice(b"Content-Length: 2\r\n\r\n{}");
+        let out = decode_all(&input);
+        assert!(matches!(out[..], [Err(Error::InvalidHeader(_))]));
+    }
+
+    #[test]
+    fn test_decode_partial_header_at_eof() {
+        let out = decode_all(b"Content-Length: 4");
+        assert!(matches!(out[..], [Err(Error::UnexpectedEof)]));
+    }
+
+    #[test]
+    fn test_decode_partial_body_at_eof() {
+        let out = decode_all(b"Content-Length: 4\r\n\r\n{}");
+        assert!(matches!(out[..], [Err(Error::UnexpectedEof)]));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let out = decode_all(b"Content-Length 4\r\n\r\n{}{}");
+        assert!(matches!(out[..], [Err(Error::InvalidHeader(_))]));
+
+        let out = decode_all(b"Content-Type: json\r\n\r\n{}");
+        assert!(matches!(out[..], [Err(Error::MissingContentLength)]));
+
+        let out = decode_all(b"\r\n{}");
+        assert!(matches!(out[..], [Err(Error::MissingContentLength)]));
+
+        let out = decode_all(b"Content-Length: x\r\n\r\n");
+        assert!(matches!(out[..], [Err(Error::InvalidContentLength(_))]));
+
+        let out = decode_all(b"Content-Length: 4\r\n\r\n\"\xff\xfe\"");
+        assert!(matches!(out[..], [Err(Error::InvalidUtf8(_))]));
+
+        let mut codec = DapCodec::new().with_max_body_size(3);
+        let mut buf = BytesMut::from(&b"Content-Length: 4\r\n\r\n"[..]);
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(Error::BodyTooLarge { length: 4, limit: 3 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_and_sink() {
+        let (client, server) = tokio::io::duplex(4096);
+        let (server_read, server_write) = tokio::io::split(server);
+        let mut client = framed(client);
+        let mut requests = message_stream(server_read);
+        let mut events = message_sink(server_write);
+
+        let req = Request::new(1, "threads".to_owned(), ());
+        client.send(ProtocolMessage::Request(req)).await.unwrap();
+        let got = requests.next().await.unwrap().unwrap();
+        let ProtocolMessage::Request(got) = got else {
+            panic!("expected a request");
+        };
+        assert_eq!(got.command, "threads");
+
+        let body = serde_json::json!({ "output": "x".repeat(200) });
+        let event = Event::new(1, "output".to_owned(), body.clone());
+        events.send(ProtocolMessage::Event(event)).await.unwrap();
+        let got = client.next().await.unwrap().unwrap();
+        let ProtocolMessage::Event(got) = got else {
+            panic!("expected an event");
+        };
+        assert_eq!(got.body, body);
+
+        drop(events);
+        drop(requests);
+        assert!(client.next().await.is_none());
+    }
+}
@@ -0,0 +1,556 @@
+//! Conformance checks of recorded traces of a debug session.
+//!
+//! A trace is either a JSONL file, with a message on each line, or a raw
+//! stream of Content-Length frames. A line of a JSONL trace can also tell the
+//! side which sent its message:
+//!
+//! ```text
+//! {"from":"client","message":{"seq":1,"type":"request","command":"initialize","arguments":{"adapterID":"x"}}}
+//! ```
+//!
+//! Otherwise, the side is inferred from the message: reverse requests and
+//! events are sent by the adapter, other requests by the client.
+//!
+//! Every message is checked against the types of this crate and the
+//! lifecycle of a [`Session`]. Each side must number its messages from 1 and
+//! by one, and every request must get exactly one response. The issues found
+//! are reported with the line where their message starts:
+//!
+//! ```
+//! use dapts::conformance::check_trace;
+//!
+//! let trace = br#"{"seq":1,"type":"request","command":"threads"}"#;
+//! let report = check_trace(trace);
+//! assert_eq!(report.issues[0].to_string(), "line 1: threads sent before initialize is answered");
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::codec::{parse_body, parse_header_line};
+use crate::event::AnyEvent;
+use crate::request::{self, AnyRequest, AnyResponse};
+use crate::session::{Session, Side, ViolationKind};
+use crate::{Direction, Error, ProtocolMessage};
+
+/// An issue found in a trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    /// The 1-based line where the message starts.
+    pub line: usize,
+    /// What is wrong with the message.
+    pub kind: IssueKind,
+}
+
+/// The kind of an [`Issue`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IssueKind {
+    /// The trace cannot be split into messages past this point.
+    InvalidFrame(String),
+    /// The message is not a valid protocol message.
+    InvalidMessage(String),
+    /// The arguments or body of the message don't match its type.
+    BadContent {
+        /// The message, e.g. `request setBreakpoints`.
+        message: String,
+        /// Why the content doesn't match.
+        error: String,
+    },
+    /// The command of a request is unknown to this crate.
+    UnknownCommand {
+        /// The command of the request.
+        command: String,
+    },
+    /// The type of an event is unknown to this crate.
+    UnknownEvent {
+        /// The type of the event.
+        event: String,
+    },
+    /// A request was sent by the wrong side.
+    WrongDirection {
+        /// The command of the request.
+        command: String,
+        /// The side which sent it.
+        side: Side,
+    },
+    /// A message is not numbered by one from the previous message of its
+    /// side.
+    WrongSeq {
+        /// The side which sent the message.
+        side: Side,
+        /// The expected `seq`.
+        expected: i64,
+        /// The `seq` of the message.
+        actual: i64,
+    },
+    /// A response answers no request.
+    UnknownRequest {
+        /// The `request_seq` of the response.
+        request_seq: i64,
+    },
+    /// A request was answered more than once.
+    DuplicateResponse {
+        /// The `request_seq` of the response.
+        request_seq: i64,
+    },
+    /// A response is not of the command of its request.
+    CommandMismatch {
+        /// The `request_seq` of the response.
+        request_seq: i64,
+        /// The command of the request.
+        expected: String,
+        /// The command of the response.
+        actual: String,
+    },
+    /// A request got no response until the end of the trace.
+    Unanswered {
+        /// The `seq` of the request.
+        seq: i64,
+        /// The command of the request.
+        command: String,
+    },
+    /// The message breaks the lifecycle of the session.
+    Lifecycle(ViolationKind),
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueKind::InvalidFrame(err) => write!(f, "invalid frame: {err}"),
+            IssueKind::InvalidMessage(err) => write!(f, "invalid message: {err}"),
+            IssueKind::BadContent { message, error } => write!(f, "{message}: {error}"),
+            IssueKind::UnknownCommand { command } => write!(f, "unknown command {command}"),
+            IssueKind::UnknownEvent { event } => write!(f, "unknown event {event}"),
+            IssueKind::WrongDirection { command, side } => write!(f, "{command} sent by the {side}"),
+            IssueKind::WrongSeq { side, expected, actual } => {
+                write!(f, "seq {actual} of the {side}, expected {expected}")
+            }
+            IssueKind::UnknownRequest { request_seq } => write!(f, "response to unknown request {request_seq}"),
+            IssueKind::DuplicateResponse { request_seq } => {
+                write!(f, "duplicate response to request {request_seq}")
+            }
+            IssueKind::CommandMismatch {
+                request_seq,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "response of command {actual} to request {request_seq} of command {expected}"
+            ),
+            IssueKind::Unanswered { seq, command } => write!(f, "request {seq} ({command}) got no response"),
+            IssueKind::Lifecycle(violation) => write!(f, "{violation}"),
+        }
+    }
+}
+
+/// The result of checking a trace.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// The number of messages checked.
+    pub messages: usize,
+    /// The issues found, in the order of their lines.
+    pub issues: Vec<Issue>,
+}
+
+impl Report {
+    /// Whether the trace has no issue.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{issue}")?;
+        }
+        write!(f, "{} messages, {} issues", self.messages, self.issues.len())
+    }
+}
+
+/// Checks messages one at a time, in the order they were sent.
+#[derive(Debug, Default)]
+pub struct Checker {
+    session: Session,
+    /// The expected `seq` of the next message of each side.
+    next_seq: HashMap<Side, i64>,
+    /// The requests waiting for a response, by side and `seq`, with their
+    /// line and command.
+    pending: HashMap<(Side, i64), (usize, String)>,
+    /// The requests already answered, by side and `seq`.
+    answered: HashSet<(Side, i64)>,
+    report: Report,
+}
+
+impl Checker {
+    /// Creates a checker of a session which didn't start yet.
+    pub fn new() -> Checker {
+        Checker::default()
+    }
+
+    /// Checks a message starting at `line`, sent by `side`, or by the side
+    /// inferred from the message if `None`.
+    pub fn check(&mut self, line: usize, side: Option<Side>, message: &ProtocolMessage) {
+        self.report.messages += 1;
+        let side = side.unwrap_or_else(|| infer_side(message));
+
+        let seq = message.seq();
+        let expected = self.next_seq.entry(side).or_insert(1);
+        if seq != *expected {
+            let expected = *expected;
+            self.issue(
+                line,
+                IssueKind::WrongSeq {
+                    side,
+                    expected,
+                    actual: seq,
+                },
+            );
+        }
+        self.next_seq.insert(side, seq + 1);
+
+        match message {
+            ProtocolMessage::Request(request) => {
+                let command = &request.command;
+                let direction = match side {
+                    Side::Client => Direction::ClientToAdapter,
+                    Side::Adapter => Direction::AdapterToClient,
+                };
+                if request::direction(command).is_some_and(|d| d != direction) {
+                    let command = command.clone();
+                    self.issue(line, IssueKind::WrongDirection { command, side });
+                }
+                match AnyRequest::from_parts(command.clone(), request.arguments.clone()) {
+                    Ok(AnyRequest::Other { .. }) => {
+                        let command = command.clone();
+                        self.issue(line, IssueKind::UnknownCommand { command });
+                    }
+                    Ok(_) => {}
+                    Err(err) => self.bad_content(line, format!("request {command}"), err),
+                }
+                self.pending.insert((side, seq), (line, command.clone()));
+            }
+            ProtocolMessage::Response(response) => {
+                let key = (side.peer(), response.request_seq);
+                let request_seq = response.request_seq;
+                match self.pending.remove(&key) {
+                    Some((_, command)) => {
+                        self.answered.insert(key);
+                        if command != response.command {
+                            let actual = response.command.clone();
+                            let expected = command.clone();
+                            self.issue(
+                                line,
+                                IssueKind::CommandMismatch {
+                                    request_seq,
+                                    expected,
+                                    actual,
+                                },
+                            );
+                        }
+                        if let Err(err) = AnyResponse::decode(&command, response.clone()) {
+                            self.bad_content(line, format!("response to {command}"), err);
+                        }
+                    }
+                    None if self.answered.contains(&key) => {
+                        self.issue(line, IssueKind::DuplicateResponse { request_seq });
+                    }
+                    None => self.issue(line, IssueKind::UnknownRequest { request_seq }),
+                }
+            }
+            ProtocolMessage::Event(event) => match AnyEvent::from_parts(event.event.clone(), event.body.clone()) {
+                Ok(AnyEvent::Other { .. }) => {
+                    let event = event.event.clone();
+                    self.issue(line, IssueKind::UnknownEvent { event });
+                }
+                Ok(_) => {}
+                Err(err) => self.bad_content(line, format!("event {}", event.event), err),
+            },
+        }
+
+        if let Err(violation) = self.session.observe(side, message) {
+            // Reported above, telling duplicate responses apart.
+            if !matches!(violation.kind, ViolationKind::UnknownRequest { .. }) {
+                self.issue(line, IssueKind::Lifecycle(violation.kind));
+            }
+        }
+    }
+
+    /// Reports a message which could not be parsed.
+    pub fn invalid(&mut self, line: usize, error: impl fmt::Display) {
+        self.report.messages += 1;
+        self.issue(line, IssueKind::InvalidMessage(error.to_string()));
+    }
+
+    /// Ends the trace, and returns the report.
+    pub fn finish(mut self) -> Report {
+        let mut unanswered: Vec<_> = self.pending.drain().collect();
+        unanswered.sort_by_key(|(_, (line, _))| *line);
+        for ((_, seq), (line, command)) in unanswered {
+            self.issue(line, IssueKind::Unanswered { seq, command });
+        }
+        self.report.issues.sort_by_key(|issue| issue.line);
+        self.report
+    }
+
+    fn issue(&mut self, line: usize, kind: IssueKind) {
+        self.report.issues.push(Issue { line, kind });
+    }
+
+    fn bad_content(&mut self, line: usize, message: String, error: Error) {
+        let error = error.to_string();
+        self.issue(line, IssueKind::BadContent { message, error });
+    }
+}
+
+/// Infers the side which sent a message: reverse requests and events are sent
+/// by the adapter, and other requests by the client.
+pub fn infer_side(message: &ProtocolMessage) -> Side {
+    let reverse = |command: &str| request::direction(command) == Some(Direction::AdapterToClient);
+    match message {
+        ProtocolMessage::Request(request) if reverse(&request.command) => Side::Adapter,
+        ProtocolMessage::Request(_) => Side::Client,
+        ProtocolMessage::Response(response) if reverse(&response.command) => Side::Client,
+        ProtocolMessage::Response(_) | ProtocolMessage::Event(_) => Side::Adapter,
+    }
+}
+
+/// Checks a trace, which is a raw stream if it starts with a Content-Length
+/// header, and a JSONL file otherwise.
+pub fn check_trace(input: &[u8]) -> Report {
+    let start = input
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(input.len());
+    let header = &input[start..];
+    if header.len() >= CONTENT_LENGTH.len() && header[..CONTENT_LENGTH.len()].eq_ignore_ascii_case(CONTENT_LENGTH) {
+        return check_stream(input);
+    }
+    match std::str::from_utf8(input) {
+        Ok(input) => check_jsonl(input),
+        Err(err) => {
+            let mut checker = Checker::new();
+            checker.issue(1, IssueKind::InvalidFrame(Error::InvalidUtf8(err).to_string()));
+            checker.finish()
+        }
+    }
+}
+
+const CONTENT_LENGTH: &[u8] = b"Content-Length";
+
+/// A line of a JSONL trace telling the side which sent its message.
+#[derive(Deserialize)]
+struct SidedMessage {
+    from: Side,
+    message: ProtocolMessage,
+}
+
+/// Checks a JSONL trace. Blank lines are skipped.
+pub fn check_jsonl(input: &str) -> Report {
+    let mut checker = Checker::new();
+    for (i, text) in input.lines().enumerate() {
+        let line = i + 1;
+        if text.trim().is_empty() {
+            continue;
+        }
+        let parsed = serde_json::from_str::<serde_json::Value>(text).and_then(|value| {
+            if value.get("from").is_some() {
+                let sided = SidedMessage::deserialize(value)?;
+                Ok((Some(sided.from), sided.message))
+            } else {
+                Ok((None, ProtocolMessage::deserialize(value)?))
+            }
+        });
+        match parsed {
+            Ok((side, message)) => checker.check(line, side, &message),
+            Err(err) => checker.invalid(line, err),
+        }
+    }
+    checker.finish()
+}
+
+/// Checks a raw stream of Content-Length frames. Whitespace between frames
+/// is skipped.
+pub fn check_stream(input: &[u8]) -> Report {
+    let mut checker = Checker::new();
+    let mut rest = input;
+    let mut line = 1;
+    loop {
+        let start = rest.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(rest.len());
+        line += count_lines(&rest[..start]);
+        rest = &rest[start..];
+        if rest.is_empty() {
+            break;
+        }
+        let (body, len) = match split_frame(rest) {
+            Ok(frame) => frame,
+            Err(err) => {
+                checker.issue(line, IssueKind::InvalidFrame(err.to_string()));
+                break;
+            }
+        };
+        match parse_body(body) {
+            Ok(message) => checker.check(line, None, &message),
+            Err(err) => checker.invalid(line, err),
+        }
+        line += count_lines(&rest[..len]);
+        rest = &rest[len..];
+    }
+    checker.finish()
+}
+
+/// Splits the frame at the start of `input`, and returns its body and its
+/// length with the header part.
+fn split_frame(input: &[u8]) -> Result<(&[u8], usize), Error> {
+    let mut content_length = None;
+    let mut pos = 0;
+    loop {
+        let end = input[pos..]
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or(Error::UnexpectedEof)?;
+        let header = &input[pos..pos + end];
+        pos += end + 2;
+        if header.is_empty() {
+            break;
+        }
+        if let Some(length) = parse_header_line(header)? {
+            content_length = Some(length);
+        }
+    }
+    let length = content_length.ok_or(Error::MissingContentLength)?;
+    let body = input.get(pos..pos + length).ok_or(Error::UnexpectedEof)?;
+    Ok((body, pos + length))
+}
+
+fn count_lines(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|&&b| b == b'\n').count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::MessageWriter;
+    use crate::{Event, Request, Response};
+
+    fn session() -> Vec<ProtocolMessage> {
+        let null = serde_json::Value::Null;
+        let initialize = serde_json::json!({ "adapterID": "test" });
+        vec![
+            ProtocolMessage::Request(Request::new(1, "initialize".to_owned(), initialize)),
+            ProtocolMessage::Response(Response::new(
+                1,
+                1,
+                "initialize".to_owned(),
+                true,
+                None,
+                Some(null.clone()),
+            )),
+            ProtocolMessage::Event(Event::new(2, "initialized".to_owned(), null.clone())),
+            ProtocolMessage::Request(Request::new(2, "launch".to_owned(), serde_json::json!({}))),
+            ProtocolMessage::Request(Request::new(
+                3,
+                "runInTerminal".to_owned(),
+                serde_json::json!({ "args": ["ls"], "cwd": "/" }),
+            )),
+            ProtocolMessage::Response(Response::new(
+                3,
+                3,
+                "runInTerminal".to_owned(),
+                true,
+                None,
+                Some(serde_json::json!({})),
+            )),
+            ProtocolMessage::Response(Response::new(4, 2, "launch".to_owned(), true, None, None::<()>)),
+            ProtocolMessage::Request(Request::new(4, "threads".to_owned(), null)),
+            ProtocolMessage::Response(Response::new(
+                5,
+                4,
+                "threads".to_owned(),
+                true,
+                None,
+                Some(serde_json::json!({ "threads": [] })),
+            )),
+        ]
+    }
+
+    fn jsonl(messages: &[ProtocolMessage]) -> String {
+        messages
+            .iter()
+            .map(|message| serde_json::to_string(message).unwrap() + "\n")
+            .collect()
+    }
+
+    #[test]
+    fn test_conforming_trace() {
+        let report = check_trace(jsonl(&session()).as_bytes());
+        assert!(report.is_ok(), "{report}");
+        assert_eq!(report.messages, 9);
+    }
+
+    #[test]
+    fn test_stream_trace() {
+        let mut messages = session();
+        messages.remove(6);
+        let mut writer = MessageWriter::new(Vec::new());
+        for message in &messages {
+            writer.write_message(message).unwrap();
+        }
+        let mut input = writer.into_inner();
+        input.extend_from_slice(b"Content-Length: 5\r\n\r\n{\"x\":");
+
+        let report = check_trace(&input);
+        let issues: Vec<_> = report.issues.iter().map(|issue| issue.to_string()).collect();
+        // Each header part spans two lines, and bodies have no newline.
+        assert_eq!(
+            issues,
+            [
+                "line 7: request 2 (launch) got no response",
+                "line 15: seq 5 of the adapter, expected 4",
+                "line 17: invalid message: body is not a valid message: EOF while parsing a value at line 1 column 5",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_issues() {
+        let trace = r#"
+{"seq":1,"type":"request","command":"initialize","arguments":{}}
+{"seq":1,"type":"response","request_seq":1,"success":true,"command":"initialize"}
+{"seq":2,"type":"response","request_seq":1,"success":true,"command":"initialize"}
+{"seq":2,"type":"request","command":"stackTrace","arguments":{"threadId":1}}
+{"seq":4,"type":"response","request_seq":2,"success":true,"command":"threads","body":{}}
+{"seq":5,"type":"event","event":"madeUp"}
+{"from":"client","message":{"seq":3,"type":"request","command":"runInTerminal","arguments":{"args":[],"cwd":"/"}}}
+not json
+"#;
+        let report = check_jsonl(trace);
+        let issues: Vec<_> = report.issues.iter().map(|issue| issue.to_string()).collect();
+        assert_eq!(
+            issues,
+            [
+                "line 2: request initialize: bad arguments: missing field `adapterID`",
+                "line 4: duplicate response to request 1",
+                "line 5: stackTrace sent before launch or attach",
+                "line 6: seq 4 of the adapter, expected 3",
+                "line 6: response of command threads to request 2 of command stackTrace",
+                "line 6: response to stackTrace: bad body: missing field `stackFrames`",
+                "line 7: unknown event madeUp",
+                "line 8: runInTerminal sent by the client",
+                "line 8: runInTerminal sent before launch or attach",
+                "line 8: request 3 (runInTerminal) got no response",
+                "line 9: invalid message: expected ident at line 1 column 2",
+            ]
+        );
+        assert_eq!(report.messages, 8);
+    }
+}
@@ -0,0 +1,406 @@
+//! A connection shared by the client and the server, which both send
+//! requests and handle the requests of the other side.
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Mutex;
+
+use bytes::BytesMut;
+use futures_util::future::{select, Either};
+use futures_util::{SinkExt, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::oneshot;
+use tokio_util::codec::{Decoder, FramedRead, FramedWrite};
+
+use crate::codec::tokio::{message_sink, DapCodec};
+use crate::codec::{is_recoverable, parse_body};
+use crate::server::MAX_QUEUED_REQUESTS;
+use crate::{Error, ErrorResponse, Event, IRequest, ProtocolMessage, Request, RequestError, Response};
+
+/// Handles the requests received on a connection, implemented by the
+/// generated dispatchers.
+pub(crate) trait Dispatch {
+    /// Handles `request`, returning the body of its response.
+    fn dispatch(&mut self, request: &Request) -> impl Future<Output = Result<serde_json::Value, RequestError>> + Send;
+}
+
+// The helpers return the same error as handlers, which is not worth boxing.
+
+/// Parses the arguments of a request handled as a request of type `R`.
+#[allow(clippy::result_large_err)]
+pub(crate) fn arguments<R: IRequest>(request: &Request) -> Result<R::Arguments, RequestError> {
+    Ok(crate::arguments_from_value(&request.arguments)?)
+}
+
+/// Serializes the result of a handler of a request of type `R`.
+#[allow(clippy::result_large_err)]
+pub(crate) fn respond<R: IRequest>(
+    result: Result<R::Response, RequestError>,
+) -> Result<serde_json::Value, RequestError> {
+    Ok(serde_json::to_value(result?).map_err(Error::Serialize)?)
+}
+
+/// Writes messages numbered by `seq`, and keeps track of the requests sent
+/// until their responses arrive.
+pub(crate) struct Connection {
+    writer: tokio::sync::Mutex<Writer>,
+    /// The requests waiting for a response, by `seq`, or `None` once the
+    /// connection is closed.
+    pending: Mutex<Option<HashMap<i64, ResponseSender>>>,
+    /// Called with the errors of malformed messages reaching no request.
+    on_malformed: Mutex<Option<OnMalformed>>,
+}
+
+/// Passes its response, or the error of a malformed one, to a request.
+type ResponseSender = oneshot::Sender<Result<Response, Error>>;
+
+type OnMalformed = Box<dyn FnMut(Error) + Send>;
+
+/// A request answered with a failure without being handled.
+type Refusal = (Request, RequestError);
+
+struct Writer {
+    /// The `seq` of the last message sent.
+    seq: i64,
+    sink: FramedWrite<Box<dyn AsyncWrite + Send + Unpin>, DapCodec>,
+}
+
+impl Connection {
+    pub(crate) fn new<W: AsyncWrite + Send + Unpin + 'static>(writer: W) -> Connection {
+        let writer: Box<dyn AsyncWrite + Send + Unpin> = Box::new(writer);
+        Connection {
+            writer: tokio::sync::Mutex::new(Writer {
+                seq: 0,
+                sink: message_sink(writer),
+            }),
+            pending: Mutex::new(Some(HashMap::new())),
+            on_malformed: Mutex::new(None),
+        }
+    }
+
+    /// Sets the function called with the errors of malformed messages which
+    /// can't be passed to a request.
+    pub(crate) fn set_on_malformed(&self, on_malformed: impl FnMut(Error) + Send + 'static) {
+        *self.on_malformed.lock().unwrap() = Some(Box::new(on_malformed));
+    }
+
+    /// Sends the message built from the next `seq`.
+    ///
+    /// The `seq` is taken under the lock of the writer, so that messages are
+    /// written in the order of their `seq`.
+    pub(crate) async fn send(&self, message: impl FnOnce(i64) -> ProtocolMessage) -> Result<(), Error> {
+        let mut writer = self.writer.lock().await;
+        writer.seq += 1;
+        let message = message(writer.seq);
+        writer.sink.send(message).await
+    }
+
+    /// Sends a request and waits for its response, successful or not.
+    pub(crate) async fn request(&self, command: String, arguments: serde_json::Value) -> Result<Response, Error> {
+        let (tx, rx) = oneshot::channel();
+        let mut writer = self.writer.lock().await;
+        let seq = writer.seq + 1;
+        match self.pending.lock().unwrap().as_mut() {
+            Some(pending) => pending.insert(seq, tx),
+            None => return Err(Error::ConnectionClosed),
+        };
+        let _guard = PendingGuard { connection: self, seq };
+        writer.seq = seq;
+        let request = Request {
+            seq,
+            command,
+            arguments,
+        };
+        writer.sink.send(ProtocolMessage::Request(request)).await?;
+        drop(writer);
+
+        rx.await.map_err(|_| Error::ConnectionClosed)?
+    }
+
+    /// Sends a request of type `R` and waits for its response.
+    ///
+    /// A failed response is returned as [`Error::Response`].
+    pub(crate) async fn typed_request<R: IRequest>(&self, arguments: R::Arguments) -> Result<R::Response, Error> {
+        let arguments = serde_json::to_value(arguments).map_err(Error::Serialize)?;
+        let response = self.request(R::COMMAND.to_owned(), arguments).await?;
+        if let Some(err) = response.parse_error() {
+            return Err(Error::Response(Box::new(err)));
+        }
+        crate::body_from_value(&response.body.unwrap_or_default())
+    }
+
+    /// Passes a response to the request waiting for it.
+    ///
+    /// Responses to requests no longer waited for are dropped.
+    pub(crate) fn resolve(&self, response: Response) {
+        let tx = self
+            .pending
+            .lock()
+            .unwrap()
+            .as_mut()
+            .and_then(|p| p.remove(&response.request_seq));
+        if let Some(tx) = tx {
+            let _ = tx.send(Ok(response));
+        }
+    }
+
+    /// Fails the request waiting for the response `request_seq` with `err`,
+    /// returning `err` back if no request waits for it.
+    fn fail(&self, request_seq: i64, err: Error) -> Result<(), Error> {
+        let tx = self
+            .pending
+            .lock()
+            .unwrap()
+            .as_mut()
+            .and_then(|p| p.remove(&request_seq));
+        match tx {
+            Some(tx) => {
+                let _ = tx.send(Err(err));
+                Ok(())
+            }
+            None => Err(err),
+        }
+    }
+
+    /// Fails the requests waiting for a response, and the ones sent later,
+    /// with [`Error::ConnectionClosed`].
+    pub(crate) fn close(&self) {
+        self.pending.lock().unwrap().take();
+    }
+
+    /// Reads messages from `reader` until it is closed or fails, then closes
+    /// the connection.
+    ///
+    /// Requests are passed to `handler` one at a time, in the order they
+    /// arrive, and answered with the response it returns. Reading goes on
+    /// while a request is handled, so that the handler can itself send
+    /// requests and wait for their responses. Past [`MAX_QUEUED_REQUESTS`]
+    /// queued requests, more requests are refused.
+    ///
+    /// Malformed requests are refused if they have a `seq`, and malformed
+    /// responses fail the request they answer if they have a `request_seq`.
+    /// The errors of other malformed messages are passed to the function set
+    /// by [`Connection::set_on_malformed`].
+    pub(crate) async fn serve<R, D>(
+        &self,
+        reader: R,
+        handler: &mut D,
+        mut on_event: impl FnMut(Event),
+    ) -> Result<(), Error>
+    where
+        R: AsyncRead + Unpin,
+        D: Dispatch,
+    {
+        let mut stream = FramedRead::new(reader, Recovering(DapCodec::new()));
+        let mut requests = VecDeque::new();
+        // Sent before reading further, so that there is at most one.
+        let mut refused = None;
+        // The result of reading, once `reader` is closed or fails.
+        let mut end = None;
+        let result = 'serve: loop {
+            if let Some(refusal) = refused.take() {
+                if let Err(err) = self.send(|seq| refuse(seq, refusal)).await {
+                    break Err(err);
+                }
+                continue;
+            }
+            let Some(request) = requests.pop_front() else {
+                if let Some(result) = end.take() {
+                    break result;
+                }
+                end = self.receive(stream.next().await, &mut requests, &mut refused, &mut on_event);
+                continue;
+            };
+
+            let result = {
+                let mut dispatch = pin!(handler.dispatch(&request));
+                loop {
+                    if let Some(refusal) = refused.take() {
+                        // Sent while the handler goes on, as it may hold the writer.
+                        let send = pin!(self.send(|seq| refuse(seq, refusal)));
+                        match select(dispatch.as_mut(), send).await {
+                            Either::Left((result, send)) => match send.await {
+                                Ok(()) => break result,
+                                Err(err) => break 'serve Err(err),
+                            },
+                            Either::Right((Ok(()), _)) => continue,
+                            Either::Right((Err(err), _)) => break 'serve Err(err),
+                        }
+                    }
+                    if end.is_some() {
+                        break dispatch.await;
+                    }
+                    match select(dispatch.as_mut(), stream.next()).await {
+                        Either::Left((result, _)) => break result,
+                        Either::Right((msg, _)) => {
+                            end = self.receive(msg, &mut requests, &mut refused, &mut on_event);
+                        }
+                    }
+                }
+            };
+            let response = |seq| ProtocolMessage::Response(response(seq, request, result));
+            if let Err(err) = self.send(response).await {
+                break Err(err);
+            }
+        };
+        self.close();
+        result
+    }
+
+    /// Handles a message read by [`Connection::serve`], queueing requests, or
+    /// refusing them if the queue is full.
+    ///
+    /// Returns the result of reading once the reader is closed or fails, in
+    /// which case the connection is closed, as requests sent by the handler
+    /// will never be answered.
+    fn receive(
+        &self,
+        msg: Option<Result<Result<ProtocolMessage, Malformed>, Error>>,
+        requests: &mut VecDeque<Request>,
+        refused: &mut Option<Refusal>,
+        on_event: &mut impl FnMut(Event),
+    ) -> Option<Result<(), Error>> {
+        let end = match msg {
+            Some(Ok(Ok(ProtocolMessage::Request(request)))) => {
+                if requests.len() < MAX_QUEUED_REQUESTS {
+                    requests.push_back(request);
+                } else {
+                    *refused = Some((request, RequestError::new("too many requests")));
+                }
+                return None;
+            }
+            Some(Ok(Ok(ProtocolMessage::Response(response)))) => {
+                self.resolve(response);
+                return None;
+            }
+            Some(Ok(Ok(ProtocolMessage::Event(event)))) => {
+                on_event(event);
+                return None;
+            }
+            Some(Ok(Err(malformed))) => {
+                *refused = self.malformed(malformed);
+                return None;
+            }
+            Some(Err(err)) => Err(err),
+            None => Ok(()),
+        };
+        self.close();
+        Some(end)
+    }
+
+    /// Handles a malformed message, returning the refusal to send if it is a
+    /// request.
+    fn malformed(&self, Malformed { error, body }: Malformed) -> Option<Refusal> {
+        let field = |key: &str| body.as_ref().and_then(|body| body.get(key));
+        let seq = |key: &str| field(key).and_then(serde_json::Value::as_i64);
+        let error = match (field("type").and_then(serde_json::Value::as_str), seq("seq")) {
+            (Some("request"), Some(seq)) => {
+                let command = field("command").and_then(serde_json::Value::as_str).unwrap_or_default();
+                let request = Request {
+                    seq,
+                    command: command.to_owned(),
+                    arguments: serde_json::Value::Null,
+                };
+                return Some((request, error.into()));
+            }
+            (Some("response"), _) => match seq("request_seq") {
+                Some(request_seq) => match self.fail(request_seq, error) {
+                    Ok(()) => return None,
+                    Err(error) => error,
+                },
+                None => error,
+            },
+            _ => error,
+        };
+        if let Some(on_malformed) = self.on_malformed.lock().unwrap().as_mut() {
+            on_malformed(error);
+        }
+        None
+    }
+}
+
+/// Builds the failed response to a refused request.
+fn refuse(seq: i64, (request, err): Refusal) -> ProtocolMessage {
+    ProtocolMessage::Response(response(seq, request, Err(err)))
+}
+
+/// A frame whose body is not a valid message.
+struct Malformed {
+    error: Error,
+    /// The body, if it is JSON.
+    body: Option<serde_json::Value>,
+}
+
+/// Decodes messages as [`DapCodec`] does, but yields the frames read as a
+/// whole whose body is invalid as items, after which reading goes on.
+///
+/// [`FramedRead`] ends the stream after a decoding error, until more bytes
+/// are read, even if frames are left in its buffer.
+struct Recovering(DapCodec);
+
+impl Recovering {
+    fn parse(body: Option<BytesMut>) -> Option<Result<ProtocolMessage, Malformed>> {
+        let body = body?;
+        Some(parse_body(&body).map_err(|error| {
+            debug_assert!(is_recoverable(&error));
+            Malformed {
+                error,
+                body: serde_json::from_slice(&body).ok(),
+            }
+        }))
+    }
+}
+
+impl Decoder for Recovering {
+    type Item = Result<ProtocolMessage, Malformed>;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(Recovering::parse(self.0.decode_body(src)?))
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(Recovering::parse(self.0.decode_body_eof(buf)?))
+    }
+}
+
+/// Builds the response to `request`.
+fn response(seq: i64, request: Request, result: Result<serde_json::Value, RequestError>) -> Response {
+    match result {
+        Ok(body) => Response {
+            seq,
+            request_seq: request.seq,
+            success: true,
+            command: request.command,
+            message: None,
+            body: (!body.is_null()).then_some(body),
+        },
+        Err(err) => Response {
+            seq,
+            request_seq: request.seq,
+            success: false,
+            command: request.command,
+            message: err.message,
+            body: err.detail.map(|error| {
+                let body = ErrorResponse { error: Some(error) };
+                serde_json::to_value(body).expect("failed to serialize error response")
+            }),
+        },
+    }
+}
+
+/// Forgets a pending request when its future is dropped, e.g. cancelled
+/// before the response arrived.
+struct PendingGuard<'a> {
+    connection: &'a Connection,
+    seq: i64,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(pending) = self.connection.pending.lock().unwrap().as_mut() {
+            pending.remove(&self.seq);
+        }
+    }
+}
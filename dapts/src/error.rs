@@ -0,0 +1,106 @@
+use std::fmt;
+use std::io;
+
+/// The error type shared by the fallible APIs of this crate.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The underlying stream failed.
+    Io(io::Error),
+    /// The stream ended in the middle of a header or body.
+    UnexpectedEof,
+    /// A header line is not of the form `name: value\r\n`.
+    InvalidHeader(String),
+    /// The header part has no `Content-Length` field.
+    MissingContentLength,
+    /// The `Content-Length` value is not a valid length.
+    InvalidContentLength(String),
+    /// The announced body is larger than the configured limit.
+    BodyTooLarge {
+        /// The announced body length.
+        length: usize,
+        /// The configured limit.
+        limit: usize,
+    },
+    /// The body is not valid UTF-8.
+    InvalidUtf8(std::str::Utf8Error),
+    /// The body is not a valid protocol message.
+    InvalidMessage(serde_json::Error),
+    /// A value could not be serialized to JSON.
+    Serialize(serde_json::Error),
+    /// The request is not of the expected command.
+    WrongCommand {
+        /// The command of the expected request type.
+        expected: &'static str,
+        /// The command of the request.
+        actual: String,
+    },
+    /// The arguments of a request do not match the type of its command.
+    BadArguments(serde_json::Error),
+    /// The body of an event or response does not match the type of its event
+    /// or command.
+    BadBody(serde_json::Error),
+    /// The peer answered a request with a failure.
+    Response(Box<crate::ResponseError>),
+    /// The connection closed before the peer answered a request.
+    ConnectionClosed,
+    /// The peer did not declare the capability a request requires.
+    Unsupported {
+        /// The command of the request.
+        command: &'static str,
+        /// The capability required by the request.
+        capability: &'static str,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "io error: {err}"),
+            Error::UnexpectedEof => f.write_str("unexpected end of stream"),
+            Error::InvalidHeader(line) => write!(f, "invalid header line: {line:?}"),
+            Error::MissingContentLength => f.write_str("missing Content-Length header"),
+            Error::InvalidContentLength(value) => write!(f, "invalid Content-Length: {value:?}"),
+            Error::BodyTooLarge { length, limit } => {
+                write!(f, "body of {length} bytes exceeds the limit of {limit} bytes")
+            }
+            Error::InvalidUtf8(err) => write!(f, "body is not valid utf-8: {err}"),
+            Error::InvalidMessage(err) => write!(f, "body is not a valid message: {err}"),
+            Error::Serialize(err) => write!(f, "failed to serialize: {err}"),
+            Error::WrongCommand { expected, actual } => {
+                write!(f, "expected command {expected:?}, got {actual:?}")
+            }
+            Error::BadArguments(err) => write!(f, "bad arguments: {err}"),
+            Error::BadBody(err) => write!(f, "bad body: {err}"),
+            Error::Response(err) => write!(f, "{err}"),
+            Error::ConnectionClosed => f.write_str("connection closed"),
+            Error::Unsupported { command, capability } => {
+                write!(f, "{command} requires the capability {capability}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::InvalidUtf8(err) => Some(err),
+            Error::Response(err) => Some(err),
+            Error::InvalidMessage(err) | Error::Serialize(err) | Error::BadArguments(err) | Error::BadBody(err) => {
+                Some(err)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            Error::UnexpectedEof
+        } else {
+            Error::Io(err)
+        }
+    }
+}
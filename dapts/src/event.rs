@@ -195,3 +195,183 @@ impl IEvent for Thread {
     const EVENT: &'static str = "thread";
     type Body = crate::ThreadEvent;
 }
+
+/// Any event, keyed by its `event` field.
+///
+/// Events whose `event` is unknown to this crate are kept as [`AnyEvent::Other`].
+#[derive(Debug, Clone)]
+pub enum AnyEvent {
+    /// See [`Breakpoint`].
+    Breakpoint(crate::BreakpointEvent),
+    /// See [`Capabilities`].
+    Capabilities(crate::CapabilitiesEvent),
+    /// See [`Continued`].
+    Continued(crate::ContinuedEvent),
+    /// See [`Exited`].
+    Exited(crate::ExitedEvent),
+    /// See [`Initialized`].
+    Initialized(Option<crate::Capabilities>),
+    /// See [`Invalidated`].
+    Invalidated(crate::InvalidatedEvent),
+    /// See [`LoadedSource`].
+    LoadedSource(crate::LoadedSourceEvent),
+    /// See [`Memory`].
+    Memory(crate::MemoryEvent),
+    /// See [`Module`].
+    Module(crate::ModuleEvent),
+    /// See [`Output`].
+    Output(crate::OutputEvent),
+    /// See [`Process`].
+    Process(crate::ProcessEvent),
+    /// See [`ProgressEnd`].
+    ProgressEnd(crate::ProgressEndEvent),
+    /// See [`ProgressStart`].
+    ProgressStart(crate::ProgressStartEvent),
+    /// See [`ProgressUpdate`].
+    ProgressUpdate(crate::ProgressUpdateEvent),
+    /// See [`Stopped`].
+    Stopped(crate::StoppedEvent),
+    /// See [`Terminated`].
+    Terminated(crate::TerminatedEvent),
+    /// See [`Thread`].
+    Thread(crate::ThreadEvent),
+    /// An event whose `event` is unknown to this crate.
+    Other {
+        /// Type of event.
+        event: String,
+        /// Event-specific information.
+        body: serde_json::Value,
+    },
+}
+
+impl AnyEvent {
+    /// Parses an event from its type and body.
+    pub fn from_parts(event: String, body: serde_json::Value) -> Result<AnyEvent, crate::Error> {
+        Ok(match event.as_str() {
+            Breakpoint::EVENT => AnyEvent::Breakpoint(crate::body_from_value(&body)?),
+            Capabilities::EVENT => AnyEvent::Capabilities(crate::body_from_value(&body)?),
+            Continued::EVENT => AnyEvent::Continued(crate::body_from_value(&body)?),
+            Exited::EVENT => AnyEvent::Exited(crate::body_from_value(&body)?),
+            Initialized::EVENT => AnyEvent::Initialized(crate::body_from_value(&body)?),
+            Invalidated::EVENT => AnyEvent::Invalidated(crate::body_from_value(&body)?),
+            LoadedSource::EVENT => AnyEvent::LoadedSource(crate::body_from_value(&body)?),
+            Memory::EVENT => AnyEvent::Memory(crate::body_from_value(&body)?),
+            Module::EVENT => AnyEvent::Module(crate::body_from_value(&body)?),
+            Output::EVENT => AnyEvent::Output(crate::body_from_value(&body)?),
+            Process::EVENT => AnyEvent::Process(crate::body_from_value(&body)?),
+            ProgressEnd::EVENT => AnyEvent::ProgressEnd(crate::body_from_value(&body)?),
+            ProgressStart::EVENT => AnyEvent::ProgressStart(crate::body_from_value(&body)?),
+            ProgressUpdate::EVENT => AnyEvent::ProgressUpdate(crate::body_from_value(&body)?),
+            Stopped::EVENT => AnyEvent::Stopped(crate::body_from_value(&body)?),
+            Terminated::EVENT => AnyEvent::Terminated(crate::body_from_value(&body)?),
+            Thread::EVENT => AnyEvent::Thread(crate::body_from_value(&body)?),
+            _ => AnyEvent::Other { event, body },
+        })
+    }
+
+    /// Returns the type of the event.
+    pub fn event(&self) -> &str {
+        match self {
+            AnyEvent::Breakpoint(_) => Breakpoint::EVENT,
+            AnyEvent::Capabilities(_) => Capabilities::EVENT,
+            AnyEvent::Continued(_) => Continued::EVENT,
+            AnyEvent::Exited(_) => Exited::EVENT,
+            AnyEvent::Initialized(_) => Initialized::EVENT,
+            AnyEvent::Invalidated(_) => Invalidated::EVENT,
+            AnyEvent::LoadedSource(_) => LoadedSource::EVENT,
+            AnyEvent::Memory(_) => Memory::EVENT,
+            AnyEvent::Module(_) => Module::EVENT,
+            AnyEvent::Output(_) => Output::EVENT,
+            AnyEvent::Process(_) => Process::EVENT,
+            AnyEvent::ProgressEnd(_) => ProgressEnd::EVENT,
+            AnyEvent::ProgressStart(_) => ProgressStart::EVENT,
+            AnyEvent::ProgressUpdate(_) => ProgressUpdate::EVENT,
+            AnyEvent::Stopped(_) => Stopped::EVENT,
+            AnyEvent::Terminated(_) => Terminated::EVENT,
+            AnyEvent::Thread(_) => Thread::EVENT,
+            AnyEvent::Other { event, .. } => event,
+        }
+    }
+
+    /// Converts into an event with the given sequence number.
+    pub fn into_event(self, seq: i64) -> crate::Event {
+        match self {
+            AnyEvent::Breakpoint(v) => crate::Event::from_typed::<Breakpoint>(seq, v),
+            AnyEvent::Capabilities(v) => crate::Event::from_typed::<Capabilities>(seq, v),
+            AnyEvent::Continued(v) => crate::Event::from_typed::<Continued>(seq, v),
+            AnyEvent::Exited(v) => crate::Event::from_typed::<Exited>(seq, v),
+            AnyEvent::Initialized(v) => crate::Event::from_typed::<Initialized>(seq, v),
+            AnyEvent::Invalidated(v) => crate::Event::from_typed::<Invalidated>(seq, v),
+            AnyEvent::LoadedSource(v) => crate::Event::from_typed::<LoadedSource>(seq, v),
+            AnyEvent::Memory(v) => crate::Event::from_typed::<Memory>(seq, v),
+            AnyEvent::Module(v) => crate::Event::from_typed::<Module>(seq, v),
+            AnyEvent::Output(v) => crate::Event::from_typed::<Output>(seq, v),
+            AnyEvent::Process(v) => crate::Event::from_typed::<Process>(seq, v),
+            AnyEvent::ProgressEnd(v) => crate::Event::from_typed::<ProgressEnd>(seq, v),
+            AnyEvent::ProgressStart(v) => crate::Event::from_typed::<ProgressStart>(seq, v),
+            AnyEvent::ProgressUpdate(v) => crate::Event::from_typed::<ProgressUpdate>(seq, v),
+            AnyEvent::Stopped(v) => crate::Event::from_typed::<Stopped>(seq, v),
+            AnyEvent::Terminated(v) => crate::Event::from_typed::<Terminated>(seq, v),
+            AnyEvent::Thread(v) => crate::Event::from_typed::<Thread>(seq, v),
+            AnyEvent::Other { event, body } => crate::Event::new(seq, event, body),
+        }
+    }
+}
+
+impl TryFrom<crate::Event> for AnyEvent {
+    type Error = crate::Error;
+
+    fn try_from(m: crate::Event) -> Result<Self, Self::Error> {
+        AnyEvent::from_parts(m.event, m.body)
+    }
+}
+
+impl From<AnyEvent> for crate::Event {
+    /// Converts with a zero sequence number, which is expected to be assigned
+    /// when the message is sent.
+    fn from(m: AnyEvent) -> Self {
+        m.into_event(0)
+    }
+}
+
+impl serde::Serialize for AnyEvent {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const TAG: (&str, &str) = ("event", "body");
+        match self {
+            AnyEvent::Breakpoint(v) => crate::serialize_tagged(serializer, TAG, Breakpoint::EVENT, Some(v)),
+            AnyEvent::Capabilities(v) => crate::serialize_tagged(serializer, TAG, Capabilities::EVENT, Some(v)),
+            AnyEvent::Continued(v) => crate::serialize_tagged(serializer, TAG, Continued::EVENT, Some(v)),
+            AnyEvent::Exited(v) => crate::serialize_tagged(serializer, TAG, Exited::EVENT, Some(v)),
+            AnyEvent::Initialized(v) => crate::serialize_tagged(serializer, TAG, Initialized::EVENT, Some(v)),
+            AnyEvent::Invalidated(v) => crate::serialize_tagged(serializer, TAG, Invalidated::EVENT, Some(v)),
+            AnyEvent::LoadedSource(v) => crate::serialize_tagged(serializer, TAG, LoadedSource::EVENT, Some(v)),
+            AnyEvent::Memory(v) => crate::serialize_tagged(serializer, TAG, Memory::EVENT, Some(v)),
+            AnyEvent::Module(v) => crate::serialize_tagged(serializer, TAG, Module::EVENT, Some(v)),
+            AnyEvent::Output(v) => crate::serialize_tagged(serializer, TAG, Output::EVENT, Some(v)),
+            AnyEvent::Process(v) => crate::serialize_tagged(serializer, TAG, Process::EVENT, Some(v)),
+            AnyEvent::ProgressEnd(v) => crate::serialize_tagged(serializer, TAG, ProgressEnd::EVENT, Some(v)),
+            AnyEvent::ProgressStart(v) => crate::serialize_tagged(serializer, TAG, ProgressStart::EVENT, Some(v)),
+            AnyEvent::ProgressUpdate(v) => crate::serialize_tagged(serializer, TAG, ProgressUpdate::EVENT, Some(v)),
+            AnyEvent::Stopped(v) => crate::serialize_tagged(serializer, TAG, Stopped::EVENT, Some(v)),
+            AnyEvent::Terminated(v) => crate::serialize_tagged(serializer, TAG, Terminated::EVENT, Some(v)),
+            AnyEvent::Thread(v) => crate::serialize_tagged(serializer, TAG, Thread::EVENT, Some(v)),
+            AnyEvent::Other { event, body } => {
+                crate::serialize_tagged(serializer, TAG, event, Some(body).filter(|v| !v.is_null()))
+            }
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for AnyEvent {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Parts {
+            event: String,
+            #[serde(default)]
+            body: serde_json::Value,
+        }
+
+        let Parts { event, body } = Parts::deserialize(deserializer)?;
+        AnyEvent::from_parts(event, body).map_err(serde::de::Error::custom)
+    }
+}
@@ -2,10 +2,28 @@
 #![allow(rustdoc::bare_urls)]
 #![allow(rustdoc::invalid_html_tags)]
 
+mod capabilities;
+#[cfg(feature = "checksums")]
+pub mod checksum;
+#[cfg(feature = "tokio")]
+pub mod client;
+pub mod codec;
+pub mod conformance;
+#[cfg(feature = "tokio")]
+mod connection;
+mod error;
 pub mod event;
+mod message;
+pub mod path;
+pub mod position;
 pub mod request;
+#[cfg(feature = "tokio")]
+pub mod server;
+pub mod session;
 mod types;
 
+pub use crate::error::Error;
+pub use crate::message::{RequestError, ResponseError};
 pub use crate::types::*;
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -14,10 +32,35 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 /// types.
 pub trait IRequest {
     const COMMAND: &'static str;
+    /// Which side of the connection sends the request, the client unless
+    /// stated otherwise.
+    const DIRECTION: Direction = Direction::ClientToAdapter;
+    /// The capability which must be true for the request to be sent, if any.
+    ///
+    /// It is a field of [`Capabilities`] for requests sent by the client, and
+    /// of [`InitializeRequestArguments`] for reverse requests. See
+    /// [`Capabilities::check`] and [`InitializeRequestArguments::check`].
+    const REQUIRED_CAPABILITY: Option<&'static str> = None;
     type Arguments: DeserializeOwned + Serialize + Send + Sync + 'static;
     type Response: DeserializeOwned + Serialize + Send + Sync + 'static;
 }
 
+/// The direction in which a request is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Sent by the client to the debug adapter.
+    ClientToAdapter,
+    /// Sent by the debug adapter to the client, also known as a reverse
+    /// request.
+    AdapterToClient,
+}
+
+/// A request sent by the client to the debug adapter.
+pub trait ClientToAdapter: IRequest {}
+
+/// A reverse request, sent by the debug adapter to the client.
+pub trait AdapterToClient: IRequest {}
+
 /// Event is an event, with associated name and body type.
 pub trait IEvent {
     const EVENT: &'static str;
@@ -49,28 +92,68 @@ pub struct Request {
 
 impl Request {
     /// Creates a new request.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `arguments` fails to serialize, see [`Request::try_new`].
     pub fn new(seq: i64, command: String, arguments: impl serde::Serialize) -> Request {
-        Request {
+        Request::try_new(seq, command, arguments).expect("failed to serialize request arguments")
+    }
+
+    /// Creates a new request, failing if `arguments` fails to serialize.
+    pub fn try_new(seq: i64, command: String, arguments: impl serde::Serialize) -> Result<Request, Error> {
+        Ok(Request {
             seq,
             command,
-            arguments: serde_json::to_value(arguments).unwrap(),
+            arguments: serde_json::to_value(arguments).map_err(Error::Serialize)?,
+        })
+    }
+
+    /// Creates a new request of type `R`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `arguments` fails to serialize, see [`Request::try_from_typed`].
+    pub fn from_typed<R: IRequest>(seq: i64, arguments: R::Arguments) -> Request {
+        Request::try_from_typed::<R>(seq, arguments).expect("failed to serialize request arguments")
+    }
+
+    /// Creates a new request of type `R`, failing if `arguments` fails to
+    /// serialize.
+    pub fn try_from_typed<R: IRequest>(seq: i64, arguments: R::Arguments) -> Result<Request, Error> {
+        Request::try_new(seq, R::COMMAND.to_owned(), arguments)
+    }
+
+    /// Parses the arguments of the request as a request of type `R`.
+    pub fn parse<R: IRequest>(&self) -> Result<R::Arguments, Error> {
+        if self.command != R::COMMAND {
+            return Err(Error::WrongCommand {
+                expected: R::COMMAND,
+                actual: self.command.clone(),
+            });
         }
+        arguments_from_value(&self.arguments)
     }
 }
 
 /// Represents response to the client.
 ///
-/// The command field (which is a string) is used as a tag in the ResponseBody
-/// enum, so users of this crate will control it by selecting the appropriate
-/// enum variant for the body.
+/// The `command` field echoes the command of the request being answered. The
+/// typed constructors [`Response::success`] and [`Response::error`] fill it
+/// from [`IRequest::COMMAND`].
 ///
-/// There is also no separate `ErrorResponse` struct. Instead, `Error` is just a
-/// variant of the ResponseBody enum.
+/// There is also no separate `ErrorResponse` struct. Instead, an error is a
+/// response with `success` set to false and an optional structured `Message`
+/// in the body.
 ///
 /// Specification: [Response](https://microsoft.github.io/debug-adapter-protocol/specification#Base_Protocol_Response)
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Response {
+    /// Sequence number of the message (also known as message ID).
+    ///
+    /// See [`Request::seq`] for details.
+    pub seq: i64,
     /// Sequence number of the corresponding request.
     #[serde(rename = "request_seq")]
     pub request_seq: i64,
@@ -81,6 +164,8 @@ pub struct Response {
     /// short form and the `body` may contain additional information (see
     /// `ErrorResponse.body.error`).
     pub success: bool,
+    /// The command requested.
+    pub command: String,
     /// Contains the raw error in short form if `success` is false.
     /// This raw error might be interpreted by the client and is not shown in
     /// the UI.
@@ -98,45 +183,129 @@ pub struct Response {
 
 impl Response {
     /// Creates a new response.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `body` fails to serialize, see [`Response::try_new`].
     pub fn new(
+        seq: i64,
         request_seq: i64,
+        command: String,
         success: bool,
         message: Option<String>,
         body: Option<impl serde::Serialize>,
     ) -> Response {
-        Response {
+        Response::try_new(seq, request_seq, command, success, message, body).expect("failed to serialize response body")
+    }
+
+    /// Creates a new response, failing if `body` fails to serialize.
+    pub fn try_new(
+        seq: i64,
+        request_seq: i64,
+        command: String,
+        success: bool,
+        message: Option<String>,
+        body: Option<impl serde::Serialize>,
+    ) -> Result<Response, Error> {
+        Ok(Response {
+            seq,
             request_seq,
             success,
+            command,
             message,
-            body: body.map(|b| serde_json::to_value(b).unwrap()),
-        }
+            body: body
+                .map(|b| serde_json::to_value(b).map_err(Error::Serialize))
+                .transpose()?,
+        })
     }
 
-    /// Creates a new successful response.
-    pub fn success(request_seq: i64, body: impl serde::Serialize) -> Response {
-        Response {
+    /// Creates a new successful response to a request of type `R`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `body` fails to serialize, see [`Response::try_success`].
+    pub fn success<R: IRequest>(seq: i64, request_seq: i64, body: R::Response) -> Response {
+        Response::try_success::<R>(seq, request_seq, body).expect("failed to serialize response body")
+    }
+
+    /// Creates a new successful response to a request of type `R`, failing if
+    /// `body` fails to serialize.
+    pub fn try_success<R: IRequest>(seq: i64, request_seq: i64, body: R::Response) -> Result<Response, Error> {
+        let body = serde_json::to_value(body).map_err(Error::Serialize)?;
+        Ok(Response {
+            seq,
             request_seq,
             success: true,
+            command: R::COMMAND.to_owned(),
             message: None,
-            body: Some(serde_json::to_value(body).unwrap()),
-        }
+            body: (!body.is_null()).then_some(body),
+        })
     }
 
-    /// Creates a new error response.
-    pub fn error(request_seq: i64, message: Option<String>, detail: Option<Message>) -> Response {
-        #[derive(Serialize)]
-        struct ErrorResponseBody {
-            /// A structured error message.
-            error: Message,
-        }
+    /// Creates a new error response to a request of type `R`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `detail` fails to serialize, see [`Response::try_error`].
+    pub fn error<R: IRequest>(
+        seq: i64,
+        request_seq: i64,
+        message: Option<String>,
+        detail: Option<Message>,
+    ) -> Response {
+        Response::try_error::<R>(seq, request_seq, message, detail).expect("failed to serialize response body")
+    }
 
-        Response {
-            request_seq,
-            success: false,
-            message,
-            body: detail.map(|error| serde_json::to_value(&ErrorResponseBody { error }).unwrap()),
-        }
+    /// Creates a new error response to a request of type `R`, failing if
+    /// `detail` fails to serialize.
+    pub fn try_error<R: IRequest>(
+        seq: i64,
+        request_seq: i64,
+        message: Option<String>,
+        detail: Option<Message>,
+    ) -> Result<Response, Error> {
+        let body = detail.map(|error| ErrorResponse { error: Some(error) });
+        Response::try_new(seq, request_seq, R::COMMAND.to_owned(), false, message, body)
+    }
+}
+
+/// Deserializes arguments or a body, treating a missing value and an empty
+/// object alike, since peers differ in how they send "no arguments".
+pub(crate) fn from_value_lenient<T: DeserializeOwned>(value: &serde_json::Value) -> Result<T, serde_json::Error> {
+    let alternative = match value {
+        serde_json::Value::Null => serde_json::Value::Object(Default::default()),
+        serde_json::Value::Object(map) if map.is_empty() => serde_json::Value::Null,
+        _ => return T::deserialize(value),
+    };
+    T::deserialize(value).or_else(|err| T::deserialize(&alternative).map_err(|_| err))
+}
+
+/// Deserializes the arguments of a request.
+pub(crate) fn arguments_from_value<T: DeserializeOwned>(value: &serde_json::Value) -> Result<T, Error> {
+    from_value_lenient(value).map_err(Error::BadArguments)
+}
+
+/// Deserializes the body of an event or response.
+pub(crate) fn body_from_value<T: DeserializeOwned>(value: &serde_json::Value) -> Result<T, Error> {
+    from_value_lenient(value).map_err(Error::BadBody)
+}
+
+/// Serializes a `{tag_key: tag, content_key: content}` object, as used by the
+/// generated `Any*` enums. The content is omitted if it is `None`.
+pub(crate) fn serialize_tagged<S: serde::Serializer, T: Serialize>(
+    serializer: S,
+    (tag_key, content_key): (&'static str, &'static str),
+    tag: &str,
+    content: Option<&T>,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+
+    let mut map = serializer.serialize_map(Some(1 + content.is_some() as usize))?;
+    map.serialize_entry(tag_key, tag)?;
+    if let Some(content) = content {
+        map.serialize_entry(content_key, content)?;
     }
+    map.end()
 }
 
 /// Represents an event from the client.
@@ -164,12 +333,35 @@ pub struct Event {
 
 impl Event {
     /// Creates a new event.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `body` fails to serialize, see [`Event::try_new`].
     pub fn new(seq: i64, event: String, body: impl serde::Serialize) -> Event {
-        Event {
+        Event::try_new(seq, event, body).expect("failed to serialize event body")
+    }
+
+    /// Creates a new event, failing if `body` fails to serialize.
+    pub fn try_new(seq: i64, event: String, body: impl serde::Serialize) -> Result<Event, Error> {
+        Ok(Event {
             seq,
             event,
-            body: serde_json::to_value(body).unwrap(),
-        }
+            body: serde_json::to_value(body).map_err(Error::Serialize)?,
+        })
+    }
+
+    /// Creates a new event of type `E`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `body` fails to serialize, see [`Event::try_from_typed`].
+    pub fn from_typed<E: IEvent>(seq: i64, body: E::Body) -> Event {
+        Event::try_from_typed::<E>(seq, body).expect("failed to serialize event body")
+    }
+
+    /// Creates a new event of type `E`, failing if `body` fails to serialize.
+    pub fn try_from_typed<E: IEvent>(seq: i64, body: E::Body) -> Result<Event, Error> {
+        Event::try_new(seq, E::EVENT.to_owned(), body)
     }
 }
 
@@ -187,4 +379,623 @@ mod tests {
         let serialized = serde_json::to_string(&reason).unwrap();
         assert_eq!(serialized, r#""function breakpoint""#);
     }
+
+    #[test]
+    fn test_request_typed_round_trip() {
+        let args = NextArguments {
+            granularity: Some(SteppingGranularity::Line),
+            single_thread: None,
+            thread_id: ThreadId(3),
+        };
+        let req = Request::from_typed::<request::Next>(5, args.clone());
+        assert_eq!(req.command, "next");
+        assert_eq!(req.parse::<request::Next>().unwrap(), args);
+    }
+
+    #[test]
+    fn test_request_parse_without_arguments() {
+        let raw = r#"{"seq":1,"type":"request","command":"threads"}"#;
+        let req: Request = serde_json::from_str(raw).unwrap();
+        req.parse::<request::Threads>().unwrap();
+    }
+
+    #[test]
+    fn test_request_parse_optional_arguments() {
+        let raw = r#"{"seq":1,"type":"request","command":"disconnect"}"#;
+        let req: Request = serde_json::from_str(raw).unwrap();
+        let args = req.parse::<request::Disconnect>().unwrap();
+        assert_eq!(args.terminate_debuggee, None);
+
+        let raw = r#"{"seq":1,"type":"request","command":"configurationDone","arguments":{}}"#;
+        let req: Request = serde_json::from_str(raw).unwrap();
+        req.parse::<request::ConfigurationDone>().unwrap();
+    }
+
+    #[test]
+    fn test_any_request() {
+        let raw = r#"{"command":"next","arguments":{"threadId":3}}"#;
+        let req: request::AnyRequest = serde_json::from_str(raw).unwrap();
+        let request::AnyRequest::Next(args) = &req else {
+            panic!("expected a next request, got {req:?}");
+        };
+        assert_eq!(args.thread_id, ThreadId(3));
+        assert_eq!(req.command(), "next");
+        assert_eq!(serde_json::to_string(&req).unwrap(), raw);
+
+        let req: request::AnyRequest = serde_json::from_str(r#"{"command":"threads"}"#).unwrap();
+        assert!(matches!(req, request::AnyRequest::Threads));
+        assert_eq!(serde_json::to_string(&req).unwrap(), r#"{"command":"threads"}"#);
+    }
+
+    #[test]
+    fn test_any_request_other() {
+        let raw = r#"{"command":"vendor/reload","arguments":{"force":true}}"#;
+        let req: request::AnyRequest = serde_json::from_str(raw).unwrap();
+        let request::AnyRequest::Other { command, arguments } = &req else {
+            panic!("expected an unknown request, got {req:?}");
+        };
+        assert_eq!(command, "vendor/reload");
+        assert_eq!(arguments, &serde_json::json!({ "force": true }));
+        assert_eq!(serde_json::to_string(&req).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_any_request_from_request() {
+        let req = Request::new(
+            4,
+            "stackTrace".to_owned(),
+            serde_json::json!({ "threadId": 1, "levels": 20 }),
+        );
+        let any = request::AnyRequest::try_from(req).unwrap();
+        let request::AnyRequest::StackTrace(args) = &any else {
+            panic!("expected a stackTrace request, got {any:?}");
+        };
+        assert_eq!(args.levels, Some(20));
+
+        let req = any.into_request(4);
+        assert_eq!(req.command, "stackTrace");
+        assert_eq!(req.arguments, serde_json::json!({ "threadId": 1, "levels": 20 }));
+
+        let bad = Request::new(5, "next".to_owned(), serde_json::json!({}));
+        assert!(request::AnyRequest::try_from(bad).is_err());
+    }
+
+    #[test]
+    fn test_any_event() {
+        let raw = r#"{"event":"stopped","body":{"reason":"breakpoint","threadId":1,"allThreadsStopped":true}}"#;
+        let event: event::AnyEvent = serde_json::from_str(raw).unwrap();
+        let event::AnyEvent::Stopped(body) = &event else {
+            panic!("expected a stopped event, got {event:?}");
+        };
+        assert_eq!(body.reason, StoppedEventReason::Breakpoint);
+        assert_eq!(event.event(), "stopped");
+        let round_trip: serde_json::Value = serde_json::to_value(&event).unwrap();
+        assert_eq!(round_trip, serde_json::from_str::<serde_json::Value>(raw).unwrap());
+
+        let event: event::AnyEvent = serde_json::from_str(r#"{"event":"initialized"}"#).unwrap();
+        assert!(matches!(event, event::AnyEvent::Initialized(None)));
+    }
+
+    #[test]
+    fn test_any_event_other() {
+        let raw = r#"{"event":"vendor/heartbeat","body":{"tick":3}}"#;
+        let event: event::AnyEvent = serde_json::from_str(raw).unwrap();
+        assert!(matches!(&event, event::AnyEvent::Other { event, .. } if event == "vendor/heartbeat"));
+        assert_eq!(serde_json::to_string(&event).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_any_event_conversions() {
+        let body = ExitedEvent { exit_code: 0 };
+        let event = Event::from_typed::<event::Exited>(12, body.clone());
+        let any = event::AnyEvent::try_from(event).unwrap();
+        assert!(matches!(&any, event::AnyEvent::Exited(b) if *b == body));
+
+        let event = Event::from(any);
+        assert_eq!(event.seq, 0);
+        assert_eq!(event.event, "exited");
+        assert_eq!(event.body, serde_json::json!({ "exitCode": 0 }));
+
+        let bad = Event::new(1, "exited".to_owned(), serde_json::json!({ "exitCode": "zero" }));
+        assert!(event::AnyEvent::try_from(bad).is_err());
+    }
+
+    #[test]
+    fn test_any_response_decode() {
+        let raw = r#"{"seq":9,"type":"response","request_seq":4,"success":true,"command":"threads",
+            "body":{"threads":[{"id":1,"name":"main"}]}}"#;
+        let resp: Response = serde_json::from_str(raw).unwrap();
+        let any = request::AnyResponse::decode("threads", resp).unwrap();
+        let request::AnyResponse::Threads(body) = &any else {
+            panic!("expected a threads response, got {any:?}");
+        };
+        assert_eq!(body.threads[0].name, "main");
+        assert_eq!(any.command(), "threads");
+
+        let resp = any.into_response(9, 4);
+        assert_eq!(resp.command, "threads");
+        assert_eq!(
+            resp.body,
+            Some(serde_json::json!({ "threads": [{ "id": 1, "name": "main" }] }))
+        );
+    }
+
+    #[test]
+    fn test_any_response_decode_without_body() {
+        let raw = r#"{"seq":3,"type":"response","request_seq":2,"success":true,"command":"next"}"#;
+        let resp: Response = serde_json::from_str(raw).unwrap();
+        let any = request::AnyResponse::decode("next", resp).unwrap();
+        assert!(matches!(any, request::AnyResponse::Next));
+    }
+
+    #[test]
+    fn test_any_response_decode_error() {
+        let raw = r#"{"seq":3,"type":"response","request_seq":2,"success":false,"command":"evaluate",
+            "message":"notStopped","body":{"error":{"id":7,"format":"not stopped"}}}"#;
+        let resp: Response = serde_json::from_str(raw).unwrap();
+        let any = request::AnyResponse::decode("evaluate", resp).unwrap();
+        let request::AnyResponse::Error { command, message, body } = &any else {
+            panic!("expected an error response, got {any:?}");
+        };
+        assert_eq!(command, "evaluate");
+        assert_eq!(message.as_deref(), Some("notStopped"));
+        assert_eq!(body.error.as_ref().unwrap().id, 7);
+
+        let resp = any.into_response(3, 2);
+        assert!(!resp.success);
+        assert_eq!(
+            resp.body,
+            Some(serde_json::json!({ "error": { "id": 7, "format": "not stopped" } }))
+        );
+
+        let raw = r#"{"seq":3,"type":"response","request_seq":2,"success":false,"command":"evaluate"}"#;
+        let resp: Response = serde_json::from_str(raw).unwrap();
+        let any = request::AnyResponse::decode("evaluate", resp).unwrap();
+        assert!(matches!(any, request::AnyResponse::Error { body, .. } if body.error.is_none()));
+
+        let resp = Response::new(
+            3,
+            2,
+            "evaluate".to_owned(),
+            false,
+            None,
+            Some(serde_json::json!({ "error": 1 })),
+        );
+        assert!(matches!(
+            request::AnyResponse::decode("evaluate", resp),
+            Err(Error::BadBody(_))
+        ));
+    }
+
+    #[test]
+    fn test_any_response_decode_other() {
+        let resp = Response::new(
+            5,
+            4,
+            "vendor/stats".to_owned(),
+            true,
+            None,
+            Some(serde_json::json!([1, 2])),
+        );
+        let any = request::AnyResponse::decode("vendor/stats", resp).unwrap();
+        assert!(matches!(&any, request::AnyResponse::Other { body, .. } if body == &serde_json::json!([1, 2])));
+
+        let resp = Response::new(5, 4, "threads".to_owned(), true, None, Some(serde_json::json!([1, 2])));
+        assert!(request::AnyResponse::decode("threads", resp).is_err());
+    }
+
+    #[test]
+    fn test_try_new_reports_serialize_error() {
+        let mut env = std::collections::HashMap::new();
+        env.insert((1, 2), "not a string key");
+        let err = Request::try_new(1, "launch".to_owned(), env.clone()).unwrap_err();
+        assert!(matches!(err, Error::Serialize(_)));
+        let err = Event::try_new(1, "output".to_owned(), env.clone()).unwrap_err();
+        assert!(matches!(err, Error::Serialize(_)));
+        let err = Response::try_new(1, 1, "launch".to_owned(), true, None, Some(env)).unwrap_err();
+        assert!(matches!(err, Error::Serialize(_)));
+    }
+
+    #[test]
+    fn test_try_from_typed_reports_serialize_error() {
+        type Unserializable = std::collections::HashMap<(i32, i32), String>;
+
+        enum Vendor {}
+
+        impl IRequest for Vendor {
+            const COMMAND: &'static str = "vendor";
+            type Arguments = Unserializable;
+            type Response = Unserializable;
+        }
+
+        impl IEvent for Vendor {
+            const EVENT: &'static str = "vendor";
+            type Body = Unserializable;
+        }
+
+        let body = Unserializable::from([((1, 2), "not a string key".to_owned())]);
+        let err = Request::try_from_typed::<Vendor>(1, body.clone()).unwrap_err();
+        assert!(matches!(err, Error::Serialize(_)));
+        let err = Response::try_success::<Vendor>(1, 1, body.clone()).unwrap_err();
+        assert!(matches!(err, Error::Serialize(_)));
+        let err = Event::try_from_typed::<Vendor>(1, body).unwrap_err();
+        assert!(matches!(err, Error::Serialize(_)));
+
+        let response = Response::try_error::<Vendor>(1, 1, Some("failed".to_owned()), None).unwrap();
+        assert_eq!(response.command, "vendor");
+        assert!(!response.success);
+    }
+
+    #[test]
+    fn test_request_parse_wrong_command() {
+        let req = Request::new(1, "stepIn".to_owned(), serde_json::json!({ "threadId": 3 }));
+        let err = req.parse::<request::Next>().unwrap_err();
+        assert!(matches!(
+            err,
+            Error::WrongCommand { expected: "next", ref actual } if actual == "stepIn"
+        ));
+    }
+
+    #[test]
+    fn test_request_parse_bad_arguments() {
+        let req = Request::new(1, "next".to_owned(), serde_json::json!({ "threadId": "main" }));
+        let err = req.parse::<request::Next>().unwrap_err();
+        assert!(matches!(err, Error::BadArguments(_)));
+    }
+
+    #[test]
+    fn test_protocol_message_request_round_trip() {
+        let raw = r#"{"type":"request","seq":1,"command":"next","arguments":{"threadId":3}}"#;
+        let msg: ProtocolMessage = serde_json::from_str(raw).unwrap();
+        let ProtocolMessage::Request(req) = &msg else {
+            panic!("expected a request, got {msg:?}");
+        };
+        assert_eq!(req.seq, 1);
+        assert_eq!(req.command, "next");
+        assert_eq!(req.arguments, serde_json::json!({ "threadId": 3 }));
+
+        let serialized = serde_json::to_value(&msg).unwrap();
+        assert_eq!(serialized, serde_json::from_str::<serde_json::Value>(raw).unwrap());
+    }
+
+    #[test]
+    fn test_protocol_message_event_round_trip() {
+        let raw = r#"{"type":"event","seq":4,"event":"stopped","body":{"reason":"step","threadId":3}}"#;
+        let msg: ProtocolMessage = serde_json::from_str(raw).unwrap();
+        let ProtocolMessage::Event(event) = &msg else {
+            panic!("expected an event, got {msg:?}");
+        };
+        assert_eq!(event.event, "stopped");
+        let body: StoppedEvent = serde_json::from_value(event.body.clone()).unwrap();
+        assert_eq!(body.reason, StoppedEventReason::Step);
+
+        let serialized = serde_json::to_value(&msg).unwrap();
+        assert_eq!(serialized, serde_json::from_str::<serde_json::Value>(raw).unwrap());
+    }
+
+    #[test]
+    fn test_protocol_message_response() {
+        let raw = r#"{"type":"response","seq":2,"request_seq":1,"success":true,"command":"next"}"#;
+        let msg: ProtocolMessage = serde_json::from_str(raw).unwrap();
+        let ProtocolMessage::Response(resp) = &msg else {
+            panic!("expected a response, got {msg:?}");
+        };
+        assert_eq!(resp.request_seq, 1);
+        assert_eq!(resp.command, "next");
+        assert!(resp.success);
+
+        let serialized = serde_json::to_value(&msg).unwrap();
+        assert_eq!(serialized, serde_json::from_str::<serde_json::Value>(raw).unwrap());
+    }
+
+    #[test]
+    fn test_protocol_message_rejects_unknown_type() {
+        let raw = r#"{"type":"notification","seq":1}"#;
+        assert!(serde_json::from_str::<ProtocolMessage>(raw).is_err());
+    }
+
+    #[test]
+    fn test_response_success_fills_command() {
+        let body = ContinueResponse {
+            all_threads_continued: Some(true),
+        };
+        let resp = Response::success::<request::Continue>(7, 6, body);
+        let serialized = serde_json::to_value(ProtocolMessage::Response(resp)).unwrap();
+        assert_eq!(
+            serialized,
+            serde_json::json!({
+                "type": "response",
+                "seq": 7,
+                "request_seq": 6,
+                "success": true,
+                "command": "continue",
+                "body": { "allThreadsContinued": true },
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_success_without_body() {
+        let resp = Response::success::<request::ConfigurationDone>(3, 2, ());
+        let serialized = serde_json::to_value(ProtocolMessage::Response(resp)).unwrap();
+        assert_eq!(
+            serialized,
+            serde_json::json!({
+                "type": "response",
+                "seq": 3,
+                "request_seq": 2,
+                "success": true,
+                "command": "configurationDone",
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_error_fills_command() {
+        let detail = Message {
+            format: "cannot find {_file}".to_owned(),
+            id: 1001,
+            send_telemetry: None,
+            show_user: Some(true),
+            url: None,
+            url_label: None,
+            variables: Some(serde_json::json!({ "_file": "main.rs" })),
+        };
+        let resp = Response::error::<request::Source>(9, 8, Some("notFound".to_owned()), Some(detail));
+        let serialized = serde_json::to_value(ProtocolMessage::Response(resp)).unwrap();
+        assert_eq!(
+            serialized,
+            serde_json::json!({
+                "type": "response",
+                "seq": 9,
+                "request_seq": 8,
+                "success": false,
+                "command": "source",
+                "message": "notFound",
+                "body": {
+                    "error": {
+                        "format": "cannot find {_file}",
+                        "id": 1001,
+                        "showUser": true,
+                        "variables": { "_file": "main.rs" },
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_has_schema_required_fields() {
+        let schema: serde_json::Value =
+            serde_json::from_str(include_str!("../../assets/debugAdapterProtocol.json")).unwrap();
+        let defs = &schema["definitions"];
+        let mut required = Vec::new();
+        for def in [&defs["ProtocolMessage"], &defs["Response"]["allOf"][1]] {
+            required.extend(def["required"].as_array().unwrap().iter().map(|r| r.as_str().unwrap()));
+        }
+
+        let resp = Response::success::<request::Threads>(1, 1, ThreadsResponse::default());
+        let serialized = serde_json::to_value(ProtocolMessage::Response(resp)).unwrap();
+        for field in required {
+            assert!(serialized.get(field).is_some(), "missing required field {field}");
+        }
+    }
+
+    #[test]
+    fn test_negative_integers() {
+        // VS Code's disassembly view scrolls backwards from the current instruction.
+        let raw = r#"{"command":"disassemble","arguments":{"memoryReference":"0x5555555551a9","offset":0,"instructionOffset":-200,"instructionCount":400,"resolveSymbols":true},"type":"request","seq":12}"#;
+        let req: Request = serde_json::from_str(raw).unwrap();
+        let args = req.parse::<request::Disassemble>().unwrap();
+        assert_eq!(args.instruction_offset, Some(-200));
+
+        let raw = r#"{"command":"readMemory","arguments":{"memoryReference":"0x7fffffffe3c0","offset":-64,"count":128},"type":"request","seq":14}"#;
+        let req: Request = serde_json::from_str(raw).unwrap();
+        assert_eq!(req.parse::<request::ReadMemory>().unwrap().offset, Some(-64));
+
+        let raw = r#"{"seq":31,"type":"response","request_seq":16,"success":true,"command":"setInstructionBreakpoints","body":{"breakpoints":[{"id":4,"verified":true,"instructionReference":"0x555555555189","offset":-4}]}}"#;
+        let resp: Response = serde_json::from_str(raw).unwrap();
+        let request::AnyResponse::SetInstructionBreakpoints(body) =
+            request::AnyResponse::decode("setInstructionBreakpoints", resp).unwrap()
+        else {
+            panic!("expected a setInstructionBreakpoints response");
+        };
+        assert_eq!(body.breakpoints[0].offset, Some(-4));
+
+        // A process killed by a signal, as reported by debugpy.
+        let raw = r#"{"seq":40,"type":"event","event":"exited","body":{"exitCode":-9}}"#;
+        let event: Event = serde_json::from_str(raw).unwrap();
+        let event::AnyEvent::Exited(body) = event::AnyEvent::try_from(event).unwrap() else {
+            panic!("expected an exited event");
+        };
+        assert_eq!(body.exit_code, -9);
+    }
+
+    #[test]
+    fn test_id_newtypes_are_transparent() {
+        let raw = r#"{"frameId":1000}"#;
+        let args: ScopesArguments = serde_json::from_str(raw).unwrap();
+        assert_eq!(args.frame_id, FrameId(1000));
+        assert_eq!(serde_json::to_string(&args).unwrap(), raw);
+
+        let raw = r#"{"reason":"breakpoint","threadId":1,"hitBreakpointIds":[2,3]}"#;
+        let body: StoppedEvent = serde_json::from_str(raw).unwrap();
+        assert_eq!(body.thread_id, Some(ThreadId(1)));
+        assert_eq!(body.hit_breakpoint_ids, Some(vec![BreakpointId(2), BreakpointId(3)]));
+
+        let raw = r#"{"name":"Locals","variablesReference":5,"expensive":false}"#;
+        let scope: Scope = serde_json::from_str(raw).unwrap();
+        assert_eq!(scope.variables_reference, VariablesReference(5));
+        assert_eq!(scope.source.and_then(|s| s.source_reference), None::<SourceReference>);
+    }
+
+    /// Builds a value of `schema` with all its properties set.
+    fn schema_value(schema: &serde_json::Value, definitions: &serde_json::Value, depth: usize) -> serde_json::Value {
+        use serde_json::{json, Map, Value};
+
+        assert!(depth < 32, "unbounded recursion in {schema}");
+        if let Some(Value::String(reference)) = schema.get("$ref") {
+            let name = reference.trim_start_matches("#/definitions/");
+            return schema_value(&definitions[name], definitions, depth + 1);
+        }
+        if let Some(Value::Array(parts)) = schema.get("allOf") {
+            let mut object = Map::new();
+            for part in parts {
+                if let Value::Object(part) = schema_value(part, definitions, depth + 1) {
+                    object.extend(part);
+                }
+            }
+            return Value::Object(object);
+        }
+        if let Some(Value::Array(values)) = schema.get("enum").or_else(|| schema.get("_enum")) {
+            return values[0].clone();
+        }
+        let ty = match &schema["type"] {
+            Value::Array(types) => types[0].as_str(),
+            ty => ty.as_str(),
+        };
+        match ty {
+            Some("string") => json!("s"),
+            Some("integer" | "number") => json!(1),
+            Some("boolean") => json!(true),
+            Some("null") => Value::Null,
+            // Recursive types, such as inner exceptions, end with empty arrays.
+            Some("array") if depth > 8 => json!([]),
+            Some("array") => json!([schema_value(&schema["items"], definitions, depth + 1)]),
+            _ => {
+                let mut object = Map::new();
+                if let Some(Value::Object(properties)) = schema.get("properties") {
+                    for (name, property) in properties {
+                        object.insert(name.clone(), schema_value(property, definitions, depth + 1));
+                    }
+                }
+                if let Some(additional @ Value::Object(_)) = schema.get("additionalProperties") {
+                    object.insert(
+                        "additional".to_owned(),
+                        schema_value(additional, definitions, depth + 1),
+                    );
+                }
+                Value::Object(object)
+            }
+        }
+    }
+
+    /// Collects the paths of the keys of the objects in `value`.
+    fn key_paths(value: &serde_json::Value, path: &str, paths: &mut std::collections::BTreeSet<String>) {
+        match value {
+            serde_json::Value::Object(object) => {
+                for (key, value) in object {
+                    let path = format!("{path}.{key}");
+                    key_paths(value, &path, paths);
+                    paths.insert(path);
+                }
+            }
+            serde_json::Value::Array(values) => values
+                .iter()
+                .for_each(|value| key_paths(value, &format!("{path}[]"), paths)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_schema_round_trip() {
+        let workspace_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let contents = std::fs::read_to_string(workspace_dir.join("assets/debugAdapterProtocol.json")).unwrap();
+        let schema: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        let definitions = &schema["definitions"];
+
+        let mut checked = 0;
+        for (name, definition) in definitions.as_object().unwrap() {
+            // Messages are all of their base message and their own properties.
+            let Some([base, message]) = definition["allOf"].as_array().map(Vec::as_slice) else {
+                continue;
+            };
+            let properties = &message["properties"];
+            let content = |key| match properties.get(key) {
+                Some(schema) => schema_value(schema, definitions, 0),
+                None => serde_json::Value::Null,
+            };
+            let (content, round_trip) = match base["$ref"].as_str() {
+                Some("#/definitions/Request") => {
+                    let command = properties["command"]["enum"][0].as_str().unwrap();
+                    let arguments = content("arguments");
+                    let request = request::AnyRequest::from_parts(command.to_owned(), arguments.clone());
+                    let request = request.unwrap_or_else(|err| panic!("{name}: {err}"));
+                    (arguments, request.into_request(1).arguments)
+                }
+                Some("#/definitions/Response") => {
+                    let command = name.strip_suffix("Response").unwrap();
+                    let command = format!("{}{}", command[..1].to_lowercase(), &command[1..]);
+                    let body = content("body");
+                    let response = Response::new(2, 1, command.clone(), true, None, Some(body.clone()));
+                    let response = request::AnyResponse::decode(&command, response);
+                    let response = response.unwrap_or_else(|err| panic!("{name}: {err}"));
+                    (body, response.into_response(2, 1).body.unwrap_or_default())
+                }
+                Some("#/definitions/Event") => {
+                    let event = properties["event"]["enum"][0].as_str().unwrap();
+                    let body = content("body");
+                    let event = event::AnyEvent::from_parts(event.to_owned(), body.clone());
+                    let event = event.unwrap_or_else(|err| panic!("{name}: {err}"));
+                    (body, event.into_event(1).body)
+                }
+                _ => continue,
+            };
+            let (mut expected, mut actual) = Default::default();
+            key_paths(&content, "", &mut expected);
+            key_paths(&round_trip, "", &mut actual);
+            assert_eq!(expected, actual, "{name}");
+            checked += 1;
+        }
+        assert!(checked > 100, "only {checked} messages checked");
+    }
+
+    #[test]
+    fn test_merge_capabilities() {
+        let mut capabilities = Capabilities {
+            supports_step_back: Some(true),
+            supports_modules_request: Some(true),
+            ..Default::default()
+        };
+        let before = capabilities.clone();
+        let delta: Capabilities = serde_json::from_value(serde_json::json!({
+            "supportsModulesRequest": false,
+            "supportsRestartFrame": true,
+            "completionTriggerCharacters": ["."],
+        }))
+        .unwrap();
+        capabilities.merge(&delta);
+        assert_eq!(capabilities.supports_step_back, Some(true));
+        assert_eq!(capabilities.supports_modules_request, Some(false));
+        assert_eq!(capabilities.supports_restart_frame, Some(true));
+        assert_eq!(capabilities.completion_trigger_characters, Some(vec![".".to_owned()]));
+        assert_eq!(
+            before.diff(&capabilities),
+            [
+                "completionTriggerCharacters",
+                "supportsModulesRequest",
+                "supportsRestartFrame"
+            ]
+        );
+        assert!(capabilities.diff(&capabilities).is_empty());
+
+        let unsupported = Capabilities {
+            supports_step_back: Some(false),
+            ..Default::default()
+        };
+        assert!(Capabilities::default().diff(&unsupported).is_empty());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_acronym_field() {
+        let json = serde_json::json!({ "supportsANSIStyling": true });
+        let mut capabilities: Capabilities = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(capabilities.supports_ansi_styling, Some(true));
+        assert_eq!(serde_json::to_value(&capabilities).unwrap(), json);
+        assert!(capabilities.supports("supportsANSIStyling"));
+
+        *capabilities.supports_ansistyling_mut() = None;
+        assert_eq!(capabilities.supports_ansistyling(), &None);
+    }
 }
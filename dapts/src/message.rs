@@ -0,0 +1,286 @@
+//! Helpers for the structured error [`Message`] of failed responses.
+
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::{Error, ErrorResponse, Message, Response};
+
+impl Message {
+    /// Creates a message with the given id and format string.
+    ///
+    /// Embedded variables of the format string have the form `{name}`, and are
+    /// looked up from the variables added by [`Message::with_variable`].
+    pub fn new(id: u64, format: impl Into<String>) -> Message {
+        Message {
+            format: format.into(),
+            id,
+            send_telemetry: None,
+            show_user: None,
+            url: None,
+            url_label: None,
+            variables: None,
+        }
+    }
+
+    /// Adds a variable for the format string.
+    ///
+    /// Names starting with an underscore mark variables that contain no user
+    /// data (PII).
+    pub fn with_variable(mut self, name: impl Into<String>, value: impl Into<String>) -> Message {
+        let variables = self
+            .variables
+            .get_or_insert_with(|| serde_json::Value::Object(Default::default()));
+        if let serde_json::Value::Object(map) = variables {
+            map.insert(name.into(), serde_json::Value::String(value.into()));
+        }
+        self
+    }
+
+    /// Sets whether the message is shown to the user.
+    pub fn with_show_user(mut self, show_user: bool) -> Message {
+        self.show_user = Some(show_user);
+        self
+    }
+
+    /// Sets whether the message is sent to telemetry.
+    pub fn with_send_telemetry(mut self, send_telemetry: bool) -> Message {
+        self.send_telemetry = Some(send_telemetry);
+        self
+    }
+
+    /// Sets a url where additional information about the message can be found.
+    pub fn with_url(mut self, url: impl Into<String>, label: Option<String>) -> Message {
+        self.url = Some(url.into());
+        self.url_label = label;
+        self
+    }
+
+    /// Renders the format string, substituting all variables.
+    ///
+    /// Placeholders without a corresponding variable are kept as is.
+    pub fn render(&self) -> String {
+        self.render_impl(false)
+    }
+
+    /// Renders the format string, substituting only the variables that contain
+    /// no user data, i.e. whose names start with an underscore.
+    ///
+    /// Other placeholders are kept as is, so the result can be safely used for
+    /// telemetry purposes.
+    pub fn render_without_pii(&self) -> String {
+        self.render_impl(true)
+    }
+
+    fn render_impl(&self, exclude_pii: bool) -> String {
+        let mut result = String::with_capacity(self.format.len());
+        let mut rest = self.format.as_str();
+        while let Some(start) = rest.find('{') {
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('}') else {
+                break;
+            };
+            result.push_str(&rest[..start]);
+            let name = &after[..end];
+            let placeholder = &rest[start..start + end + 2];
+            match self.variable(name) {
+                Some(value) if !name.is_empty() && (!exclude_pii || name.starts_with('_')) => result.push_str(&value),
+                _ => result.push_str(placeholder),
+            }
+            rest = &after[end + 1..];
+        }
+        result.push_str(rest);
+        result
+    }
+
+    fn variable(&self, name: &str) -> Option<String> {
+        match self.variables.as_ref()?.get(name)? {
+            serde_json::Value::String(value) => Some(value.clone()),
+            value => Some(value.to_string()),
+        }
+    }
+}
+
+/// A failed response, as seen by the client that sent the request.
+#[derive(Debug, Clone)]
+pub struct ResponseError {
+    /// The command requested.
+    pub command: String,
+    /// Contains the raw error in short form.
+    pub message: Option<String>,
+    /// A structured error message.
+    pub detail: Option<Message>,
+}
+
+impl fmt::Display for ResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.detail, &self.message) {
+            (Some(detail), _) => write!(f, "{} failed: {}", self.command, detail.render()),
+            (None, Some(message)) => write!(f, "{} failed: {message}", self.command),
+            (None, None) => write!(f, "{} failed", self.command),
+        }
+    }
+}
+
+impl std::error::Error for ResponseError {}
+
+/// The failure of a request, answered with an error response.
+#[derive(Debug, Clone)]
+pub struct RequestError {
+    /// The raw error in short form, sent as [`Response::message`].
+    pub message: Option<String>,
+    /// A structured error message, sent in the body of the response.
+    pub detail: Option<Message>,
+}
+
+impl RequestError {
+    /// Creates an error with the given short message.
+    pub fn new(message: impl Into<String>) -> RequestError {
+        RequestError {
+            message: Some(message.into()),
+            detail: None,
+        }
+    }
+
+    /// Creates the error of a request the adapter doesn't support.
+    pub fn unsupported() -> RequestError {
+        RequestError::new("unsupported")
+    }
+
+    /// Creates the error of a request cancelled by the client.
+    pub fn cancelled() -> RequestError {
+        RequestError::new("cancelled")
+    }
+
+    /// Sets the structured error message.
+    pub fn with_detail(mut self, detail: Message) -> RequestError {
+        self.detail = Some(detail);
+        self
+    }
+}
+
+impl From<Message> for RequestError {
+    fn from(detail: Message) -> Self {
+        RequestError {
+            message: None,
+            detail: Some(detail),
+        }
+    }
+}
+
+impl From<Error> for RequestError {
+    fn from(err: Error) -> Self {
+        RequestError::new(err.to_string())
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.detail, &self.message) {
+            (Some(detail), _) => f.write_str(&detail.render()),
+            (None, Some(message)) => f.write_str(message),
+            (None, None) => f.write_str("request failed"),
+        }
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+impl Response {
+    /// Extracts the error of a failed response.
+    ///
+    /// Returns `None` if the response is successful. A body that does not
+    /// carry a valid structured error is ignored.
+    pub fn parse_error(&self) -> Option<ResponseError> {
+        if self.success {
+            return None;
+        }
+        let detail = self
+            .body
+            .as_ref()
+            .and_then(|body| ErrorResponse::deserialize(body).ok())
+            .and_then(|body| body.error);
+        Some(ResponseError {
+            command: self.command.clone(),
+            message: self.message.clone(),
+            detail,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request;
+
+    fn message() -> Message {
+        Message::new(2001, "cannot read {path} with {_reason}: {missing}")
+            .with_variable("path", "/home/alice/secret.txt")
+            .with_variable("_reason", "EACCES")
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            message().render(),
+            "cannot read /home/alice/secret.txt with EACCES: {missing}"
+        );
+    }
+
+    #[test]
+    fn test_render_without_pii() {
+        assert_eq!(
+            message().render_without_pii(),
+            "cannot read {path} with EACCES: {missing}"
+        );
+    }
+
+    #[test]
+    fn test_render_unbalanced_braces() {
+        let msg = Message::new(1, "{} and {_x} and {unclosed").with_variable("_x", "x");
+        assert_eq!(msg.render(), "{} and x and {unclosed");
+    }
+
+    #[test]
+    fn test_builder_serializes_to_spec() {
+        let msg = Message::new(7, "failed")
+            .with_show_user(true)
+            .with_url("https://example.com/errors/7", Some("More info".to_owned()));
+        assert_eq!(
+            serde_json::to_value(&msg).unwrap(),
+            serde_json::json!({
+                "id": 7,
+                "format": "failed",
+                "showUser": true,
+                "url": "https://example.com/errors/7",
+                "urlLabel": "More info",
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let resp = Response::error::<request::Evaluate>(3, 2, Some("notStopped".to_owned()), Some(message()));
+        let raw = serde_json::to_string(&resp).unwrap();
+        let resp: Response = serde_json::from_str(&raw).unwrap();
+        let err = resp.parse_error().unwrap();
+        assert_eq!(err.command, "evaluate");
+        assert_eq!(err.message.as_deref(), Some("notStopped"));
+        assert_eq!(err.detail.as_ref().unwrap().id, 2001);
+        assert_eq!(
+            err.to_string(),
+            "evaluate failed: cannot read /home/alice/secret.txt with EACCES: {missing}"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_without_detail() {
+        let resp = Response::new(3, 2, "next".to_owned(), false, Some("cancelled".to_owned()), Some(42));
+        let err = resp.parse_error().unwrap();
+        assert!(err.detail.is_none());
+        assert_eq!(err.to_string(), "next failed: cancelled");
+
+        let resp = Response::success::<request::Next>(3, 2, ());
+        assert!(resp.parse_error().is_none());
+    }
+}
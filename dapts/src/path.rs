@@ -0,0 +1,284 @@
+//! Paths of sources, which are file system paths or URIs depending on the
+//! `pathFormat` of the `initialize` request.
+//!
+//! [`SourcePath`] parses both forms, decoding `file` URIs into paths, and
+//! renders them back in the negotiated format:
+//!
+//! ```
+//! use dapts::path::SourcePath;
+//! use dapts::InitializeRequestArgumentsPathFormat as PathFormat;
+//!
+//! let path = SourcePath::parse("file:///home/me/my%20project/main.rs", &PathFormat::Uri);
+//! assert_eq!(path, SourcePath::Path("/home/me/my project/main.rs".into()));
+//! assert_eq!(path.render(&PathFormat::Path), "/home/me/my project/main.rs");
+//!
+//! // Windows paths are handled on every platform.
+//! let path = SourcePath::parse(r"C:\Users\me\main.rs", &PathFormat::Path);
+//! assert_eq!(path.render(&PathFormat::Uri), "file:///C:/Users/me/main.rs");
+//! ```
+//!
+//! Paths are handled as strings, so that an adapter can handle the paths of
+//! a client running on another platform. A path starting with a drive letter,
+//! like `C:\`, is a Windows path, whose separators are backslashes. So is the
+//! path of a `file` URI starting with one, like `file:///C:/`.
+
+use std::path::{Path, PathBuf};
+
+use crate::{InitializeRequestArgumentsPathFormat as PathFormat, Source};
+
+/// The path of a source.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SourcePath {
+    /// A path of the file system, absolute or relative.
+    Path(PathBuf),
+    /// A URI other than a local `file` URI, such as `untitled:Untitled-1`.
+    Uri(String),
+}
+
+impl SourcePath {
+    /// Parses a path given in `format`.
+    ///
+    /// In the `path` format, the string is a path. In the `uri` format, local
+    /// `file` URIs are decoded to absolute paths, other URIs are kept as they
+    /// are, and relative references are decoded to relative paths. In an
+    /// unknown format, strings starting with a scheme are URIs, and others
+    /// paths.
+    pub fn parse(path: &str, format: &PathFormat) -> SourcePath {
+        match format {
+            PathFormat::Path => SourcePath::Path(path.into()),
+            PathFormat::Uri => parse_uri(path),
+            _ if scheme(path).is_some() => parse_uri(path),
+            _ => SourcePath::Path(path.into()),
+        }
+    }
+
+    /// Renders the path in `format`.
+    ///
+    /// URIs are rendered as they are in every format. Paths are rendered as
+    /// `file` URIs in the `uri` format, or percent-encoded if they are
+    /// relative, and as they are otherwise.
+    pub fn render(&self, format: &PathFormat) -> String {
+        match (self, format) {
+            (SourcePath::Uri(uri), _) => uri.clone(),
+            (SourcePath::Path(path), PathFormat::Uri) => render_uri(&path.to_string_lossy()),
+            (SourcePath::Path(path), _) => path.to_string_lossy().into_owned(),
+        }
+    }
+
+    /// Returns the path, if this is a path.
+    pub fn as_path(&self) -> Option<&Path> {
+        match self {
+            SourcePath::Path(path) => Some(path),
+            SourcePath::Uri(_) => None,
+        }
+    }
+}
+
+impl Source {
+    /// Returns the parsed `path` of the source, given in `format`.
+    pub fn source_path(&self, format: &PathFormat) -> Option<SourcePath> {
+        Some(SourcePath::parse(self.path.as_deref()?, format))
+    }
+
+    /// Sets the `path` of the source, rendered in `format`.
+    pub fn set_source_path(&mut self, path: &SourcePath, format: &PathFormat) {
+        self.path = Some(path.render(format));
+    }
+}
+
+/// Returns the scheme of `uri`, if it starts with one.
+///
+/// Single letters are drive letters, not schemes.
+fn scheme(uri: &str) -> Option<&str> {
+    let (scheme, _) = uri.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    (valid && scheme.len() > 1).then_some(scheme)
+}
+
+/// Returns the drive letter and the rest of a Windows path.
+fn drive(path: &str) -> Option<(char, &str)> {
+    let mut chars = path.chars();
+    let letter = chars.next().filter(char::is_ascii_alphabetic)?;
+    let rest = chars.as_str().strip_prefix(':')?;
+    (rest.is_empty() || rest.starts_with(['\\', '/'])).then_some((letter, rest))
+}
+
+fn parse_uri(uri: &str) -> SourcePath {
+    let path = match scheme(uri) {
+        None => decode(uri),
+        Some(scheme) if scheme.eq_ignore_ascii_case("file") => {
+            let rest = &uri[scheme.len() + 1..];
+            match rest.strip_prefix("//") {
+                // Only local files have a path.
+                Some(rest) => rest
+                    .strip_prefix("localhost")
+                    .unwrap_or(rest)
+                    .strip_prefix('/')
+                    .and_then(|path| {
+                        let path = decode(path)?;
+                        match drive(&path) {
+                            Some(_) => Some(path.replace('/', "\\")),
+                            None => Some(format!("/{path}")),
+                        }
+                    }),
+                None => rest.starts_with('/').then(|| decode(rest)).flatten(),
+            }
+        }
+        Some(_) => None,
+    };
+    match path {
+        Some(path) => SourcePath::Path(path.into()),
+        None => SourcePath::Uri(uri.to_owned()),
+    }
+}
+
+fn render_uri(path: &str) -> String {
+    if let Some((letter, rest)) = drive(path) {
+        return format!("file:///{letter}:{}", encode(&rest.replace('\\', "/")));
+    }
+    let encoded = encode(path);
+    if path.starts_with('/') {
+        format!("file://{encoded}")
+    } else {
+        encoded
+    }
+}
+
+/// Percent-encodes all bytes of `path` but the unreserved characters of URIs
+/// and slashes.
+fn encode(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Decodes the percent-encoded bytes of `path`.
+///
+/// Returns `None` if an escape is invalid, or the bytes are not UTF-8.
+fn decode(path: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+        // `from_str_radix` accepts a sign, which is not a hex digit.
+        let hex = rest.get(..2).filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+        bytes.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+        rest = &rest[2..];
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn path(path: &str) -> SourcePath {
+        SourcePath::Path(path.into())
+    }
+
+    #[test]
+    fn test_parse_uri() {
+        let parse = |uri| SourcePath::parse(uri, &PathFormat::Uri);
+        assert_eq!(parse("file:///a%20b/%E4%B8%AD.rs"), path("/a b/中.rs"));
+        assert_eq!(parse("file://localhost/a"), path("/a"));
+        assert_eq!(parse("file:/a"), path("/a"));
+        assert_eq!(parse("FILE:///c%3A/x/y.rs"), path(r"c:\x\y.rs"));
+        assert_eq!(parse("src/a%2Bb.rs"), path("src/a+b.rs"));
+        for uri in [
+            "file://server/share/a",
+            "file:///a%zz",
+            "file:///a%+1",
+            "file:///a%-1",
+            "file:///%FF",
+            "untitled:Untitled-1",
+            "https://x/y",
+        ] {
+            assert_eq!(parse(uri), SourcePath::Uri(uri.to_owned()));
+        }
+    }
+
+    #[test]
+    fn test_parse_unknown_format() {
+        let parse = |uri| SourcePath::parse(uri, &PathFormat::Unknown);
+        assert_eq!(parse("file:///a%20b"), path("/a b"));
+        assert_eq!(parse("/a%20b"), path("/a%20b"));
+        assert_eq!(parse(r"C:\a"), path(r"C:\a"));
+        assert_eq!(parse("git:/a"), SourcePath::Uri("git:/a".to_owned()));
+    }
+
+    #[test]
+    fn test_render() {
+        let uri = |p| path(p).render(&PathFormat::Uri);
+        assert_eq!(uri("/a b/#1?.rs"), "file:///a%20b/%231%3F.rs");
+        assert_eq!(uri(r"d:\é"), "file:///d:/%C3%A9");
+        assert_eq!(uri("a/b c"), "a/b%20c");
+        assert_eq!(path("/a b").render(&PathFormat::Path), "/a b");
+        let untitled = SourcePath::Uri("untitled:1".to_owned());
+        assert_eq!(untitled.render(&PathFormat::Path), "untitled:1");
+    }
+
+    #[test]
+    fn test_source() {
+        let mut source = Source::default();
+        assert_eq!(source.source_path(&PathFormat::Uri), None);
+        source.set_source_path(&path("/a b"), &PathFormat::Uri);
+        assert_eq!(source.path.as_deref(), Some("file:///a%20b"));
+        assert_eq!(source.source_path(&PathFormat::Uri), Some(path("/a b")));
+    }
+
+    /// A segment of a path, with spaces, escapes, and non-ASCII characters.
+    fn segment() -> impl Strategy<Value = String> {
+        "[a-zA-Z0-9 %#?&+:;=@~._中é😀-]{1,8}"
+    }
+
+    proptest! {
+        #[test]
+        fn test_unix_path_round_trip(segments in prop::collection::vec(segment(), 0..5)) {
+            // `/C:/a` is ambiguous, and taken as a Windows path in URIs.
+            prop_assume!(segments.first().and_then(|first| drive(first)).is_none());
+            let path = SourcePath::Path(format!("/{}", segments.join("/")).into());
+            let uri = path.render(&PathFormat::Uri);
+            prop_assert!(uri.is_ascii());
+            prop_assert_eq!(SourcePath::parse(&uri, &PathFormat::Uri), path);
+        }
+
+        #[test]
+        fn test_windows_path_round_trip(
+            letter in "[a-zA-Z]",
+            segments in prop::collection::vec(segment(), 0..5),
+        ) {
+            let path = SourcePath::Path(format!(r"{letter}:\{}", segments.join(r"\")).into());
+            let uri = path.render(&PathFormat::Uri);
+            prop_assert!(uri.starts_with("file:///"));
+            prop_assert_eq!(SourcePath::parse(&uri, &PathFormat::Uri), path);
+        }
+
+        #[test]
+        fn test_relative_path_round_trip(segments in prop::collection::vec(segment(), 1..5)) {
+            // `C:/a` is a Windows path.
+            prop_assume!(drive(&segments.join("/")).is_none());
+            let path = SourcePath::Path(segments.join("/").into());
+            let uri = path.render(&PathFormat::Uri);
+            prop_assert_eq!(SourcePath::parse(&uri, &PathFormat::Uri), path);
+        }
+
+        #[test]
+        fn test_path_format_is_verbatim(path in ".*") {
+            let parsed = SourcePath::parse(&path, &PathFormat::Path);
+            prop_assert_eq!(parsed.render(&PathFormat::Path), path);
+        }
+    }
+}
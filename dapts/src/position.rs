@@ -0,0 +1,329 @@
+//! Line and column numbers, which are 1-based unless the client says
+//! otherwise in the `initialize` request.
+//!
+//! A [`PositionEncoding`] is negotiated by `initialize`, and converts the line
+//! and column numbers of messages between the bases of the client and a
+//! canonical 0-based form: messages received are decoded, and messages to
+//! send are encoded.
+//!
+//! Columns are measured in UTF-16 code units. [`LineIndex`] converts them to
+//! and from byte offsets in the text of a source.
+//!
+//! ```
+//! use dapts::position::{Position, PositionEncoding};
+//! use dapts::{Breakpoint, InitializeRequestArguments, SetBreakpointsResponse};
+//!
+//! let args = InitializeRequestArguments {
+//!     adapter_id: "example".to_owned(),
+//!     lines_start_at1: Some(true),
+//!     columns_start_at1: Some(false),
+//!     ..Default::default()
+//! };
+//! let encoding = PositionEncoding::from_initialize(&args);
+//!
+//! // Positions are 0-based in the adapter.
+//! let breakpoint = Breakpoint {
+//!     line: Some(9),
+//!     column: Some(4),
+//!     ..Default::default()
+//! };
+//! let mut response = SetBreakpointsResponse {
+//!     breakpoints: vec![breakpoint],
+//! };
+//! encoding.encode(&mut response);
+//! assert_eq!((response.breakpoints[0].line, response.breakpoints[0].column), (Some(10), Some(4)));
+//! assert_eq!(encoding.decode_position(10, 4), Position { line: 9, column: 4 });
+//! ```
+
+mod impls;
+mod line_index;
+
+pub use self::line_index::{utf16_to_utf8, utf8_to_utf16, LineIndex};
+use crate::{InitializeRequestArguments, SourceBreakpoint, StackFrame};
+
+/// A 0-based position in a source.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    /// The 0-based line.
+    pub line: u32,
+    /// The 0-based column.
+    pub column: u32,
+}
+
+/// Whether the line and column numbers of a client start at 1 or 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PositionEncoding {
+    /// Whether lines start at 1.
+    pub lines_start_at1: bool,
+    /// Whether columns start at 1.
+    pub columns_start_at1: bool,
+}
+
+impl Default for PositionEncoding {
+    /// Lines and columns start at 1, as for clients that don't tell.
+    fn default() -> Self {
+        PositionEncoding {
+            lines_start_at1: true,
+            columns_start_at1: true,
+        }
+    }
+}
+
+impl PositionEncoding {
+    /// The encoding of the canonical form, in which nothing is converted.
+    pub const ZERO_BASED: PositionEncoding = PositionEncoding {
+        lines_start_at1: false,
+        columns_start_at1: false,
+    };
+
+    /// Returns the encoding the client asks for in `initialize`.
+    pub fn from_initialize(args: &InitializeRequestArguments) -> PositionEncoding {
+        PositionEncoding {
+            lines_start_at1: args.lines_start_at1.unwrap_or(true),
+            columns_start_at1: args.columns_start_at1.unwrap_or(true),
+        }
+    }
+
+    /// The shift from the bases of the client to 0-based.
+    fn decoding(&self) -> Shift {
+        Shift {
+            lines: -i32::from(self.lines_start_at1),
+            columns: -i32::from(self.columns_start_at1),
+        }
+    }
+
+    /// The shift from 0-based to the bases of the client.
+    fn encoding(&self) -> Shift {
+        Shift {
+            lines: i32::from(self.lines_start_at1),
+            columns: i32::from(self.columns_start_at1),
+        }
+    }
+
+    /// Converts the line and column numbers of a message received from the
+    /// client, or sent to it, to 0-based.
+    ///
+    /// Numbers below the base, e.g. line 0 when lines start at 1, become 0.
+    /// The line and column of a stack frame without a source, which are 0 to
+    /// be ignored, aren't converted.
+    pub fn decode<T: Positions + ?Sized>(&self, value: &mut T) {
+        value.shift_positions(self.decoding());
+    }
+
+    /// Converts the 0-based line and column numbers of a message to the
+    /// bases of the client.
+    pub fn encode<T: Positions + ?Sized>(&self, value: &mut T) {
+        value.shift_positions(self.encoding());
+    }
+
+    /// Converts a line and a column of the client to a position.
+    pub fn decode_position(&self, mut line: u32, mut column: u32) -> Position {
+        let shift = self.decoding();
+        shift.line(&mut line);
+        shift.column(&mut column);
+        Position { line, column }
+    }
+
+    /// Converts a position to a line and a column of the client.
+    pub fn encode_position(&self, position: Position) -> (u32, u32) {
+        let Position { mut line, mut column } = position;
+        let shift = self.encoding();
+        shift.line(&mut line);
+        shift.column(&mut column);
+        (line, column)
+    }
+}
+
+impl SourceBreakpoint {
+    /// Returns the position of the breakpoint, whose line and column are in
+    /// `encoding`.
+    ///
+    /// A breakpoint without a column is at the start of its line. The column
+    /// is in UTF-16 code units, which [`LineIndex`] converts to a byte offset.
+    pub fn position(&self, encoding: &PositionEncoding) -> Position {
+        let column = self.column.unwrap_or(u32::from(encoding.columns_start_at1));
+        encoding.decode_position(self.line, column)
+    }
+}
+
+impl StackFrame {
+    /// Returns the position of the frame, whose line and column are in
+    /// `encoding`.
+    pub fn position(&self, encoding: &PositionEncoding) -> Position {
+        encoding.decode_position(self.line, self.column)
+    }
+}
+
+/// A shift of line and column numbers, applied by [`Positions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shift {
+    lines: i32,
+    columns: i32,
+}
+
+impl Shift {
+    /// Shifts a line number.
+    pub fn line(self, line: &mut u32) {
+        *line = line.saturating_add_signed(self.lines);
+    }
+
+    /// Shifts a column number.
+    pub fn column(self, column: &mut u32) {
+        *column = column.saturating_add_signed(self.columns);
+    }
+}
+
+/// A value holding line and column numbers, such as the arguments or body of
+/// a message.
+///
+/// It is implemented for all the types of this crate holding positions, and
+/// for the `Any*` enums of messages.
+pub trait Positions {
+    /// Shifts all the line and column numbers of the value.
+    fn shift_positions(&mut self, shift: Shift);
+}
+
+impl<T: Positions> Positions for Option<T> {
+    fn shift_positions(&mut self, shift: Shift) {
+        if let Some(value) = self {
+            value.shift_positions(shift);
+        }
+    }
+}
+
+impl<T: Positions> Positions for Vec<T> {
+    fn shift_positions(&mut self, shift: Shift) {
+        for value in self {
+            value.shift_positions(shift);
+        }
+    }
+}
+
+impl<T: Positions> Positions for [T] {
+    fn shift_positions(&mut self, shift: Shift) {
+        for value in self {
+            value.shift_positions(shift);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AnyEvent;
+    use crate::request::{AnyRequest, AnyResponse};
+    use crate::{OutputEvent, SetBreakpointsArguments, Source, SourceBreakpoint, StackTraceResponse};
+
+    #[test]
+    fn test_decode_request() {
+        let args = SetBreakpointsArguments {
+            breakpoints: Some(vec![SourceBreakpoint {
+                column: Some(1),
+                condition: None,
+                hit_condition: None,
+                line: 1,
+                log_message: None,
+                mode: None,
+            }]),
+            lines: Some(vec![1, 5]),
+            source: Source::default(),
+            source_modified: None,
+        };
+        let mut request = AnyRequest::SetBreakpoints(args);
+        PositionEncoding::default().decode(&mut request);
+        let AnyRequest::SetBreakpoints(args) = request else {
+            unreachable!()
+        };
+        let breakpoint = &args.breakpoints.unwrap()[0];
+        assert_eq!((breakpoint.line, breakpoint.column), (0, Some(0)));
+        assert_eq!(args.lines, Some(vec![0, 4]));
+    }
+
+    #[test]
+    fn test_encode_response() {
+        let body = serde_json::json!({
+            "targets": [{ "id": 1, "label": "x", "line": 2, "endColumn": 3 }]
+        });
+        let mut response = AnyResponse::GotoTargets(serde_json::from_value(body).unwrap());
+        let encoding = PositionEncoding {
+            lines_start_at1: true,
+            columns_start_at1: false,
+        };
+        encoding.encode(&mut response);
+        let AnyResponse::GotoTargets(body) = response else {
+            unreachable!()
+        };
+        assert_eq!((body.targets[0].line, body.targets[0].end_column), (3, Some(3)));
+    }
+
+    #[test]
+    fn test_frame_without_source() {
+        let body = serde_json::json!({
+            "stackFrames": [
+                { "id": 1, "name": "main", "line": 0, "column": 0 },
+                { "id": 2, "name": "f", "source": {}, "line": 3, "column": 1 },
+            ]
+        });
+        let positions = |body: &StackTraceResponse| {
+            body.stack_frames
+                .iter()
+                .map(|frame| (frame.line, frame.column))
+                .collect::<Vec<_>>()
+        };
+        let mut body: StackTraceResponse = serde_json::from_value(body).unwrap();
+        let encoding = PositionEncoding::default();
+        encoding.decode(&mut body);
+        assert_eq!(positions(&body), [(0, 0), (2, 0)]);
+        encoding.encode(&mut body);
+        assert_eq!(positions(&body), [(0, 0), (3, 1)]);
+    }
+
+    #[test]
+    fn test_decode_event() {
+        let body = OutputEvent {
+            output: "x".to_owned(),
+            line: Some(0),
+            column: Some(7),
+            ..Default::default()
+        };
+        let mut event = AnyEvent::Output(body);
+        PositionEncoding::default().decode(&mut event);
+        let AnyEvent::Output(body) = event else { unreachable!() };
+        // Line 0 is below the base, and stays 0.
+        assert_eq!((body.line, body.column), (Some(0), Some(6)));
+    }
+
+    #[test]
+    fn test_breakpoint_offset() {
+        let text = "fn main() {\n    let s = \"😀\"; dbg!(s);\n}\n";
+        let breakpoint = SourceBreakpoint {
+            column: Some(19),
+            condition: None,
+            hit_condition: None,
+            line: 2,
+            log_message: None,
+            mode: None,
+        };
+        let position = breakpoint.position(&PositionEncoding::default());
+        assert_eq!(position, Position { line: 1, column: 18 });
+        let offset = LineIndex::new(text).offset(position).unwrap();
+        assert!(text[offset..].starts_with("dbg!"));
+
+        let breakpoint = SourceBreakpoint {
+            column: None,
+            ..breakpoint
+        };
+        assert_eq!(
+            breakpoint.position(&PositionEncoding::ZERO_BASED),
+            Position { line: 2, column: 0 }
+        );
+    }
+
+    #[test]
+    fn test_zero_based() {
+        let encoding = PositionEncoding::ZERO_BASED;
+        let position = Position { line: 3, column: 0 };
+        assert_eq!(encoding.encode_position(position), (3, 0));
+        assert_eq!(encoding.decode_position(3, 0), position);
+    }
+}
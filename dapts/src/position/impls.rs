@@ -0,0 +1,261 @@
+// This file is autogenerated. Do not edit by hand.
+// To regenerate from schema, run `cargo run -p generator`.
+
+use super::{Positions, Shift};
+use crate::event::AnyEvent;
+use crate::request::{AnyRequest, AnyResponse};
+
+impl Positions for crate::Breakpoint {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        self.end_column.iter_mut().for_each(|column| shift.column(column));
+        self.end_line.iter_mut().for_each(|line| shift.line(line));
+        self.line.iter_mut().for_each(|line| shift.line(line));
+    }
+}
+
+impl Positions for crate::BreakpointEvent {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.breakpoint.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::BreakpointLocation {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        self.end_column.iter_mut().for_each(|column| shift.column(column));
+        self.end_line.iter_mut().for_each(|line| shift.line(line));
+        shift.line(&mut self.line);
+    }
+}
+
+impl Positions for crate::BreakpointLocationsArguments {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        self.end_column.iter_mut().for_each(|column| shift.column(column));
+        self.end_line.iter_mut().for_each(|line| shift.line(line));
+        shift.line(&mut self.line);
+    }
+}
+
+impl Positions for crate::BreakpointLocationsResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.breakpoints.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::CompletionItem {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.start.iter_mut().for_each(|column| shift.column(column));
+    }
+}
+
+impl Positions for crate::CompletionsArguments {
+    fn shift_positions(&mut self, shift: Shift) {
+        shift.column(&mut self.column);
+        self.line.iter_mut().for_each(|line| shift.line(line));
+    }
+}
+
+impl Positions for crate::CompletionsResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.targets.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::DisassembleResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.instructions.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::DisassembledInstruction {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        self.end_column.iter_mut().for_each(|column| shift.column(column));
+        self.end_line.iter_mut().for_each(|line| shift.line(line));
+        self.line.iter_mut().for_each(|line| shift.line(line));
+    }
+}
+
+impl Positions for crate::EvaluateArguments {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        self.line.iter_mut().for_each(|line| shift.line(line));
+    }
+}
+
+impl Positions for crate::GotoTarget {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        self.end_column.iter_mut().for_each(|column| shift.column(column));
+        self.end_line.iter_mut().for_each(|line| shift.line(line));
+        shift.line(&mut self.line);
+    }
+}
+
+impl Positions for crate::GotoTargetsArguments {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        shift.line(&mut self.line);
+    }
+}
+
+impl Positions for crate::GotoTargetsResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.targets.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::LocationsResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        self.end_column.iter_mut().for_each(|column| shift.column(column));
+        self.end_line.iter_mut().for_each(|line| shift.line(line));
+        shift.line(&mut self.line);
+    }
+}
+
+impl Positions for crate::OutputEvent {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        self.line.iter_mut().for_each(|line| shift.line(line));
+    }
+}
+
+impl Positions for crate::Scope {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        self.end_column.iter_mut().for_each(|column| shift.column(column));
+        self.end_line.iter_mut().for_each(|line| shift.line(line));
+        self.line.iter_mut().for_each(|line| shift.line(line));
+    }
+}
+
+impl Positions for crate::ScopesResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.scopes.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::SetBreakpointsArguments {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.breakpoints.shift_positions(shift);
+        self.lines.iter_mut().flatten().for_each(|line| shift.line(line));
+    }
+}
+
+impl Positions for crate::SetBreakpointsResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.breakpoints.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::SetDataBreakpointsResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.breakpoints.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::SetExceptionBreakpointsResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.breakpoints.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::SetFunctionBreakpointsResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.breakpoints.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::SetInstructionBreakpointsResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.breakpoints.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::SourceBreakpoint {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        shift.line(&mut self.line);
+    }
+}
+
+impl Positions for crate::StackFrame {
+    fn shift_positions(&mut self, shift: Shift) {
+        if self.source.is_some() {
+            shift.column(&mut self.column);
+        }
+        self.end_column.iter_mut().for_each(|column| shift.column(column));
+        self.end_line.iter_mut().for_each(|line| shift.line(line));
+        if self.source.is_some() {
+            shift.line(&mut self.line);
+        }
+    }
+}
+
+impl Positions for crate::StackTraceResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.stack_frames.shift_positions(shift);
+    }
+}
+
+impl Positions for crate::StepInTarget {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.column.iter_mut().for_each(|column| shift.column(column));
+        self.end_column.iter_mut().for_each(|column| shift.column(column));
+        self.end_line.iter_mut().for_each(|line| shift.line(line));
+        self.line.iter_mut().for_each(|line| shift.line(line));
+    }
+}
+
+impl Positions for crate::StepInTargetsResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        self.targets.shift_positions(shift);
+    }
+}
+
+impl Positions for AnyRequest {
+    fn shift_positions(&mut self, shift: Shift) {
+        match self {
+            AnyRequest::BreakpointLocations(v) => v.shift_positions(shift),
+            AnyRequest::Completions(v) => v.shift_positions(shift),
+            AnyRequest::Evaluate(v) => v.shift_positions(shift),
+            AnyRequest::GotoTargets(v) => v.shift_positions(shift),
+            AnyRequest::SetBreakpoints(v) => v.shift_positions(shift),
+            _ => {}
+        }
+    }
+}
+
+impl Positions for AnyResponse {
+    fn shift_positions(&mut self, shift: Shift) {
+        match self {
+            AnyResponse::BreakpointLocations(v) => v.shift_positions(shift),
+            AnyResponse::Completions(v) => v.shift_positions(shift),
+            AnyResponse::Disassemble(v) => v.shift_positions(shift),
+            AnyResponse::GotoTargets(v) => v.shift_positions(shift),
+            AnyResponse::Locations(v) => v.shift_positions(shift),
+            AnyResponse::Scopes(v) => v.shift_positions(shift),
+            AnyResponse::SetBreakpoints(v) => v.shift_positions(shift),
+            AnyResponse::SetDataBreakpoints(v) => v.shift_positions(shift),
+            AnyResponse::SetExceptionBreakpoints(v) => v.shift_positions(shift),
+            AnyResponse::SetFunctionBreakpoints(v) => v.shift_positions(shift),
+            AnyResponse::SetInstructionBreakpoints(v) => v.shift_positions(shift),
+            AnyResponse::StackTrace(v) => v.shift_positions(shift),
+            AnyResponse::StepInTargets(v) => v.shift_positions(shift),
+            _ => {}
+        }
+    }
+}
+
+impl Positions for AnyEvent {
+    fn shift_positions(&mut self, shift: Shift) {
+        match self {
+            AnyEvent::Breakpoint(v) => v.shift_positions(shift),
+            AnyEvent::Output(v) => v.shift_positions(shift),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::ops::Range;
+
+use super::Position;
+
+/// Returns the byte offset of the UTF-16 `column` in `line`.
+///
+/// Returns `None` if `column` is past the end of `line`, or in the middle of a
+/// character.
+pub fn utf16_to_utf8(line: &str, column: u32) -> Option<usize> {
+    let mut utf16 = 0;
+    for (offset, c) in line.char_indices() {
+        if utf16 >= column {
+            return (utf16 == column).then_some(offset);
+        }
+        utf16 += c.len_utf16() as u32;
+    }
+    (utf16 == column).then_some(line.len())
+}
+
+/// Returns the UTF-16 column of the byte `offset` in `line`.
+///
+/// Returns `None` if `offset` is past the end of `line`, or in the middle of a
+/// character.
+pub fn utf8_to_utf16(line: &str, offset: usize) -> Option<u32> {
+    Some(line.get(..offset)?.encode_utf16().count() as u32)
+}
+
+/// An index of the lines of a text, converting between byte offsets and
+/// positions with UTF-16 columns.
+///
+/// Lines end with `\n` or `\r\n`, which are not part of them. Finding a line
+/// takes O(log n) in the number of lines, and converting a column O(log n) in
+/// the number of non-ASCII characters of the line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    lines: Vec<Line>,
+    len: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Line {
+    /// The byte range of the line in the text, without its terminator.
+    range: Range<usize>,
+    /// The characters of the line longer than a byte, in order.
+    wide: Vec<WideChar>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WideChar {
+    /// The byte offset of the character in the line.
+    utf8: usize,
+    /// The UTF-16 column of the character.
+    utf16: u32,
+    len_utf8: u8,
+    len_utf16: u8,
+}
+
+impl WideChar {
+    /// The difference between the byte offsets and the UTF-16 columns after
+    /// the character.
+    fn delta(&self) -> usize {
+        self.utf8 + usize::from(self.len_utf8) - self.utf16 as usize - usize::from(self.len_utf16)
+    }
+}
+
+impl LineIndex {
+    /// Indexes the lines of `text`.
+    pub fn new(text: &str) -> LineIndex {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        for line in text.split_inclusive('\n') {
+            let content = line.strip_suffix('\n').unwrap_or(line);
+            let content = content.strip_suffix('\r').unwrap_or(content);
+            lines.push(Line::new(start, content));
+            start += line.len();
+        }
+        // After a final line terminator, or in an empty text.
+        if text.is_empty() || text.ends_with('\n') {
+            lines.push(Line::new(start, ""));
+        }
+        LineIndex { lines, len: text.len() }
+    }
+
+    /// Returns the number of lines.
+    pub fn line_count(&self) -> u32 {
+        self.lines.len() as u32
+    }
+
+    /// Returns the byte range of the 0-based `line`, without its terminator.
+    pub fn line(&self, line: u32) -> Option<Range<usize>> {
+        Some(self.lines.get(line as usize)?.range.clone())
+    }
+
+    /// Returns the byte offset of `position`, a 0-based line and UTF-16
+    /// column.
+    ///
+    /// Returns `None` if the position is past the end of its line, or in the
+    /// middle of a character.
+    pub fn offset(&self, position: Position) -> Option<usize> {
+        let line = self.lines.get(position.line as usize)?;
+        let offset = line.range.start + line.utf16_to_utf8(position.column)?;
+        (offset <= line.range.end).then_some(offset)
+    }
+
+    /// Returns the position, a 0-based line and UTF-16 column, of the byte
+    /// `offset`.
+    ///
+    /// Offsets in a line terminator are at the end of their line. Returns
+    /// `None` if `offset` is past the end of the text, or in the middle of a
+    /// character.
+    pub fn position(&self, offset: usize) -> Option<Position> {
+        if offset > self.len {
+            return None;
+        }
+        let line = self.lines.partition_point(|line| line.range.start <= offset) - 1;
+        let column = self.lines[line].utf8_to_utf16(offset)?;
+        Some(Position {
+            line: line as u32,
+            column,
+        })
+    }
+}
+
+impl Line {
+    fn new(start: usize, content: &str) -> Line {
+        let mut wide = Vec::new();
+        let mut utf16 = 0;
+        for (utf8, c) in content.char_indices() {
+            if c.len_utf8() > 1 {
+                wide.push(WideChar {
+                    utf8,
+                    utf16,
+                    len_utf8: c.len_utf8() as u8,
+                    len_utf16: c.len_utf16() as u8,
+                });
+            }
+            utf16 += c.len_utf16() as u32;
+        }
+        Line {
+            range: start..start + content.len(),
+            wide,
+        }
+    }
+
+    /// Converts a UTF-16 column to a byte offset in the line, which may be
+    /// past its end.
+    fn utf16_to_utf8(&self, column: u32) -> Option<usize> {
+        let after = self.wide.partition_point(|c| c.utf16 < column);
+        let Some(before) = after.checked_sub(1).map(|i| self.wide[i]) else {
+            return Some(column as usize);
+        };
+        if column < before.utf16 + u32::from(before.len_utf16) {
+            return None;
+        }
+        Some(column as usize + before.delta())
+    }
+
+    /// Converts the byte `offset` of the text to a UTF-16 column of the line.
+    fn utf8_to_utf16(&self, offset: usize) -> Option<u32> {
+        let offset = offset.min(self.range.end) - self.range.start;
+        let after = self.wide.partition_point(|c| c.utf8 < offset);
+        let Some(before) = after.checked_sub(1).map(|i| self.wide[i]) else {
+            return Some(offset as u32);
+        };
+        if offset < before.utf8 + usize::from(before.len_utf8) {
+            return None;
+        }
+        Some((offset - before.delta()) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "let a = 1;\r\nlet é = \"😀\"; // 中\n\nend";
+
+    #[test]
+    fn test_line_functions() {
+        let line = "é😀x";
+        assert_eq!(utf16_to_utf8(line, 0), Some(0));
+        assert_eq!(utf16_to_utf8(line, 1), Some(2));
+        assert_eq!(utf16_to_utf8(line, 2), None);
+        assert_eq!(utf16_to_utf8(line, 3), Some(6));
+        assert_eq!(utf16_to_utf8(line, 4), Some(7));
+        assert_eq!(utf16_to_utf8(line, 5), None);
+        assert_eq!(utf8_to_utf16(line, 6), Some(3));
+        assert_eq!(utf8_to_utf16(line, 3), None);
+        assert_eq!(utf8_to_utf16(line, 8), None);
+    }
+
+    #[test]
+    fn test_lines() {
+        let index = LineIndex::new(TEXT);
+        assert_eq!(index.line_count(), 4);
+        let lines: Vec<_> = (0..4).map(|line| &TEXT[index.line(line).unwrap()]).collect();
+        assert_eq!(lines, ["let a = 1;", "let é = \"😀\"; // 中", "", "end"]);
+        assert_eq!(index.line(4), None);
+
+        assert_eq!(LineIndex::new("").line_count(), 1);
+        assert_eq!(LineIndex::new("a\n").line_count(), 2);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let index = LineIndex::new(TEXT);
+        let position = |line, column| Position { line, column };
+        assert_eq!(index.offset(position(0, 10)), Some(10));
+        assert_eq!(index.offset(position(0, 11)), None);
+        assert_eq!(index.offset(position(1, 9)), Some(22));
+        assert_eq!(index.offset(position(1, 10)), None);
+        assert_eq!(index.offset(position(1, 11)), Some(26));
+        assert_eq!(index.offset(position(1, 18)), Some(35));
+        assert_eq!(index.offset(position(1, 19)), None);
+        assert_eq!(index.offset(position(3, 3)), Some(TEXT.len()));
+
+        assert_eq!(index.position(11), Some(position(0, 10)));
+        assert_eq!(index.position(22), Some(position(1, 9)));
+        assert_eq!(index.position(23), None);
+        assert_eq!(index.position(26), Some(position(1, 11)));
+        assert_eq!(index.position(TEXT.len()), Some(position(3, 3)));
+        assert_eq!(index.position(TEXT.len() + 1), None);
+
+        // Every character boundary round-trips, and agrees with the line functions.
+        for (offset, _) in TEXT.char_indices() {
+            let position = index.position(offset).unwrap();
+            let range = index.line(position.line).unwrap();
+            if offset <= range.end {
+                assert_eq!(index.offset(position), Some(offset));
+                let line = &TEXT[range.clone()];
+                assert_eq!(utf16_to_utf8(line, position.column), Some(offset - range.start));
+            }
+        }
+    }
+}
@@ -3,7 +3,7 @@
 
 #![allow(clippy::doc_lazy_continuation)]
 
-pub use crate::IRequest;
+pub use crate::{AdapterToClient, ClientToAdapter, IRequest};
 
 /// The `attach` request is sent from the client to the debug adapter to attach to a debuggee that is already running.
 /// Since attaching is debugger/runtime specific, the arguments for this request are not part of this specification.
@@ -17,6 +17,8 @@ impl IRequest for Attach {
     type Response = ();
 }
 
+impl ClientToAdapter for Attach {}
+
 /// The `breakpointLocations` request returns all possible locations for source breakpoints in a given range.
 /// Clients should only call this request if the corresponding capability `supportsBreakpointLocationsRequest` is true.
 ///
@@ -25,10 +27,13 @@ pub enum BreakpointLocations {}
 
 impl IRequest for BreakpointLocations {
     const COMMAND: &'static str = "breakpointLocations";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsBreakpointLocationsRequest");
     type Arguments = crate::BreakpointLocationsArguments;
     type Response = crate::BreakpointLocationsResponse;
 }
 
+impl ClientToAdapter for BreakpointLocations {}
+
 /// The `cancel` request is used by the client in two situations:
 /// - to indicate that it is no longer interested in the result produced by a specific request issued earlier
 /// - to cancel a progress sequence.
@@ -45,10 +50,13 @@ pub enum Cancel {}
 
 impl IRequest for Cancel {
     const COMMAND: &'static str = "cancel";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsCancelRequest");
     type Arguments = crate::CancelArguments;
     type Response = ();
 }
 
+impl ClientToAdapter for Cancel {}
+
 /// Returns a list of possible completions for a given caret position and text.
 /// Clients should only call this request if the corresponding capability `supportsCompletionsRequest` is true.
 ///
@@ -57,10 +65,13 @@ pub enum Completions {}
 
 impl IRequest for Completions {
     const COMMAND: &'static str = "completions";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsCompletionsRequest");
     type Arguments = crate::CompletionsArguments;
     type Response = crate::CompletionsResponse;
 }
 
+impl ClientToAdapter for Completions {}
+
 /// This request indicates that the client has finished initialization of the debug adapter.
 /// So it is the last request in the sequence of configuration requests (which was started by the `initialized` event).
 /// Clients should only call this request if the corresponding capability `supportsConfigurationDoneRequest` is true.
@@ -70,10 +81,13 @@ pub enum ConfigurationDone {}
 
 impl IRequest for ConfigurationDone {
     const COMMAND: &'static str = "configurationDone";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsConfigurationDoneRequest");
     type Arguments = crate::ConfigurationDoneArguments;
     type Response = ();
 }
 
+impl ClientToAdapter for ConfigurationDone {}
+
 /// The request resumes execution of all threads. If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true resumes only the specified thread. If not all threads were resumed, the `allThreadsContinued` attribute of the response should be set to false.
 ///
 /// See [ContinueRequest.](https://microsoft.github.io/debug-adapter-protocol/specification#Requests_Continue)
@@ -85,6 +99,8 @@ impl IRequest for Continue {
     type Response = crate::ContinueResponse;
 }
 
+impl ClientToAdapter for Continue {}
+
 /// Obtains information on a possible data breakpoint that could be set on an expression or variable.
 /// Clients should only call this request if the corresponding capability `supportsDataBreakpoints` is true.
 ///
@@ -93,10 +109,13 @@ pub enum DataBreakpointInfo {}
 
 impl IRequest for DataBreakpointInfo {
     const COMMAND: &'static str = "dataBreakpointInfo";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsDataBreakpoints");
     type Arguments = crate::DataBreakpointInfoArguments;
     type Response = crate::DataBreakpointInfoResponse;
 }
 
+impl ClientToAdapter for DataBreakpointInfo {}
+
 /// Disassembles code stored at the provided location.
 /// Clients should only call this request if the corresponding capability `supportsDisassembleRequest` is true.
 ///
@@ -105,10 +124,13 @@ pub enum Disassemble {}
 
 impl IRequest for Disassemble {
     const COMMAND: &'static str = "disassemble";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsDisassembleRequest");
     type Arguments = crate::DisassembleArguments;
     type Response = crate::DisassembleResponse;
 }
 
+impl ClientToAdapter for Disassemble {}
+
 /// The `disconnect` request asks the debug adapter to disconnect from the debuggee (thus ending the debug session) and then to shut down itself (the debug adapter).
 /// In addition, the debug adapter must terminate the debuggee if it was started with the `launch` request. If an `attach` request was used to connect to the debuggee, then the debug adapter must not terminate the debuggee.
 /// This implicit behavior of when to terminate the debuggee can be overridden with the `terminateDebuggee` argument (which is only supported by a debug adapter if the corresponding capability `supportTerminateDebuggee` is true).
@@ -122,6 +144,8 @@ impl IRequest for Disconnect {
     type Response = ();
 }
 
+impl ClientToAdapter for Disconnect {}
+
 /// Evaluates the given expression in the context of a stack frame.
 /// The expression has access to any variables and arguments that are in scope.
 ///
@@ -134,6 +158,8 @@ impl IRequest for Evaluate {
     type Response = crate::EvaluateResponse;
 }
 
+impl ClientToAdapter for Evaluate {}
+
 /// Retrieves the details of the exception that caused this event to be raised.
 /// Clients should only call this request if the corresponding capability `supportsExceptionInfoRequest` is true.
 ///
@@ -142,10 +168,13 @@ pub enum ExceptionInfo {}
 
 impl IRequest for ExceptionInfo {
     const COMMAND: &'static str = "exceptionInfo";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsExceptionInfoRequest");
     type Arguments = crate::ExceptionInfoArguments;
     type Response = crate::ExceptionInfoResponse;
 }
 
+impl ClientToAdapter for ExceptionInfo {}
+
 /// The request sets the location where the debuggee will continue to run.
 /// This makes it possible to skip the execution of code or to execute code again.
 /// The code between the current location and the goto target is not executed but skipped.
@@ -157,10 +186,13 @@ pub enum Goto {}
 
 impl IRequest for Goto {
     const COMMAND: &'static str = "goto";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsGotoTargetsRequest");
     type Arguments = crate::GotoArguments;
     type Response = ();
 }
 
+impl ClientToAdapter for Goto {}
+
 /// This request retrieves the possible goto targets for the specified source location.
 /// These targets can be used in the `goto` request.
 /// Clients should only call this request if the corresponding capability `supportsGotoTargetsRequest` is true.
@@ -170,10 +202,13 @@ pub enum GotoTargets {}
 
 impl IRequest for GotoTargets {
     const COMMAND: &'static str = "gotoTargets";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsGotoTargetsRequest");
     type Arguments = crate::GotoTargetsArguments;
     type Response = crate::GotoTargetsResponse;
 }
 
+impl ClientToAdapter for GotoTargets {}
+
 /// The `initialize` request is sent as the first request from the client to the debug adapter in order to configure it with client capabilities and to retrieve capabilities from the debug adapter.
 /// Until the debug adapter has responded with an `initialize` response, the client must not send any additional requests or events to the debug adapter.
 /// In addition the debug adapter is not allowed to send any requests or events to the client until it has responded with an `initialize` response.
@@ -188,6 +223,8 @@ impl IRequest for Initialize {
     type Response = crate::Capabilities;
 }
 
+impl ClientToAdapter for Initialize {}
+
 /// This launch request is sent from the client to the debug adapter to start the debuggee with or without debugging (if `noDebug` is true).
 /// Since launching is debugger/runtime specific, the arguments for this request are not part of this specification.
 ///
@@ -200,6 +237,8 @@ impl IRequest for Launch {
     type Response = ();
 }
 
+impl ClientToAdapter for Launch {}
+
 /// Retrieves the set of all sources currently loaded by the debugged process.
 /// Clients should only call this request if the corresponding capability `supportsLoadedSourcesRequest` is true.
 ///
@@ -208,10 +247,13 @@ pub enum LoadedSources {}
 
 impl IRequest for LoadedSources {
     const COMMAND: &'static str = "loadedSources";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsLoadedSourcesRequest");
     type Arguments = crate::LoadedSourcesArguments;
     type Response = crate::LoadedSourcesResponse;
 }
 
+impl ClientToAdapter for LoadedSources {}
+
 /// Looks up information about a location reference previously returned by the debug adapter.
 ///
 /// See [LocationsRequest.](https://microsoft.github.io/debug-adapter-protocol/specification#Requests_Locations)
@@ -223,6 +265,8 @@ impl IRequest for Locations {
     type Response = crate::LocationsResponse;
 }
 
+impl ClientToAdapter for Locations {}
+
 /// Modules can be retrieved from the debug adapter with this request which can either return all modules or a range of modules to support paging.
 /// Clients should only call this request if the corresponding capability `supportsModulesRequest` is true.
 ///
@@ -231,10 +275,13 @@ pub enum Modules {}
 
 impl IRequest for Modules {
     const COMMAND: &'static str = "modules";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsModulesRequest");
     type Arguments = crate::ModulesArguments;
     type Response = crate::ModulesResponse;
 }
 
+impl ClientToAdapter for Modules {}
+
 /// The request executes one step (in the given granularity) for the specified thread and allows all other threads to run freely by resuming them.
 /// If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true prevents other suspended threads from resuming.
 /// The debug adapter first sends the response and then a `stopped` event (with reason `step`) after the step has completed.
@@ -248,6 +295,8 @@ impl IRequest for Next {
     type Response = ();
 }
 
+impl ClientToAdapter for Next {}
+
 /// The request suspends the debuggee.
 /// The debug adapter first sends the response and then a `stopped` event (with reason `pause`) after the thread has been paused successfully.
 ///
@@ -260,6 +309,8 @@ impl IRequest for Pause {
     type Response = ();
 }
 
+impl ClientToAdapter for Pause {}
+
 /// Reads bytes from memory at the provided location.
 /// Clients should only call this request if the corresponding capability `supportsReadMemoryRequest` is true.
 ///
@@ -268,10 +319,13 @@ pub enum ReadMemory {}
 
 impl IRequest for ReadMemory {
     const COMMAND: &'static str = "readMemory";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsReadMemoryRequest");
     type Arguments = crate::ReadMemoryArguments;
     type Response = crate::ReadMemoryResponse;
 }
 
+impl ClientToAdapter for ReadMemory {}
+
 /// The request restarts execution of the specified stack frame.
 /// The debug adapter first sends the response and then a `stopped` event (with reason `restart`) after the restart has completed.
 /// Clients should only call this request if the corresponding capability `supportsRestartFrame` is true.
@@ -281,10 +335,13 @@ pub enum RestartFrame {}
 
 impl IRequest for RestartFrame {
     const COMMAND: &'static str = "restartFrame";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsRestartFrame");
     type Arguments = crate::RestartFrameArguments;
     type Response = ();
 }
 
+impl ClientToAdapter for RestartFrame {}
+
 /// Restarts a debug session. Clients should only call this request if the corresponding capability `supportsRestartRequest` is true.
 /// If the capability is missing or has the value false, a typical client emulates `restart` by terminating the debug adapter first and then launching it anew.
 ///
@@ -293,10 +350,13 @@ pub enum Restart {}
 
 impl IRequest for Restart {
     const COMMAND: &'static str = "restart";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsRestartRequest");
     type Arguments = crate::RestartArguments;
     type Response = ();
 }
 
+impl ClientToAdapter for Restart {}
+
 /// The request resumes backward execution of all threads. If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true resumes only the specified thread. If not all threads were resumed, the `allThreadsContinued` attribute of the response should be set to false.
 /// Clients should only call this request if the corresponding capability `supportsStepBack` is true.
 ///
@@ -305,10 +365,13 @@ pub enum ReverseContinue {}
 
 impl IRequest for ReverseContinue {
     const COMMAND: &'static str = "reverseContinue";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsStepBack");
     type Arguments = crate::ReverseContinueArguments;
     type Response = ();
 }
 
+impl ClientToAdapter for ReverseContinue {}
+
 /// This request is sent from the debug adapter to the client to run a command in a terminal.
 /// This is typically used to launch the debuggee in a terminal provided by the client.
 /// This request should only be called if the corresponding client capability `supportsRunInTerminalRequest` is true.
@@ -320,10 +383,14 @@ pub enum RunInTerminal {}
 
 impl IRequest for RunInTerminal {
     const COMMAND: &'static str = "runInTerminal";
+    const DIRECTION: crate::Direction = crate::Direction::AdapterToClient;
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsRunInTerminalRequest");
     type Arguments = crate::RunInTerminalRequestArguments;
     type Response = crate::RunInTerminalResponse;
 }
 
+impl AdapterToClient for RunInTerminal {}
+
 /// The request returns the variable scopes for a given stack frame ID.
 ///
 /// See [ScopesRequest.](https://microsoft.github.io/debug-adapter-protocol/specification#Requests_Scopes)
@@ -335,6 +402,8 @@ impl IRequest for Scopes {
     type Response = crate::ScopesResponse;
 }
 
+impl ClientToAdapter for Scopes {}
+
 /// Sets multiple breakpoints for a single source and clears all previous breakpoints in that source.
 /// To clear all breakpoint for a source, specify an empty array.
 /// When a breakpoint is hit, a `stopped` event (with reason `breakpoint`) is generated.
@@ -348,6 +417,8 @@ impl IRequest for SetBreakpoints {
     type Response = crate::SetBreakpointsResponse;
 }
 
+impl ClientToAdapter for SetBreakpoints {}
+
 /// Replaces all existing data breakpoints with new data breakpoints.
 /// To clear all data breakpoints, specify an empty array.
 /// When a data breakpoint is hit, a `stopped` event (with reason `data breakpoint`) is generated.
@@ -358,10 +429,13 @@ pub enum SetDataBreakpoints {}
 
 impl IRequest for SetDataBreakpoints {
     const COMMAND: &'static str = "setDataBreakpoints";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsDataBreakpoints");
     type Arguments = crate::SetDataBreakpointsArguments;
     type Response = crate::SetDataBreakpointsResponse;
 }
 
+impl ClientToAdapter for SetDataBreakpoints {}
+
 /// The request configures the debugger's response to thrown exceptions. Each of the `filters`, `filterOptions`, and `exceptionOptions` in the request are independent configurations to a debug adapter indicating a kind of exception to catch. An exception thrown in a program should result in a `stopped` event from the debug adapter (with reason `exception`) if any of the configured filters match.
 /// Clients should only call this request if the corresponding capability `exceptionBreakpointFilters` returns one or more filters.
 ///
@@ -374,6 +448,8 @@ impl IRequest for SetExceptionBreakpoints {
     type Response = crate::SetExceptionBreakpointsResponse;
 }
 
+impl ClientToAdapter for SetExceptionBreakpoints {}
+
 /// Evaluates the given `value` expression and assigns it to the `expression` which must be a modifiable l-value.
 /// The expressions have access to any variables and arguments that are in scope of the specified frame.
 /// Clients should only call this request if the corresponding capability `supportsSetExpression` is true.
@@ -384,10 +460,13 @@ pub enum SetExpression {}
 
 impl IRequest for SetExpression {
     const COMMAND: &'static str = "setExpression";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsSetExpression");
     type Arguments = crate::SetExpressionArguments;
     type Response = crate::SetExpressionResponse;
 }
 
+impl ClientToAdapter for SetExpression {}
+
 /// Replaces all existing function breakpoints with new function breakpoints.
 /// To clear all function breakpoints, specify an empty array.
 /// When a function breakpoint is hit, a `stopped` event (with reason `function breakpoint`) is generated.
@@ -398,10 +477,13 @@ pub enum SetFunctionBreakpoints {}
 
 impl IRequest for SetFunctionBreakpoints {
     const COMMAND: &'static str = "setFunctionBreakpoints";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsFunctionBreakpoints");
     type Arguments = crate::SetFunctionBreakpointsArguments;
     type Response = crate::SetFunctionBreakpointsResponse;
 }
 
+impl ClientToAdapter for SetFunctionBreakpoints {}
+
 /// Replaces all existing instruction breakpoints. Typically, instruction breakpoints would be set from a disassembly window.
 /// To clear all instruction breakpoints, specify an empty array.
 /// When an instruction breakpoint is hit, a `stopped` event (with reason `instruction breakpoint`) is generated.
@@ -412,10 +494,13 @@ pub enum SetInstructionBreakpoints {}
 
 impl IRequest for SetInstructionBreakpoints {
     const COMMAND: &'static str = "setInstructionBreakpoints";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsInstructionBreakpoints");
     type Arguments = crate::SetInstructionBreakpointsArguments;
     type Response = crate::SetInstructionBreakpointsResponse;
 }
 
+impl ClientToAdapter for SetInstructionBreakpoints {}
+
 /// Set the variable with the given name in the variable container to a new value. Clients should only call this request if the corresponding capability `supportsSetVariable` is true.
 /// If a debug adapter implements both `setVariable` and `setExpression`, a client will only use `setExpression` if the variable has an `evaluateName` property.
 ///
@@ -424,10 +509,13 @@ pub enum SetVariable {}
 
 impl IRequest for SetVariable {
     const COMMAND: &'static str = "setVariable";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsSetVariable");
     type Arguments = crate::SetVariableArguments;
     type Response = crate::SetVariableResponse;
 }
 
+impl ClientToAdapter for SetVariable {}
+
 /// The request retrieves the source code for a given source reference.
 ///
 /// See [SourceRequest.](https://microsoft.github.io/debug-adapter-protocol/specification#Requests_Source)
@@ -439,6 +527,8 @@ impl IRequest for Source {
     type Response = crate::SourceResponse;
 }
 
+impl ClientToAdapter for Source {}
+
 /// The request returns a stacktrace from the current execution state of a given thread.
 /// A client can request all stack frames by omitting the startFrame and levels arguments. For performance-conscious clients and if the corresponding capability `supportsDelayedStackTraceLoading` is true, stack frames can be retrieved in a piecemeal way with the `startFrame` and `levels` arguments. The response of the `stackTrace` request may contain a `totalFrames` property that hints at the total number of frames in the stack. If a client needs this total number upfront, it can issue a request for a single (first) frame and depending on the value of `totalFrames` decide how to proceed. In any case a client should be prepared to receive fewer frames than requested, which is an indication that the end of the stack has been reached.
 ///
@@ -451,6 +541,8 @@ impl IRequest for StackTrace {
     type Response = crate::StackTraceResponse;
 }
 
+impl ClientToAdapter for StackTrace {}
+
 /// This request is sent from the debug adapter to the client to start a new debug session of the same type as the caller.
 /// This request should only be sent if the corresponding client capability `supportsStartDebuggingRequest` is true.
 /// A client implementation of `startDebugging` should start a new debug session (of the same type as the caller) in the same way that the caller's session was started. If the client supports hierarchical debug sessions, the newly created session can be treated as a child of the caller session.
@@ -460,10 +552,14 @@ pub enum StartDebugging {}
 
 impl IRequest for StartDebugging {
     const COMMAND: &'static str = "startDebugging";
+    const DIRECTION: crate::Direction = crate::Direction::AdapterToClient;
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsStartDebuggingRequest");
     type Arguments = crate::StartDebuggingRequestArguments;
     type Response = ();
 }
 
+impl AdapterToClient for StartDebugging {}
+
 /// The request executes one backward step (in the given granularity) for the specified thread and allows all other threads to run backward freely by resuming them.
 /// If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true prevents other suspended threads from resuming.
 /// The debug adapter first sends the response and then a `stopped` event (with reason `step`) after the step has completed.
@@ -474,10 +570,13 @@ pub enum StepBack {}
 
 impl IRequest for StepBack {
     const COMMAND: &'static str = "stepBack";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsStepBack");
     type Arguments = crate::StepBackArguments;
     type Response = ();
 }
 
+impl ClientToAdapter for StepBack {}
+
 /// The request resumes the given thread to step into a function/method and allows all other threads to run freely by resuming them.
 /// If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true prevents other suspended threads from resuming.
 /// If the request cannot step into a target, `stepIn` behaves like the `next` request.
@@ -495,6 +594,8 @@ impl IRequest for StepIn {
     type Response = ();
 }
 
+impl ClientToAdapter for StepIn {}
+
 /// This request retrieves the possible step-in targets for the specified stack frame.
 /// These targets can be used in the `stepIn` request.
 /// Clients should only call this request if the corresponding capability `supportsStepInTargetsRequest` is true.
@@ -504,10 +605,13 @@ pub enum StepInTargets {}
 
 impl IRequest for StepInTargets {
     const COMMAND: &'static str = "stepInTargets";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsStepInTargetsRequest");
     type Arguments = crate::StepInTargetsArguments;
     type Response = crate::StepInTargetsResponse;
 }
 
+impl ClientToAdapter for StepInTargets {}
+
 /// The request resumes the given thread to step out (return) from a function/method and allows all other threads to run freely by resuming them.
 /// If the debug adapter supports single thread execution (see capability `supportsSingleThreadExecutionRequests`), setting the `singleThread` argument to true prevents other suspended threads from resuming.
 /// The debug adapter first sends the response and then a `stopped` event (with reason `step`) after the step has completed.
@@ -521,6 +625,8 @@ impl IRequest for StepOut {
     type Response = ();
 }
 
+impl ClientToAdapter for StepOut {}
+
 /// The `terminate` request is sent from the client to the debug adapter in order to shut down the debuggee gracefully. Clients should only call this request if the capability `supportsTerminateRequest` is true.
 /// Typically a debug adapter implements `terminate` by sending a software signal which the debuggee intercepts in order to clean things up properly before terminating itself.
 /// Please note that this request does not directly affect the state of the debug session: if the debuggee decides to veto the graceful shutdown for any reason by not terminating itself, then the debug session just continues.
@@ -531,10 +637,13 @@ pub enum Terminate {}
 
 impl IRequest for Terminate {
     const COMMAND: &'static str = "terminate";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsTerminateRequest");
     type Arguments = crate::TerminateArguments;
     type Response = ();
 }
 
+impl ClientToAdapter for Terminate {}
+
 /// The request terminates the threads with the given ids.
 /// Clients should only call this request if the corresponding capability `supportsTerminateThreadsRequest` is true.
 ///
@@ -543,10 +652,13 @@ pub enum TerminateThreads {}
 
 impl IRequest for TerminateThreads {
     const COMMAND: &'static str = "terminateThreads";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsTerminateThreadsRequest");
     type Arguments = crate::TerminateThreadsArguments;
     type Response = ();
 }
 
+impl ClientToAdapter for TerminateThreads {}
+
 /// The request retrieves a list of all threads.
 ///
 /// See [ThreadsRequest.](https://microsoft.github.io/debug-adapter-protocol/specification#Requests_Threads)
@@ -558,6 +670,8 @@ impl IRequest for Threads {
     type Response = crate::ThreadsResponse;
 }
 
+impl ClientToAdapter for Threads {}
+
 /// Retrieves all child variables for the given variable reference.
 /// A filter can be used to limit the fetched children to either named or indexed children.
 ///
@@ -570,6 +684,8 @@ impl IRequest for Variables {
     type Response = crate::VariablesResponse;
 }
 
+impl ClientToAdapter for Variables {}
+
 /// Writes bytes to memory at the provided location.
 /// Clients should only call this request if the corresponding capability `supportsWriteMemoryRequest` is true.
 ///
@@ -578,6 +694,733 @@ pub enum WriteMemory {}
 
 impl IRequest for WriteMemory {
     const COMMAND: &'static str = "writeMemory";
+    const REQUIRED_CAPABILITY: Option<&'static str> = Some("supportsWriteMemoryRequest");
     type Arguments = crate::WriteMemoryArguments;
     type Response = crate::WriteMemoryResponse;
 }
+
+impl ClientToAdapter for WriteMemory {}
+
+/// Any request, keyed by its `command` field.
+///
+/// Requests whose `command` is unknown to this crate are kept as [`AnyRequest::Other`].
+#[derive(Debug, Clone)]
+pub enum AnyRequest {
+    /// See [`Attach`].
+    Attach(crate::AttachRequestArguments),
+    /// See [`BreakpointLocations`].
+    BreakpointLocations(crate::BreakpointLocationsArguments),
+    /// See [`Cancel`].
+    Cancel(crate::CancelArguments),
+    /// See [`Completions`].
+    Completions(crate::CompletionsArguments),
+    /// See [`ConfigurationDone`].
+    ConfigurationDone(crate::ConfigurationDoneArguments),
+    /// See [`Continue`].
+    Continue(crate::ContinueArguments),
+    /// See [`DataBreakpointInfo`].
+    DataBreakpointInfo(crate::DataBreakpointInfoArguments),
+    /// See [`Disassemble`].
+    Disassemble(crate::DisassembleArguments),
+    /// See [`Disconnect`].
+    Disconnect(crate::DisconnectArguments),
+    /// See [`Evaluate`].
+    Evaluate(crate::EvaluateArguments),
+    /// See [`ExceptionInfo`].
+    ExceptionInfo(crate::ExceptionInfoArguments),
+    /// See [`Goto`].
+    Goto(crate::GotoArguments),
+    /// See [`GotoTargets`].
+    GotoTargets(crate::GotoTargetsArguments),
+    /// See [`Initialize`].
+    Initialize(crate::InitializeRequestArguments),
+    /// See [`Launch`].
+    Launch(crate::LaunchRequestArguments),
+    /// See [`LoadedSources`].
+    LoadedSources(crate::LoadedSourcesArguments),
+    /// See [`Locations`].
+    Locations(crate::LocationsArguments),
+    /// See [`Modules`].
+    Modules(crate::ModulesArguments),
+    /// See [`Next`].
+    Next(crate::NextArguments),
+    /// See [`Pause`].
+    Pause(crate::PauseArguments),
+    /// See [`ReadMemory`].
+    ReadMemory(crate::ReadMemoryArguments),
+    /// See [`RestartFrame`].
+    RestartFrame(crate::RestartFrameArguments),
+    /// See [`Restart`].
+    Restart(crate::RestartArguments),
+    /// See [`ReverseContinue`].
+    ReverseContinue(crate::ReverseContinueArguments),
+    /// See [`RunInTerminal`].
+    RunInTerminal(crate::RunInTerminalRequestArguments),
+    /// See [`Scopes`].
+    Scopes(crate::ScopesArguments),
+    /// See [`SetBreakpoints`].
+    SetBreakpoints(crate::SetBreakpointsArguments),
+    /// See [`SetDataBreakpoints`].
+    SetDataBreakpoints(crate::SetDataBreakpointsArguments),
+    /// See [`SetExceptionBreakpoints`].
+    SetExceptionBreakpoints(crate::SetExceptionBreakpointsArguments),
+    /// See [`SetExpression`].
+    SetExpression(crate::SetExpressionArguments),
+    /// See [`SetFunctionBreakpoints`].
+    SetFunctionBreakpoints(crate::SetFunctionBreakpointsArguments),
+    /// See [`SetInstructionBreakpoints`].
+    SetInstructionBreakpoints(crate::SetInstructionBreakpointsArguments),
+    /// See [`SetVariable`].
+    SetVariable(crate::SetVariableArguments),
+    /// See [`Source`].
+    Source(crate::SourceArguments),
+    /// See [`StackTrace`].
+    StackTrace(crate::StackTraceArguments),
+    /// See [`StartDebugging`].
+    StartDebugging(crate::StartDebuggingRequestArguments),
+    /// See [`StepBack`].
+    StepBack(crate::StepBackArguments),
+    /// See [`StepIn`].
+    StepIn(crate::StepInArguments),
+    /// See [`StepInTargets`].
+    StepInTargets(crate::StepInTargetsArguments),
+    /// See [`StepOut`].
+    StepOut(crate::StepOutArguments),
+    /// See [`Terminate`].
+    Terminate(crate::TerminateArguments),
+    /// See [`TerminateThreads`].
+    TerminateThreads(crate::TerminateThreadsArguments),
+    /// See [`Threads`].
+    Threads,
+    /// See [`Variables`].
+    Variables(crate::VariablesArguments),
+    /// See [`WriteMemory`].
+    WriteMemory(crate::WriteMemoryArguments),
+    /// A request whose `command` is unknown to this crate.
+    Other {
+        /// The command to execute.
+        command: String,
+        /// Object containing arguments for the command.
+        arguments: serde_json::Value,
+    },
+}
+
+impl AnyRequest {
+    /// Parses a request from its command and arguments.
+    pub fn from_parts(command: String, arguments: serde_json::Value) -> Result<AnyRequest, crate::Error> {
+        Ok(match command.as_str() {
+            Attach::COMMAND => AnyRequest::Attach(crate::arguments_from_value(&arguments)?),
+            BreakpointLocations::COMMAND => AnyRequest::BreakpointLocations(crate::arguments_from_value(&arguments)?),
+            Cancel::COMMAND => AnyRequest::Cancel(crate::arguments_from_value(&arguments)?),
+            Completions::COMMAND => AnyRequest::Completions(crate::arguments_from_value(&arguments)?),
+            ConfigurationDone::COMMAND => AnyRequest::ConfigurationDone(crate::arguments_from_value(&arguments)?),
+            Continue::COMMAND => AnyRequest::Continue(crate::arguments_from_value(&arguments)?),
+            DataBreakpointInfo::COMMAND => AnyRequest::DataBreakpointInfo(crate::arguments_from_value(&arguments)?),
+            Disassemble::COMMAND => AnyRequest::Disassemble(crate::arguments_from_value(&arguments)?),
+            Disconnect::COMMAND => AnyRequest::Disconnect(crate::arguments_from_value(&arguments)?),
+            Evaluate::COMMAND => AnyRequest::Evaluate(crate::arguments_from_value(&arguments)?),
+            ExceptionInfo::COMMAND => AnyRequest::ExceptionInfo(crate::arguments_from_value(&arguments)?),
+            Goto::COMMAND => AnyRequest::Goto(crate::arguments_from_value(&arguments)?),
+            GotoTargets::COMMAND => AnyRequest::GotoTargets(crate::arguments_from_value(&arguments)?),
+            Initialize::COMMAND => AnyRequest::Initialize(crate::arguments_from_value(&arguments)?),
+            Launch::COMMAND => AnyRequest::Launch(crate::arguments_from_value(&arguments)?),
+            LoadedSources::COMMAND => AnyRequest::LoadedSources(crate::arguments_from_value(&arguments)?),
+            Locations::COMMAND => AnyRequest::Locations(crate::arguments_from_value(&arguments)?),
+            Modules::COMMAND => AnyRequest::Modules(crate::arguments_from_value(&arguments)?),
+            Next::COMMAND => AnyRequest::Next(crate::arguments_from_value(&arguments)?),
+            Pause::COMMAND => AnyRequest::Pause(crate::arguments_from_value(&arguments)?),
+            ReadMemory::COMMAND => AnyRequest::ReadMemory(crate::arguments_from_value(&arguments)?),
+            RestartFrame::COMMAND => AnyRequest::RestartFrame(crate::arguments_from_value(&arguments)?),
+            Restart::COMMAND => AnyRequest::Restart(crate::arguments_from_value(&arguments)?),
+            ReverseContinue::COMMAND => AnyRequest::ReverseContinue(crate::arguments_from_value(&arguments)?),
+            RunInTerminal::COMMAND => AnyRequest::RunInTerminal(crate::arguments_from_value(&arguments)?),
+            Scopes::COMMAND => AnyRequest::Scopes(crate::arguments_from_value(&arguments)?),
+            SetBreakpoints::COMMAND => AnyRequest::SetBreakpoints(crate::arguments_from_value(&arguments)?),
+            SetDataBreakpoints::COMMAND => AnyRequest::SetDataBreakpoints(crate::arguments_from_value(&arguments)?),
+            SetExceptionBreakpoints::COMMAND => {
+                AnyRequest::SetExceptionBreakpoints(crate::arguments_from_value(&arguments)?)
+            }
+            SetExpression::COMMAND => AnyRequest::SetExpression(crate::arguments_from_value(&arguments)?),
+            SetFunctionBreakpoints::COMMAND => {
+                AnyRequest::SetFunctionBreakpoints(crate::arguments_from_value(&arguments)?)
+            }
+            SetInstructionBreakpoints::COMMAND => {
+                AnyRequest::SetInstructionBreakpoints(crate::arguments_from_value(&arguments)?)
+            }
+            SetVariable::COMMAND => AnyRequest::SetVariable(crate::arguments_from_value(&arguments)?),
+            Source::COMMAND => AnyRequest::Source(crate::arguments_from_value(&arguments)?),
+            StackTrace::COMMAND => AnyRequest::StackTrace(crate::arguments_from_value(&arguments)?),
+            StartDebugging::COMMAND => AnyRequest::StartDebugging(crate::arguments_from_value(&arguments)?),
+            StepBack::COMMAND => AnyRequest::StepBack(crate::arguments_from_value(&arguments)?),
+            StepIn::COMMAND => AnyRequest::StepIn(crate::arguments_from_value(&arguments)?),
+            StepInTargets::COMMAND => AnyRequest::StepInTargets(crate::arguments_from_value(&arguments)?),
+            StepOut::COMMAND => AnyRequest::StepOut(crate::arguments_from_value(&arguments)?),
+            Terminate::COMMAND => AnyRequest::Terminate(crate::arguments_from_value(&arguments)?),
+            TerminateThreads::COMMAND => AnyRequest::TerminateThreads(crate::arguments_from_value(&arguments)?),
+            Threads::COMMAND => AnyRequest::Threads,
+            Variables::COMMAND => AnyRequest::Variables(crate::arguments_from_value(&arguments)?),
+            WriteMemory::COMMAND => AnyRequest::WriteMemory(crate::arguments_from_value(&arguments)?),
+            _ => AnyRequest::Other { command, arguments },
+        })
+    }
+
+    /// Returns the command of the request.
+    pub fn command(&self) -> &str {
+        match self {
+            AnyRequest::Attach(_) => Attach::COMMAND,
+            AnyRequest::BreakpointLocations(_) => BreakpointLocations::COMMAND,
+            AnyRequest::Cancel(_) => Cancel::COMMAND,
+            AnyRequest::Completions(_) => Completions::COMMAND,
+            AnyRequest::ConfigurationDone(_) => ConfigurationDone::COMMAND,
+            AnyRequest::Continue(_) => Continue::COMMAND,
+            AnyRequest::DataBreakpointInfo(_) => DataBreakpointInfo::COMMAND,
+            AnyRequest::Disassemble(_) => Disassemble::COMMAND,
+            AnyRequest::Disconnect(_) => Disconnect::COMMAND,
+            AnyRequest::Evaluate(_) => Evaluate::COMMAND,
+            AnyRequest::ExceptionInfo(_) => ExceptionInfo::COMMAND,
+            AnyRequest::Goto(_) => Goto::COMMAND,
+            AnyRequest::GotoTargets(_) => GotoTargets::COMMAND,
+            AnyRequest::Initialize(_) => Initialize::COMMAND,
+            AnyRequest::Launch(_) => Launch::COMMAND,
+            AnyRequest::LoadedSources(_) => LoadedSources::COMMAND,
+            AnyRequest::Locations(_) => Locations::COMMAND,
+            AnyRequest::Modules(_) => Modules::COMMAND,
+            AnyRequest::Next(_) => Next::COMMAND,
+            AnyRequest::Pause(_) => Pause::COMMAND,
+            AnyRequest::ReadMemory(_) => ReadMemory::COMMAND,
+            AnyRequest::RestartFrame(_) => RestartFrame::COMMAND,
+            AnyRequest::Restart(_) => Restart::COMMAND,
+            AnyRequest::ReverseContinue(_) => ReverseContinue::COMMAND,
+            AnyRequest::RunInTerminal(_) => RunInTerminal::COMMAND,
+            AnyRequest::Scopes(_) => Scopes::COMMAND,
+            AnyRequest::SetBreakpoints(_) => SetBreakpoints::COMMAND,
+            AnyRequest::SetDataBreakpoints(_) => SetDataBreakpoints::COMMAND,
+            AnyRequest::SetExceptionBreakpoints(_) => SetExceptionBreakpoints::COMMAND,
+            AnyRequest::SetExpression(_) => SetExpression::COMMAND,
+            AnyRequest::SetFunctionBreakpoints(_) => SetFunctionBreakpoints::COMMAND,
+            AnyRequest::SetInstructionBreakpoints(_) => SetInstructionBreakpoints::COMMAND,
+            AnyRequest::SetVariable(_) => SetVariable::COMMAND,
+            AnyRequest::Source(_) => Source::COMMAND,
+            AnyRequest::StackTrace(_) => StackTrace::COMMAND,
+            AnyRequest::StartDebugging(_) => StartDebugging::COMMAND,
+            AnyRequest::StepBack(_) => StepBack::COMMAND,
+            AnyRequest::StepIn(_) => StepIn::COMMAND,
+            AnyRequest::StepInTargets(_) => StepInTargets::COMMAND,
+            AnyRequest::StepOut(_) => StepOut::COMMAND,
+            AnyRequest::Terminate(_) => Terminate::COMMAND,
+            AnyRequest::TerminateThreads(_) => TerminateThreads::COMMAND,
+            AnyRequest::Threads => Threads::COMMAND,
+            AnyRequest::Variables(_) => Variables::COMMAND,
+            AnyRequest::WriteMemory(_) => WriteMemory::COMMAND,
+            AnyRequest::Other { command, .. } => command,
+        }
+    }
+
+    /// Converts into a request with the given sequence number.
+    pub fn into_request(self, seq: i64) -> crate::Request {
+        match self {
+            AnyRequest::Attach(v) => crate::Request::from_typed::<Attach>(seq, v),
+            AnyRequest::BreakpointLocations(v) => crate::Request::from_typed::<BreakpointLocations>(seq, v),
+            AnyRequest::Cancel(v) => crate::Request::from_typed::<Cancel>(seq, v),
+            AnyRequest::Completions(v) => crate::Request::from_typed::<Completions>(seq, v),
+            AnyRequest::ConfigurationDone(v) => crate::Request::from_typed::<ConfigurationDone>(seq, v),
+            AnyRequest::Continue(v) => crate::Request::from_typed::<Continue>(seq, v),
+            AnyRequest::DataBreakpointInfo(v) => crate::Request::from_typed::<DataBreakpointInfo>(seq, v),
+            AnyRequest::Disassemble(v) => crate::Request::from_typed::<Disassemble>(seq, v),
+            AnyRequest::Disconnect(v) => crate::Request::from_typed::<Disconnect>(seq, v),
+            AnyRequest::Evaluate(v) => crate::Request::from_typed::<Evaluate>(seq, v),
+            AnyRequest::ExceptionInfo(v) => crate::Request::from_typed::<ExceptionInfo>(seq, v),
+            AnyRequest::Goto(v) => crate::Request::from_typed::<Goto>(seq, v),
+            AnyRequest::GotoTargets(v) => crate::Request::from_typed::<GotoTargets>(seq, v),
+            AnyRequest::Initialize(v) => crate::Request::from_typed::<Initialize>(seq, v),
+            AnyRequest::Launch(v) => crate::Request::from_typed::<Launch>(seq, v),
+            AnyRequest::LoadedSources(v) => crate::Request::from_typed::<LoadedSources>(seq, v),
+            AnyRequest::Locations(v) => crate::Request::from_typed::<Locations>(seq, v),
+            AnyRequest::Modules(v) => crate::Request::from_typed::<Modules>(seq, v),
+            AnyRequest::Next(v) => crate::Request::from_typed::<Next>(seq, v),
+            AnyRequest::Pause(v) => crate::Request::from_typed::<Pause>(seq, v),
+            AnyRequest::ReadMemory(v) => crate::Request::from_typed::<ReadMemory>(seq, v),
+            AnyRequest::RestartFrame(v) => crate::Request::from_typed::<RestartFrame>(seq, v),
+            AnyRequest::Restart(v) => crate::Request::from_typed::<Restart>(seq, v),
+            AnyRequest::ReverseContinue(v) => crate::Request::from_typed::<ReverseContinue>(seq, v),
+            AnyRequest::RunInTerminal(v) => crate::Request::from_typed::<RunInTerminal>(seq, v),
+            AnyRequest::Scopes(v) => crate::Request::from_typed::<Scopes>(seq, v),
+            AnyRequest::SetBreakpoints(v) => crate::Request::from_typed::<SetBreakpoints>(seq, v),
+            AnyRequest::SetDataBreakpoints(v) => crate::Request::from_typed::<SetDataBreakpoints>(seq, v),
+            AnyRequest::SetExceptionBreakpoints(v) => crate::Request::from_typed::<SetExceptionBreakpoints>(seq, v),
+            AnyRequest::SetExpression(v) => crate::Request::from_typed::<SetExpression>(seq, v),
+            AnyRequest::SetFunctionBreakpoints(v) => crate::Request::from_typed::<SetFunctionBreakpoints>(seq, v),
+            AnyRequest::SetInstructionBreakpoints(v) => crate::Request::from_typed::<SetInstructionBreakpoints>(seq, v),
+            AnyRequest::SetVariable(v) => crate::Request::from_typed::<SetVariable>(seq, v),
+            AnyRequest::Source(v) => crate::Request::from_typed::<Source>(seq, v),
+            AnyRequest::StackTrace(v) => crate::Request::from_typed::<StackTrace>(seq, v),
+            AnyRequest::StartDebugging(v) => crate::Request::from_typed::<StartDebugging>(seq, v),
+            AnyRequest::StepBack(v) => crate::Request::from_typed::<StepBack>(seq, v),
+            AnyRequest::StepIn(v) => crate::Request::from_typed::<StepIn>(seq, v),
+            AnyRequest::StepInTargets(v) => crate::Request::from_typed::<StepInTargets>(seq, v),
+            AnyRequest::StepOut(v) => crate::Request::from_typed::<StepOut>(seq, v),
+            AnyRequest::Terminate(v) => crate::Request::from_typed::<Terminate>(seq, v),
+            AnyRequest::TerminateThreads(v) => crate::Request::from_typed::<TerminateThreads>(seq, v),
+            AnyRequest::Threads => crate::Request::from_typed::<Threads>(seq, ()),
+            AnyRequest::Variables(v) => crate::Request::from_typed::<Variables>(seq, v),
+            AnyRequest::WriteMemory(v) => crate::Request::from_typed::<WriteMemory>(seq, v),
+            AnyRequest::Other { command, arguments } => crate::Request::new(seq, command, arguments),
+        }
+    }
+}
+
+impl TryFrom<crate::Request> for AnyRequest {
+    type Error = crate::Error;
+
+    fn try_from(m: crate::Request) -> Result<Self, Self::Error> {
+        AnyRequest::from_parts(m.command, m.arguments)
+    }
+}
+
+impl From<AnyRequest> for crate::Request {
+    /// Converts with a zero sequence number, which is expected to be assigned
+    /// when the message is sent.
+    fn from(m: AnyRequest) -> Self {
+        m.into_request(0)
+    }
+}
+
+impl serde::Serialize for AnyRequest {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        const TAG: (&str, &str) = ("command", "arguments");
+        match self {
+            AnyRequest::Attach(v) => crate::serialize_tagged(serializer, TAG, Attach::COMMAND, Some(v)),
+            AnyRequest::BreakpointLocations(v) => {
+                crate::serialize_tagged(serializer, TAG, BreakpointLocations::COMMAND, Some(v))
+            }
+            AnyRequest::Cancel(v) => crate::serialize_tagged(serializer, TAG, Cancel::COMMAND, Some(v)),
+            AnyRequest::Completions(v) => crate::serialize_tagged(serializer, TAG, Completions::COMMAND, Some(v)),
+            AnyRequest::ConfigurationDone(v) => {
+                crate::serialize_tagged(serializer, TAG, ConfigurationDone::COMMAND, Some(v))
+            }
+            AnyRequest::Continue(v) => crate::serialize_tagged(serializer, TAG, Continue::COMMAND, Some(v)),
+            AnyRequest::DataBreakpointInfo(v) => {
+                crate::serialize_tagged(serializer, TAG, DataBreakpointInfo::COMMAND, Some(v))
+            }
+            AnyRequest::Disassemble(v) => crate::serialize_tagged(serializer, TAG, Disassemble::COMMAND, Some(v)),
+            AnyRequest::Disconnect(v) => crate::serialize_tagged(serializer, TAG, Disconnect::COMMAND, Some(v)),
+            AnyRequest::Evaluate(v) => crate::serialize_tagged(serializer, TAG, Evaluate::COMMAND, Some(v)),
+            AnyRequest::ExceptionInfo(v) => crate::serialize_tagged(serializer, TAG, ExceptionInfo::COMMAND, Some(v)),
+            AnyRequest::Goto(v) => crate::serialize_tagged(serializer, TAG, Goto::COMMAND, Some(v)),
+            AnyRequest::GotoTargets(v) => crate::serialize_tagged(serializer, TAG, GotoTargets::COMMAND, Some(v)),
+            AnyRequest::Initialize(v) => crate::serialize_tagged(serializer, TAG, Initialize::COMMAND, Some(v)),
+            AnyRequest::Launch(v) => crate::serialize_tagged(serializer, TAG, Launch::COMMAND, Some(v)),
+            AnyRequest::LoadedSources(v) => crate::serialize_tagged(serializer, TAG, LoadedSources::COMMAND, Some(v)),
+            AnyRequest::Locations(v) => crate::serialize_tagged(serializer, TAG, Locations::COMMAND, Some(v)),
+            AnyRequest::Modules(v) => crate::serialize_tagged(serializer, TAG, Modules::COMMAND, Some(v)),
+            AnyRequest::Next(v) => crate::serialize_tagged(serializer, TAG, Next::COMMAND, Some(v)),
+            AnyRequest::Pause(v) => crate::serialize_tagged(serializer, TAG, Pause::COMMAND, Some(v)),
+            AnyRequest::ReadMemory(v) => crate::serialize_tagged(serializer, TAG, ReadMemory::COMMAND, Some(v)),
+            AnyRequest::RestartFrame(v) => crate::serialize_tagged(serializer, TAG, RestartFrame::COMMAND, Some(v)),
+            AnyRequest::Restart(v) => crate::serialize_tagged(serializer, TAG, Restart::COMMAND, Some(v)),
+            AnyRequest::ReverseContinue(v) => {
+                crate::serialize_tagged(serializer, TAG, ReverseContinue::COMMAND, Some(v))
+            }
+            AnyRequest::RunInTerminal(v) => crate::serialize_tagged(serializer, TAG, RunInTerminal::COMMAND, Some(v)),
+            AnyRequest::Scopes(v) => crate::serialize_tagged(serializer, TAG, Scopes::COMMAND, Some(v)),
+            AnyRequest::SetBreakpoints(v) => crate::serialize_tagged(serializer, TAG, SetBreakpoints::COMMAND, Some(v)),
+            AnyRequest::SetDataBreakpoints(v) => {
+                crate::serialize_tagged(serializer, TAG, SetDataBreakpoints::COMMAND, Some(v))
+            }
+            AnyRequest::SetExceptionBreakpoints(v) => {
+                crate::serialize_tagged(serializer, TAG, SetExceptionBreakpoints::COMMAND, Some(v))
+            }
+            AnyRequest::SetExpression(v) => crate::serialize_tagged(serializer, TAG, SetExpression::COMMAND, Some(v)),
+            AnyRequest::SetFunctionBreakpoints(v) => {
+                crate::serialize_tagged(serializer, TAG, SetFunctionBreakpoints::COMMAND, Some(v))
+            }
+            AnyRequest::SetInstructionBreakpoints(v) => {
+                crate::serialize_tagged(serializer, TAG, SetInstructionBreakpoints::COMMAND, Some(v))
+            }
+            AnyRequest::SetVariable(v) => crate::serialize_tagged(serializer, TAG, SetVariable::COMMAND, Some(v)),
+            AnyRequest::Source(v) => crate::serialize_tagged(serializer, TAG, Source::COMMAND, Some(v)),
+            AnyRequest::StackTrace(v) => crate::serialize_tagged(serializer, TAG, StackTrace::COMMAND, Some(v)),
+            AnyRequest::StartDebugging(v) => crate::serialize_tagged(serializer, TAG, StartDebugging::COMMAND, Some(v)),
+            AnyRequest::StepBack(v) => crate::serialize_tagged(serializer, TAG, StepBack::COMMAND, Some(v)),
+            AnyRequest::StepIn(v) => crate::serialize_tagged(serializer, TAG, StepIn::COMMAND, Some(v)),
+            AnyRequest::StepInTargets(v) => crate::serialize_tagged(serializer, TAG, StepInTargets::COMMAND, Some(v)),
+            AnyRequest::StepOut(v) => crate::serialize_tagged(serializer, TAG, StepOut::COMMAND, Some(v)),
+            AnyRequest::Terminate(v) => crate::serialize_tagged(serializer, TAG, Terminate::COMMAND, Some(v)),
+            AnyRequest::TerminateThreads(v) => {
+                crate::serialize_tagged(serializer, TAG, TerminateThreads::COMMAND, Some(v))
+            }
+            AnyRequest::Threads => crate::serialize_tagged::<_, ()>(serializer, TAG, Threads::COMMAND, None),
+            AnyRequest::Variables(v) => crate::serialize_tagged(serializer, TAG, Variables::COMMAND, Some(v)),
+            AnyRequest::WriteMemory(v) => crate::serialize_tagged(serializer, TAG, WriteMemory::COMMAND, Some(v)),
+            AnyRequest::Other { command, arguments } => {
+                crate::serialize_tagged(serializer, TAG, command, Some(arguments).filter(|v| !v.is_null()))
+            }
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for AnyRequest {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Parts {
+            command: String,
+            #[serde(default)]
+            arguments: serde_json::Value,
+        }
+
+        let Parts { command, arguments } = Parts::deserialize(deserializer)?;
+        AnyRequest::from_parts(command, arguments).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Any response, keyed by the command of the request it answers.
+///
+/// The body type of a response depends on the command of its request, so responses are decoded by [`AnyResponse::decode`] given that command.
+#[derive(Debug, Clone)]
+pub enum AnyResponse {
+    /// See [`Attach`].
+    Attach,
+    /// See [`BreakpointLocations`].
+    BreakpointLocations(crate::BreakpointLocationsResponse),
+    /// See [`Cancel`].
+    Cancel,
+    /// See [`Completions`].
+    Completions(crate::CompletionsResponse),
+    /// See [`ConfigurationDone`].
+    ConfigurationDone,
+    /// See [`Continue`].
+    Continue(crate::ContinueResponse),
+    /// See [`DataBreakpointInfo`].
+    DataBreakpointInfo(crate::DataBreakpointInfoResponse),
+    /// See [`Disassemble`].
+    Disassemble(crate::DisassembleResponse),
+    /// See [`Disconnect`].
+    Disconnect,
+    /// See [`Evaluate`].
+    Evaluate(crate::EvaluateResponse),
+    /// See [`ExceptionInfo`].
+    ExceptionInfo(crate::ExceptionInfoResponse),
+    /// See [`Goto`].
+    Goto,
+    /// See [`GotoTargets`].
+    GotoTargets(crate::GotoTargetsResponse),
+    /// See [`Initialize`].
+    Initialize(crate::Capabilities),
+    /// See [`Launch`].
+    Launch,
+    /// See [`LoadedSources`].
+    LoadedSources(crate::LoadedSourcesResponse),
+    /// See [`Locations`].
+    Locations(crate::LocationsResponse),
+    /// See [`Modules`].
+    Modules(crate::ModulesResponse),
+    /// See [`Next`].
+    Next,
+    /// See [`Pause`].
+    Pause,
+    /// See [`ReadMemory`].
+    ReadMemory(crate::ReadMemoryResponse),
+    /// See [`RestartFrame`].
+    RestartFrame,
+    /// See [`Restart`].
+    Restart,
+    /// See [`ReverseContinue`].
+    ReverseContinue,
+    /// See [`RunInTerminal`].
+    RunInTerminal(crate::RunInTerminalResponse),
+    /// See [`Scopes`].
+    Scopes(crate::ScopesResponse),
+    /// See [`SetBreakpoints`].
+    SetBreakpoints(crate::SetBreakpointsResponse),
+    /// See [`SetDataBreakpoints`].
+    SetDataBreakpoints(crate::SetDataBreakpointsResponse),
+    /// See [`SetExceptionBreakpoints`].
+    SetExceptionBreakpoints(crate::SetExceptionBreakpointsResponse),
+    /// See [`SetExpression`].
+    SetExpression(crate::SetExpressionResponse),
+    /// See [`SetFunctionBreakpoints`].
+    SetFunctionBreakpoints(crate::SetFunctionBreakpointsResponse),
+    /// See [`SetInstructionBreakpoints`].
+    SetInstructionBreakpoints(crate::SetInstructionBreakpointsResponse),
+    /// See [`SetVariable`].
+    SetVariable(crate::SetVariableResponse),
+    /// See [`Source`].
+    Source(crate::SourceResponse),
+    /// See [`StackTrace`].
+    StackTrace(crate::StackTraceResponse),
+    /// See [`StartDebugging`].
+    StartDebugging,
+    /// See [`StepBack`].
+    StepBack,
+    /// See [`StepIn`].
+    StepIn,
+    /// See [`StepInTargets`].
+    StepInTargets(crate::StepInTargetsResponse),
+    /// See [`StepOut`].
+    StepOut,
+    /// See [`Terminate`].
+    Terminate,
+    /// See [`TerminateThreads`].
+    TerminateThreads,
+    /// See [`Threads`].
+    Threads(crate::ThreadsResponse),
+    /// See [`Variables`].
+    Variables(crate::VariablesResponse),
+    /// See [`WriteMemory`].
+    WriteMemory(crate::WriteMemoryResponse),
+    /// A failed response.
+    Error {
+        /// The command requested.
+        command: String,
+        /// Contains the raw error in short form.
+        message: Option<String>,
+        /// Contains the structured error, if any.
+        body: crate::ErrorResponse,
+    },
+    /// A successful response to a command unknown to this crate.
+    Other {
+        /// The command requested.
+        command: String,
+        /// Contains the request result.
+        body: serde_json::Value,
+    },
+}
+
+impl AnyResponse {
+    /// Decodes a response to a request with the given command.
+    ///
+    /// The command is usually looked up by `request_seq` from the table of pending
+    /// requests, rather than trusted from the response itself.
+    pub fn decode(command: &str, response: crate::Response) -> Result<AnyResponse, crate::Error> {
+        let body = response.body.unwrap_or_default();
+        if !response.success {
+            return Ok(AnyResponse::Error {
+                command: command.to_owned(),
+                message: response.message,
+                body: crate::body_from_value(&body)?,
+            });
+        }
+        Ok(match command {
+            Attach::COMMAND => AnyResponse::Attach,
+            BreakpointLocations::COMMAND => AnyResponse::BreakpointLocations(crate::body_from_value(&body)?),
+            Cancel::COMMAND => AnyResponse::Cancel,
+            Completions::COMMAND => AnyResponse::Completions(crate::body_from_value(&body)?),
+            ConfigurationDone::COMMAND => AnyResponse::ConfigurationDone,
+            Continue::COMMAND => AnyResponse::Continue(crate::body_from_value(&body)?),
+            DataBreakpointInfo::COMMAND => AnyResponse::DataBreakpointInfo(crate::body_from_value(&body)?),
+            Disassemble::COMMAND => AnyResponse::Disassemble(crate::body_from_value(&body)?),
+            Disconnect::COMMAND => AnyResponse::Disconnect,
+            Evaluate::COMMAND => AnyResponse::Evaluate(crate::body_from_value(&body)?),
+            ExceptionInfo::COMMAND => AnyResponse::ExceptionInfo(crate::body_from_value(&body)?),
+            Goto::COMMAND => AnyResponse::Goto,
+            GotoTargets::COMMAND => AnyResponse::GotoTargets(crate::body_from_value(&body)?),
+            Initialize::COMMAND => AnyResponse::Initialize(crate::body_from_value(&body)?),
+            Launch::COMMAND => AnyResponse::Launch,
+            LoadedSources::COMMAND => AnyResponse::LoadedSources(crate::body_from_value(&body)?),
+            Locations::COMMAND => AnyResponse::Locations(crate::body_from_value(&body)?),
+            Modules::COMMAND => AnyResponse::Modules(crate::body_from_value(&body)?),
+            Next::COMMAND => AnyResponse::Next,
+            Pause::COMMAND => AnyResponse::Pause,
+            ReadMemory::COMMAND => AnyResponse::ReadMemory(crate::body_from_value(&body)?),
+            RestartFrame::COMMAND => AnyResponse::RestartFrame,
+            Restart::COMMAND => AnyResponse::Restart,
+            ReverseContinue::COMMAND => AnyResponse::ReverseContinue,
+            RunInTerminal::COMMAND => AnyResponse::RunInTerminal(crate::body_from_value(&body)?),
+            Scopes::COMMAND => AnyResponse::Scopes(crate::body_from_value(&body)?),
+            SetBreakpoints::COMMAND => AnyResponse::SetBreakpoints(crate::body_from_value(&body)?),
+            SetDataBreakpoints::COMMAND => AnyResponse::SetDataBreakpoints(crate::body_from_value(&body)?),
+            SetExceptionBreakpoints::COMMAND => AnyResponse::SetExceptionBreakpoints(crate::body_from_value(&body)?),
+            SetExpression::COMMAND => AnyResponse::SetExpression(crate::body_from_value(&body)?),
+            SetFunctionBreakpoints::COMMAND => AnyResponse::SetFunctionBreakpoints(crate::body_from_value(&body)?),
+            SetInstructionBreakpoints::COMMAND => {
+                AnyResponse::SetInstructionBreakpoints(crate::body_from_value(&body)?)
+            }
+            SetVariable::COMMAND => AnyResponse::SetVariable(crate::body_from_value(&body)?),
+            Source::COMMAND => AnyResponse::Source(crate::body_from_value(&body)?),
+            StackTrace::COMMAND => AnyResponse::StackTrace(crate::body_from_value(&body)?),
+            StartDebugging::COMMAND => AnyResponse::StartDebugging,
+            StepBack::COMMAND => AnyResponse::StepBack,
+            StepIn::COMMAND => AnyResponse::StepIn,
+            StepInTargets::COMMAND => AnyResponse::StepInTargets(crate::body_from_value(&body)?),
+            StepOut::COMMAND => AnyResponse::StepOut,
+            Terminate::COMMAND => AnyResponse::Terminate,
+            TerminateThreads::COMMAND => AnyResponse::TerminateThreads,
+            Threads::COMMAND => AnyResponse::Threads(crate::body_from_value(&body)?),
+            Variables::COMMAND => AnyResponse::Variables(crate::body_from_value(&body)?),
+            WriteMemory::COMMAND => AnyResponse::WriteMemory(crate::body_from_value(&body)?),
+            _ => AnyResponse::Other {
+                command: command.to_owned(),
+                body,
+            },
+        })
+    }
+
+    /// Returns the command of the request answered.
+    pub fn command(&self) -> &str {
+        match self {
+            AnyResponse::Attach => Attach::COMMAND,
+            AnyResponse::BreakpointLocations(_) => BreakpointLocations::COMMAND,
+            AnyResponse::Cancel => Cancel::COMMAND,
+            AnyResponse::Completions(_) => Completions::COMMAND,
+            AnyResponse::ConfigurationDone => ConfigurationDone::COMMAND,
+            AnyResponse::Continue(_) => Continue::COMMAND,
+            AnyResponse::DataBreakpointInfo(_) => DataBreakpointInfo::COMMAND,
+            AnyResponse::Disassemble(_) => Disassemble::COMMAND,
+            AnyResponse::Disconnect => Disconnect::COMMAND,
+            AnyResponse::Evaluate(_) => Evaluate::COMMAND,
+            AnyResponse::ExceptionInfo(_) => ExceptionInfo::COMMAND,
+            AnyResponse::Goto => Goto::COMMAND,
+            AnyResponse::GotoTargets(_) => GotoTargets::COMMAND,
+            AnyResponse::Initialize(_) => Initialize::COMMAND,
+            AnyResponse::Launch => Launch::COMMAND,
+            AnyResponse::LoadedSources(_) => LoadedSources::COMMAND,
+            AnyResponse::Locations(_) => Locations::COMMAND,
+            AnyResponse::Modules(_) => Modules::COMMAND,
+            AnyResponse::Next => Next::COMMAND,
+            AnyResponse::Pause => Pause::COMMAND,
+            AnyResponse::ReadMemory(_) => ReadMemory::COMMAND,
+            AnyResponse::RestartFrame => RestartFrame::COMMAND,
+            AnyResponse::Restart => Restart::COMMAND,
+            AnyResponse::ReverseContinue => ReverseContinue::COMMAND,
+            AnyResponse::RunInTerminal(_) => RunInTerminal::COMMAND,
+            AnyResponse::Scopes(_) => Scopes::COMMAND,
+            AnyResponse::SetBreakpoints(_) => SetBreakpoints::COMMAND,
+            AnyResponse::SetDataBreakpoints(_) => SetDataBreakpoints::COMMAND,
+            AnyResponse::SetExceptionBreakpoints(_) => SetExceptionBreakpoints::COMMAND,
+            AnyResponse::SetExpression(_) => SetExpression::COMMAND,
+            AnyResponse::SetFunctionBreakpoints(_) => SetFunctionBreakpoints::COMMAND,
+            AnyResponse::SetInstructionBreakpoints(_) => SetInstructionBreakpoints::COMMAND,
+            AnyResponse::SetVariable(_) => SetVariable::COMMAND,
+            AnyResponse::Source(_) => Source::COMMAND,
+            AnyResponse::StackTrace(_) => StackTrace::COMMAND,
+            AnyResponse::StartDebugging => StartDebugging::COMMAND,
+            AnyResponse::StepBack => StepBack::COMMAND,
+            AnyResponse::StepIn => StepIn::COMMAND,
+            AnyResponse::StepInTargets(_) => StepInTargets::COMMAND,
+            AnyResponse::StepOut => StepOut::COMMAND,
+            AnyResponse::Terminate => Terminate::COMMAND,
+            AnyResponse::TerminateThreads => TerminateThreads::COMMAND,
+            AnyResponse::Threads(_) => Threads::COMMAND,
+            AnyResponse::Variables(_) => Variables::COMMAND,
+            AnyResponse::WriteMemory(_) => WriteMemory::COMMAND,
+            AnyResponse::Error { command, .. } | AnyResponse::Other { command, .. } => command,
+        }
+    }
+
+    /// Converts into a response with the given sequence numbers.
+    pub fn into_response(self, seq: i64, request_seq: i64) -> crate::Response {
+        match self {
+            AnyResponse::Attach => crate::Response::success::<Attach>(seq, request_seq, ()),
+            AnyResponse::BreakpointLocations(v) => crate::Response::success::<BreakpointLocations>(seq, request_seq, v),
+            AnyResponse::Cancel => crate::Response::success::<Cancel>(seq, request_seq, ()),
+            AnyResponse::Completions(v) => crate::Response::success::<Completions>(seq, request_seq, v),
+            AnyResponse::ConfigurationDone => crate::Response::success::<ConfigurationDone>(seq, request_seq, ()),
+            AnyResponse::Continue(v) => crate::Response::success::<Continue>(seq, request_seq, v),
+            AnyResponse::DataBreakpointInfo(v) => crate::Response::success::<DataBreakpointInfo>(seq, request_seq, v),
+            AnyResponse::Disassemble(v) => crate::Response::success::<Disassemble>(seq, request_seq, v),
+            AnyResponse::Disconnect => crate::Response::success::<Disconnect>(seq, request_seq, ()),
+            AnyResponse::Evaluate(v) => crate::Response::success::<Evaluate>(seq, request_seq, v),
+            AnyResponse::ExceptionInfo(v) => crate::Response::success::<ExceptionInfo>(seq, request_seq, v),
+            AnyResponse::Goto => crate::Response::success::<Goto>(seq, request_seq, ()),
+            AnyResponse::GotoTargets(v) => crate::Response::success::<GotoTargets>(seq, request_seq, v),
+            AnyResponse::Initialize(v) => crate::Response::success::<Initialize>(seq, request_seq, v),
+            AnyResponse::Launch => crate::Response::success::<Launch>(seq, request_seq, ()),
+            AnyResponse::LoadedSources(v) => crate::Response::success::<LoadedSources>(seq, request_seq, v),
+            AnyResponse::Locations(v) => crate::Response::success::<Locations>(seq, request_seq, v),
+            AnyResponse::Modules(v) => crate::Response::success::<Modules>(seq, request_seq, v),
+            AnyResponse::Next => crate::Response::success::<Next>(seq, request_seq, ()),
+            AnyResponse::Pause => crate::Response::success::<Pause>(seq, request_seq, ()),
+            AnyResponse::ReadMemory(v) => crate::Response::success::<ReadMemory>(seq, request_seq, v),
+            AnyResponse::RestartFrame => crate::Response::success::<RestartFrame>(seq, request_seq, ()),
+            AnyResponse::Restart => crate::Response::success::<Restart>(seq, request_seq, ()),
+            AnyResponse::ReverseContinue => crate::Response::success::<ReverseContinue>(seq, request_seq, ()),
+            AnyResponse::RunInTerminal(v) => crate::Response::success::<RunInTerminal>(seq, request_seq, v),
+            AnyResponse::Scopes(v) => crate::Response::success::<Scopes>(seq, request_seq, v),
+            AnyResponse::SetBreakpoints(v) => crate::Response::success::<SetBreakpoints>(seq, request_seq, v),
+            AnyResponse::SetDataBreakpoints(v) => crate::Response::success::<SetDataBreakpoints>(seq, request_seq, v),
+            AnyResponse::SetExceptionBreakpoints(v) => {
+                crate::Response::success::<SetExceptionBreakpoints>(seq, request_seq, v)
+            }
+            AnyResponse::SetExpression(v) => crate::Response::success::<SetExpression>(seq, request_seq, v),
+            AnyResponse::SetFunctionBreakpoints(v) => {
+                crate::Response::success::<SetFunctionBreakpoints>(seq, request_seq, v)
+            }
+            AnyResponse::SetInstructionBreakpoints(v) => {
+                crate::Response::success::<SetInstructionBreakpoints>(seq, request_seq, v)
+            }
+            AnyResponse::SetVariable(v) => crate::Response::success::<SetVariable>(seq, request_seq, v),
+            AnyResponse::Source(v) => crate::Response::success::<Source>(seq, request_seq, v),
+            AnyResponse::StackTrace(v) => crate::Response::success::<StackTrace>(seq, request_seq, v),
+            AnyResponse::StartDebugging => crate::Response::success::<StartDebugging>(seq, request_seq, ()),
+            AnyResponse::StepBack => crate::Response::success::<StepBack>(seq, request_seq, ()),
+            AnyResponse::StepIn => crate::Response::success::<StepIn>(seq, request_seq, ()),
+            AnyResponse::StepInTargets(v) => crate::Response::success::<StepInTargets>(seq, request_seq, v),
+            AnyResponse::StepOut => crate::Response::success::<StepOut>(seq, request_seq, ()),
+            AnyResponse::Terminate => crate::Response::success::<Terminate>(seq, request_seq, ()),
+            AnyResponse::TerminateThreads => crate::Response::success::<TerminateThreads>(seq, request_seq, ()),
+            AnyResponse::Threads(v) => crate::Response::success::<Threads>(seq, request_seq, v),
+            AnyResponse::Variables(v) => crate::Response::success::<Variables>(seq, request_seq, v),
+            AnyResponse::WriteMemory(v) => crate::Response::success::<WriteMemory>(seq, request_seq, v),
+            AnyResponse::Error { command, message, body } => {
+                let body = body.error.is_some().then_some(body);
+                crate::Response::new(seq, request_seq, command, false, message, body)
+            }
+            AnyResponse::Other { command, body } => {
+                let body = Some(body).filter(|b| !b.is_null());
+                crate::Response::new(seq, request_seq, command, true, None, body)
+            }
+        }
+    }
+}
+
+/// Returns the direction of requests with the given command, or `None` if the command is unknown to this crate.
+pub fn direction(command: &str) -> Option<crate::Direction> {
+    match command {
+        Attach::COMMAND => Some(Attach::DIRECTION),
+        BreakpointLocations::COMMAND => Some(BreakpointLocations::DIRECTION),
+        Cancel::COMMAND => Some(Cancel::DIRECTION),
+        Completions::COMMAND => Some(Completions::DIRECTION),
+        ConfigurationDone::COMMAND => Some(ConfigurationDone::DIRECTION),
+        Continue::COMMAND => Some(Continue::DIRECTION),
+        DataBreakpointInfo::COMMAND => Some(DataBreakpointInfo::DIRECTION),
+        Disassemble::COMMAND => Some(Disassemble::DIRECTION),
+        Disconnect::COMMAND => Some(Disconnect::DIRECTION),
+        Evaluate::COMMAND => Some(Evaluate::DIRECTION),
+        ExceptionInfo::COMMAND => Some(ExceptionInfo::DIRECTION),
+        Goto::COMMAND => Some(Goto::DIRECTION),
+        GotoTargets::COMMAND => Some(GotoTargets::DIRECTION),
+        Initialize::COMMAND => Some(Initialize::DIRECTION),
+        Launch::COMMAND => Some(Launch::DIRECTION),
+        LoadedSources::COMMAND => Some(LoadedSources::DIRECTION),
+        Locations::COMMAND => Some(Locations::DIRECTION),
+        Modules::COMMAND => Some(Modules::DIRECTION),
+        Next::COMMAND => Some(Next::DIRECTION),
+        Pause::COMMAND => Some(Pause::DIRECTION),
+        ReadMemory::COMMAND => Some(ReadMemory::DIRECTION),
+        RestartFrame::COMMAND => Some(RestartFrame::DIRECTION),
+        Restart::COMMAND => Some(Restart::DIRECTION),
+        ReverseContinue::COMMAND => Some(ReverseContinue::DIRECTION),
+        RunInTerminal::COMMAND => Some(RunInTerminal::DIRECTION),
+        Scopes::COMMAND => Some(Scopes::DIRECTION),
+        SetBreakpoints::COMMAND => Some(SetBreakpoints::DIRECTION),
+        SetDataBreakpoints::COMMAND => Some(SetDataBreakpoints::DIRECTION),
+        SetExceptionBreakpoints::COMMAND => Some(SetExceptionBreakpoints::DIRECTION),
+        SetExpression::COMMAND => Some(SetExpression::DIRECTION),
+        SetFunctionBreakpoints::COMMAND => Some(SetFunctionBreakpoints::DIRECTION),
+        SetInstructionBreakpoints::COMMAND => Some(SetInstructionBreakpoints::DIRECTION),
+        SetVariable::COMMAND => Some(SetVariable::DIRECTION),
+        Source::COMMAND => Some(Source::DIRECTION),
+        StackTrace::COMMAND => Some(StackTrace::DIRECTION),
+        StartDebugging::COMMAND => Some(StartDebugging::DIRECTION),
+        StepBack::COMMAND => Some(StepBack::DIRECTION),
+        StepIn::COMMAND => Some(StepIn::DIRECTION),
+        StepInTargets::COMMAND => Some(StepInTargets::DIRECTION),
+        StepOut::COMMAND => Some(StepOut::DIRECTION),
+        Terminate::COMMAND => Some(Terminate::DIRECTION),
+        TerminateThreads::COMMAND => Some(TerminateThreads::DIRECTION),
+        Threads::COMMAND => Some(Threads::DIRECTION),
+        Variables::COMMAND => Some(Variables::DIRECTION),
+        WriteMemory::COMMAND => Some(WriteMemory::DIRECTION),
+        _ => None,
+    }
+}
@@ -0,0 +1,484 @@
+//! A server, which runs a [`DebugAdapter`] over a connection to a client.
+//!
+//! [`Server::run`] reads requests, dispatches each of them to the method of
+//! the adapter handling it, and answers with the response it returns. Events
+//! are sent by an [`EventSender`], which the adapter usually keeps:
+//!
+//! ```no_run
+//! use dapts::server::{DebugAdapter, EventSender, RequestError, Server};
+//! use dapts::{Capabilities, InitializeRequestArguments, Thread, ThreadId, ThreadsResponse};
+//!
+//! struct Adapter {
+//!     events: EventSender,
+//! }
+//!
+//! impl DebugAdapter for Adapter {
+//!     async fn initialize(&mut self, _args: InitializeRequestArguments) -> Result<Capabilities, RequestError> {
+//!         Ok(Capabilities::default())
+//!     }
+//!
+//!     async fn threads(&mut self) -> Result<ThreadsResponse, RequestError> {
+//!         let main = Thread {
+//!             id: ThreadId(1),
+//!             name: "main".to_owned(),
+//!         };
+//!         Ok(ThreadsResponse { threads: vec![main] })
+//!     }
+//! }
+//!
+//! # async fn example(reader: tokio::io::DuplexStream, writer: tokio::io::DuplexStream) -> Result<(), dapts::Error> {
+//! let (server, events) = Server::new(reader, writer);
+//! server.run(Adapter { events }).await
+//! # }
+//! ```
+
+mod adapter;
+
+use std::sync::Arc;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+
+pub use self::adapter::DebugAdapter;
+use self::adapter::Dispatcher;
+use crate::connection::Connection;
+pub use crate::RequestError;
+use crate::{AdapterToClient, Error, Event, IEvent, ProtocolMessage};
+
+/// The number of requests queued while one is handled, after which more
+/// requests are answered with a failure.
+///
+/// The same bound applies to the reverse requests queued by a client.
+pub const MAX_QUEUED_REQUESTS: usize = 64;
+
+/// Runs a debug adapter over a connection to a client.
+pub struct Server<R> {
+    reader: R,
+    connection: Arc<Connection>,
+}
+
+impl<R: AsyncRead + Unpin> Server<R> {
+    /// Creates a server reading requests from `reader` and writing responses
+    /// and events to `writer`.
+    ///
+    /// Returns the server, and a sender of events to the client.
+    pub fn new<W>(reader: R, writer: W) -> (Server<R>, EventSender)
+    where
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let connection = Arc::new(Connection::new(writer));
+        let events = EventSender {
+            connection: connection.clone(),
+        };
+        (Server { reader, connection }, events)
+    }
+
+    /// Returns a sender of reverse requests to the client.
+    pub fn reverse_requests(&self) -> ReverseRequestSender {
+        ReverseRequestSender {
+            connection: self.connection.clone(),
+        }
+    }
+
+    /// Sets the function called with the errors of the malformed messages
+    /// which can't be answered, e.g. without a valid `seq`.
+    pub fn on_malformed(&self, on_malformed: impl FnMut(Error) + Send + 'static) {
+        self.connection.set_on_malformed(on_malformed);
+    }
+
+    /// Runs `adapter` until `reader` is closed or fails.
+    ///
+    /// Requests are handled one at a time, in the order they arrive. Events
+    /// sent while handling a request are written before its response.
+    /// Responses to reverse requests are read while a request is handled, so
+    /// its handler can wait for them. Requests sent in the meantime are
+    /// queued, up to [`MAX_QUEUED_REQUESTS`], and answered with a failure past
+    /// that.
+    ///
+    /// Requests can't be cancelled while they are handled: a `cancel` request
+    /// waits for the requests before it like any other, so
+    /// [`DebugAdapter::cancel`] can only cancel progress outliving its
+    /// request.
+    ///
+    /// Malformed requests with a `seq` are answered with a failure, and
+    /// malformed responses to reverse requests fail them. Other malformed
+    /// messages are passed to [`Server::on_malformed`].
+    pub async fn run<A: DebugAdapter>(self, adapter: A) -> Result<(), Error> {
+        let mut dispatcher = Dispatcher(adapter);
+        // Clients don't send events.
+        let on_event = |_| {};
+        self.connection.serve(self.reader, &mut dispatcher, on_event).await
+    }
+}
+
+/// Sends events to the client.
+///
+/// Clones share the same connection.
+#[derive(Clone)]
+pub struct EventSender {
+    connection: Arc<Connection>,
+}
+
+impl EventSender {
+    /// Sends an event of type `E`.
+    pub async fn send<E: IEvent>(&self, body: E::Body) -> Result<(), Error> {
+        let body = serde_json::to_value(body).map_err(Error::Serialize)?;
+        self.send_event(E::EVENT.to_owned(), body).await
+    }
+
+    /// Sends an event.
+    pub async fn send_event(&self, event: String, body: serde_json::Value) -> Result<(), Error> {
+        let event = |seq| ProtocolMessage::Event(Event { seq, event, body });
+        self.connection.send(event).await
+    }
+}
+
+/// Sends reverse requests to the client.
+///
+/// Clones share the same connection.
+#[derive(Clone)]
+pub struct ReverseRequestSender {
+    connection: Arc<Connection>,
+}
+
+impl ReverseRequestSender {
+    /// Sends a reverse request of type `R` and waits for its response.
+    ///
+    /// A failed response is returned as [`Error::Response`].
+    pub async fn send<R: AdapterToClient>(&self, arguments: R::Arguments) -> Result<R::Response, Error> {
+        self.connection.typed_request::<R>(arguments).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::io::DuplexStream;
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::client::{Client, ReverseRequestHandler};
+    use crate::codec::tokio::framed;
+    use crate::{event, request, Message, Request, Response};
+    use crate::{Capabilities, EvaluateArguments, EvaluateResponse, InitializeRequestArguments, ThreadsResponse};
+    use crate::{LaunchRequestArguments, RunInTerminalRequestArguments, RunInTerminalResponse};
+
+    struct TestAdapter {
+        events: EventSender,
+        reverse: ReverseRequestSender,
+    }
+
+    impl DebugAdapter for TestAdapter {
+        async fn initialize(&mut self, _args: InitializeRequestArguments) -> Result<Capabilities, RequestError> {
+            Ok(Capabilities {
+                supports_configuration_done_request: Some(true),
+                ..Default::default()
+            })
+        }
+
+        async fn threads(&mut self) -> Result<ThreadsResponse, RequestError> {
+            let body = crate::OutputEvent {
+                output: "listing threads\n".to_owned(),
+                ..Default::default()
+            };
+            self.events.send::<event::Output>(body).await?;
+            Ok(ThreadsResponse::default())
+        }
+
+        async fn launch(&mut self, _args: LaunchRequestArguments) -> Result<(), RequestError> {
+            let args = RunInTerminalRequestArguments {
+                args: vec!["ls".to_owned()],
+                args_can_be_interpreted_by_shell: None,
+                cwd: "/".to_owned(),
+                env: None,
+                kind: None,
+                title: None,
+            };
+            let response = self.reverse.send::<request::RunInTerminal>(args).await?;
+            assert_eq!(response.process_id, Some(42));
+            Ok(())
+        }
+
+        async fn evaluate(&mut self, args: EvaluateArguments) -> Result<EvaluateResponse, RequestError> {
+            let detail = Message::new(1, "cannot evaluate {expr}").with_variable("expr", args.expression);
+            Err(RequestError::new("notStopped").with_detail(detail))
+        }
+    }
+
+    struct Terminal;
+
+    impl ReverseRequestHandler for Terminal {
+        async fn run_in_terminal(
+            &mut self,
+            _args: RunInTerminalRequestArguments,
+        ) -> Result<RunInTerminalResponse, RequestError> {
+            Ok(RunInTerminalResponse {
+                process_id: Some(42),
+                shell_process_id: None,
+            })
+        }
+    }
+
+    fn serve(server: DuplexStream) {
+        let (reader, writer) = tokio::io::split(server);
+        let (server, events) = Server::new(reader, writer);
+        let reverse = server.reverse_requests();
+        tokio::spawn(server.run(TestAdapter { events, reverse }));
+    }
+
+    fn connect() -> (Client, mpsc::UnboundedReceiver<Event>) {
+        let (client, server) = tokio::io::duplex(4096);
+        let (reader, writer) = tokio::io::split(client);
+        let (client, events, connection) = Client::with_handler(reader, writer, Terminal);
+        tokio::spawn(connection);
+        serve(server);
+        (client, events)
+    }
+
+    #[tokio::test]
+    async fn test_dispatch() {
+        let (client, _events) = connect();
+        let args = InitializeRequestArguments {
+            adapter_id: "test".to_owned(),
+            ..Default::default()
+        };
+        let capabilities = client.send::<request::Initialize>(args).await.unwrap();
+        assert_eq!(capabilities.supports_configuration_done_request, Some(true));
+
+        let response = client
+            .send_request("initialize".to_owned(), serde_json::json!({ "adapterID": 1 }))
+            .await
+            .unwrap();
+        assert!(!response.success);
+        assert_eq!(response.command, "initialize");
+        assert!(response.message.unwrap().starts_with("bad arguments"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_request() {
+        let (client, _events) = connect();
+        let args = EvaluateArguments {
+            column: None,
+            context: None,
+            expression: "x".to_owned(),
+            format: None,
+            frame_id: None,
+            line: None,
+            source: None,
+        };
+        let Err(Error::Response(err)) = client.send::<request::Evaluate>(args).await else {
+            panic!("expected a failed response");
+        };
+        assert_eq!(err.command, "evaluate");
+        assert_eq!(err.message.as_deref(), Some("notStopped"));
+        assert_eq!(err.detail.unwrap().render(), "cannot evaluate x");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_request() {
+        let (client, _events) = connect();
+        for command in ["next", "fooBar"] {
+            let response = client
+                .send_request(command.to_owned(), serde_json::json!({ "threadId": 1 }))
+                .await
+                .unwrap();
+            assert!(!response.success);
+            assert_eq!(response.command, command);
+            assert_eq!(response.message.as_deref(), Some("unsupported"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_before_response() {
+        let (client, mut events) = connect();
+        client.send::<request::Threads>(()).await.unwrap();
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.event, "output");
+        assert_eq!(event.body["output"], "listing threads\n");
+    }
+
+    #[tokio::test]
+    async fn test_reverse_request() {
+        let (client, _events) = connect();
+        let args = LaunchRequestArguments {
+            raw: serde_json::json!({}),
+        };
+        client.send::<request::Launch>(args).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reverse_request_refused() {
+        let (client, server) = tokio::io::duplex(4096);
+        let (reader, writer) = tokio::io::split(client);
+        let (client, _events, connection) = Client::new(reader, writer);
+        tokio::spawn(connection);
+        serve(server);
+
+        let args = LaunchRequestArguments {
+            raw: serde_json::json!({}),
+        };
+        let Err(Error::Response(err)) = client.send::<request::Launch>(args).await else {
+            panic!("expected a failed response");
+        };
+        assert_eq!(err.message.as_deref(), Some("runInTerminal failed: unsupported"));
+    }
+
+    #[tokio::test]
+    async fn test_requests_queued_during_reverse_request() {
+        let (client, server) = tokio::io::duplex(4096);
+        serve(server);
+        let mut client = framed(client);
+        let recv = |msg: Option<Result<ProtocolMessage, Error>>| msg.unwrap().unwrap();
+
+        let launch = Request::new(1, "launch".to_owned(), serde_json::json!({}));
+        client.send(ProtocolMessage::Request(launch)).await.unwrap();
+        let ProtocolMessage::Request(reverse) = recv(client.next().await) else {
+            panic!("expected a reverse request");
+        };
+        assert_eq!((reverse.seq, reverse.command.as_str()), (1, "runInTerminal"));
+
+        // Sent before the reverse request is answered, handled after launch.
+        let threads = Request::new(2, "threads".to_owned(), serde_json::json!(null));
+        client.send(ProtocolMessage::Request(threads)).await.unwrap();
+        let body = RunInTerminalResponse {
+            process_id: Some(42),
+            shell_process_id: None,
+        };
+        let response = Response::success::<request::RunInTerminal>(3, reverse.seq, body);
+        client.send(ProtocolMessage::Response(response)).await.unwrap();
+
+        let mut seqs = Vec::new();
+        for _ in 0..3 {
+            seqs.push(match recv(client.next().await) {
+                ProtocolMessage::Response(response) => {
+                    assert!(response.success);
+                    (response.seq, response.request_seq, response.command)
+                }
+                ProtocolMessage::Event(event) => (event.seq, 0, event.event),
+                ProtocolMessage::Request(_) => panic!("unexpected request"),
+            });
+        }
+        let expected = [(2, 1, "launch"), (3, 0, "output"), (4, 2, "threads")];
+        assert_eq!(
+            seqs,
+            expected.map(|(seq, request_seq, name)| (seq, request_seq, name.to_owned()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_malformed_messages() {
+        use tokio::io::AsyncWriteExt;
+
+        let (client, server) = tokio::io::duplex(4096);
+        let (reader, writer) = tokio::io::split(server);
+        let (server, events) = Server::new(reader, writer);
+        let (tx, mut malformed) = mpsc::unbounded_channel();
+        server.on_malformed(move |err| {
+            let _ = tx.send(err);
+        });
+        let reverse = server.reverse_requests();
+        tokio::spawn(server.run(TestAdapter { events, reverse }));
+        let mut client = framed(client);
+
+        let mut input = Vec::new();
+        for body in [&b"{}"[..], b"\xff", br#"{"seq":1,"type":"request"}"#] {
+            input.extend_from_slice(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes());
+            input.extend_from_slice(body);
+        }
+        let threads = Request::new(2, "threads".to_owned(), serde_json::json!(null));
+        crate::codec::encode_message(&ProtocolMessage::Request(threads), &mut input).unwrap();
+        client.get_mut().write_all(&input).await.unwrap();
+
+        let mut seen = Vec::new();
+        while seen.len() < 3 {
+            match client.next().await.unwrap().unwrap() {
+                ProtocolMessage::Response(response) => {
+                    seen.push((response.request_seq, response.command, response.success));
+                }
+                ProtocolMessage::Event(event) => seen.push((0, event.event, true)),
+                ProtocolMessage::Request(_) => panic!("unexpected request"),
+            }
+        }
+        let expected = [(1, "", false), (0, "output", true), (2, "threads", true)];
+        assert_eq!(
+            seen,
+            expected.map(|(request_seq, name, success)| (request_seq, name.to_owned(), success))
+        );
+        assert!(matches!(malformed.recv().await, Some(Error::InvalidMessage(_))));
+        assert!(matches!(malformed.recv().await, Some(Error::InvalidUtf8(_))));
+        assert!(malformed.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_during_reverse_request() {
+        let (client, server) = tokio::io::duplex(4096);
+        serve(server);
+        let (mut sink, mut stream) = framed(client).split();
+
+        let launch = Request::new(1, "launch".to_owned(), serde_json::json!({}));
+        sink.send(ProtocolMessage::Request(launch)).await.unwrap();
+        let Some(Ok(ProtocolMessage::Request(reverse))) = stream.next().await else {
+            panic!("expected a reverse request");
+        };
+
+        // Queued or refused while launch waits for the reverse request.
+        let count = MAX_QUEUED_REQUESTS as i64 + 6;
+        let send = async {
+            for seq in 2..count + 2 {
+                let threads = Request::new(seq, "threads".to_owned(), serde_json::json!(null));
+                sink.send(ProtocolMessage::Request(threads)).await.unwrap();
+            }
+            let body = RunInTerminalResponse {
+                process_id: Some(42),
+                shell_process_id: None,
+            };
+            let response = Response::success::<request::RunInTerminal>(count + 2, reverse.seq, body);
+            sink.send(ProtocolMessage::Response(response)).await.unwrap();
+        };
+        let receive = async {
+            let mut responses = Vec::new();
+            while responses.len() < count as usize + 1 {
+                if let ProtocolMessage::Response(response) = stream.next().await.unwrap().unwrap() {
+                    responses.push(response);
+                }
+            }
+            responses
+        };
+        let ((), responses) = tokio::join!(send, receive);
+        let refused: Vec<_> = responses.iter().filter(|response| !response.success).collect();
+        assert_eq!(refused.len(), 6);
+        assert!(refused
+            .iter()
+            .all(|response| response.message.as_deref() == Some("too many requests")));
+        assert!(responses
+            .iter()
+            .any(|response| response.command == "launch" && response.success));
+    }
+
+    #[tokio::test]
+    async fn test_many_queued_requests() {
+        let (client, server) = tokio::io::duplex(4096);
+        serve(server);
+        let (mut sink, mut stream) = framed(client).split();
+
+        let count = 200;
+        let send = async {
+            for seq in 1..=count {
+                let threads = Request::new(seq, "threads".to_owned(), serde_json::json!(null));
+                sink.send(ProtocolMessage::Request(threads)).await.unwrap();
+            }
+        };
+        let receive = async {
+            let mut answered = Vec::new();
+            while answered.len() < count as usize {
+                if let ProtocolMessage::Response(response) = stream.next().await.unwrap().unwrap() {
+                    answered.push(response.request_seq);
+                }
+            }
+            answered
+        };
+        let ((), mut answered) = tokio::join!(send, receive);
+        // Handled in order, or refused as soon as read: each once either way.
+        answered.sort();
+        assert!(answered.into_iter().eq(1..=count));
+    }
+}
@@ -0,0 +1,543 @@
+// This file is autogenerated. Do not edit by hand.
+// To regenerate from schema, run `cargo run -p generator`.
+
+use std::future::Future;
+
+use crate::connection::{arguments, respond, Dispatch};
+use crate::request::*;
+use crate::RequestError;
+
+/// A debug adapter, handling the requests of a client.
+///
+/// Each request is handled by a method returning the response body, or a [`RequestError`] to answer with an error response. Requests whose method is not implemented fail with [`RequestError::unsupported`].
+pub trait DebugAdapter: Send {
+    /// Handles the [`Attach`] request.
+    fn attach(&mut self, args: crate::AttachRequestArguments) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`BreakpointLocations`] request.
+    fn breakpoint_locations(
+        &mut self,
+        args: crate::BreakpointLocationsArguments,
+    ) -> impl Future<Output = Result<crate::BreakpointLocationsResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Cancel`] request.
+    fn cancel(&mut self, args: crate::CancelArguments) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Completions`] request.
+    fn completions(
+        &mut self,
+        args: crate::CompletionsArguments,
+    ) -> impl Future<Output = Result<crate::CompletionsResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`ConfigurationDone`] request.
+    fn configuration_done(
+        &mut self,
+        args: crate::ConfigurationDoneArguments,
+    ) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Continue`] request.
+    fn r#continue(
+        &mut self,
+        args: crate::ContinueArguments,
+    ) -> impl Future<Output = Result<crate::ContinueResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`DataBreakpointInfo`] request.
+    fn data_breakpoint_info(
+        &mut self,
+        args: crate::DataBreakpointInfoArguments,
+    ) -> impl Future<Output = Result<crate::DataBreakpointInfoResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Disassemble`] request.
+    fn disassemble(
+        &mut self,
+        args: crate::DisassembleArguments,
+    ) -> impl Future<Output = Result<crate::DisassembleResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Disconnect`] request.
+    fn disconnect(
+        &mut self,
+        args: crate::DisconnectArguments,
+    ) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Evaluate`] request.
+    fn evaluate(
+        &mut self,
+        args: crate::EvaluateArguments,
+    ) -> impl Future<Output = Result<crate::EvaluateResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`ExceptionInfo`] request.
+    fn exception_info(
+        &mut self,
+        args: crate::ExceptionInfoArguments,
+    ) -> impl Future<Output = Result<crate::ExceptionInfoResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Goto`] request.
+    fn goto(&mut self, args: crate::GotoArguments) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`GotoTargets`] request.
+    fn goto_targets(
+        &mut self,
+        args: crate::GotoTargetsArguments,
+    ) -> impl Future<Output = Result<crate::GotoTargetsResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Initialize`] request.
+    fn initialize(
+        &mut self,
+        args: crate::InitializeRequestArguments,
+    ) -> impl Future<Output = Result<crate::Capabilities, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Launch`] request.
+    fn launch(&mut self, args: crate::LaunchRequestArguments) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`LoadedSources`] request.
+    fn loaded_sources(
+        &mut self,
+        args: crate::LoadedSourcesArguments,
+    ) -> impl Future<Output = Result<crate::LoadedSourcesResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Locations`] request.
+    fn locations(
+        &mut self,
+        args: crate::LocationsArguments,
+    ) -> impl Future<Output = Result<crate::LocationsResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Modules`] request.
+    fn modules(
+        &mut self,
+        args: crate::ModulesArguments,
+    ) -> impl Future<Output = Result<crate::ModulesResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Next`] request.
+    fn next(&mut self, args: crate::NextArguments) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Pause`] request.
+    fn pause(&mut self, args: crate::PauseArguments) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`ReadMemory`] request.
+    fn read_memory(
+        &mut self,
+        args: crate::ReadMemoryArguments,
+    ) -> impl Future<Output = Result<crate::ReadMemoryResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`RestartFrame`] request.
+    fn restart_frame(
+        &mut self,
+        args: crate::RestartFrameArguments,
+    ) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Restart`] request.
+    fn restart(&mut self, args: crate::RestartArguments) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`ReverseContinue`] request.
+    fn reverse_continue(
+        &mut self,
+        args: crate::ReverseContinueArguments,
+    ) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Scopes`] request.
+    fn scopes(
+        &mut self,
+        args: crate::ScopesArguments,
+    ) -> impl Future<Output = Result<crate::ScopesResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`SetBreakpoints`] request.
+    fn set_breakpoints(
+        &mut self,
+        args: crate::SetBreakpointsArguments,
+    ) -> impl Future<Output = Result<crate::SetBreakpointsResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`SetDataBreakpoints`] request.
+    fn set_data_breakpoints(
+        &mut self,
+        args: crate::SetDataBreakpointsArguments,
+    ) -> impl Future<Output = Result<crate::SetDataBreakpointsResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`SetExceptionBreakpoints`] request.
+    fn set_exception_breakpoints(
+        &mut self,
+        args: crate::SetExceptionBreakpointsArguments,
+    ) -> impl Future<Output = Result<crate::SetExceptionBreakpointsResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`SetExpression`] request.
+    fn set_expression(
+        &mut self,
+        args: crate::SetExpressionArguments,
+    ) -> impl Future<Output = Result<crate::SetExpressionResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`SetFunctionBreakpoints`] request.
+    fn set_function_breakpoints(
+        &mut self,
+        args: crate::SetFunctionBreakpointsArguments,
+    ) -> impl Future<Output = Result<crate::SetFunctionBreakpointsResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`SetInstructionBreakpoints`] request.
+    fn set_instruction_breakpoints(
+        &mut self,
+        args: crate::SetInstructionBreakpointsArguments,
+    ) -> impl Future<Output = Result<crate::SetInstructionBreakpointsResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`SetVariable`] request.
+    fn set_variable(
+        &mut self,
+        args: crate::SetVariableArguments,
+    ) -> impl Future<Output = Result<crate::SetVariableResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Source`] request.
+    fn source(
+        &mut self,
+        args: crate::SourceArguments,
+    ) -> impl Future<Output = Result<crate::SourceResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`StackTrace`] request.
+    fn stack_trace(
+        &mut self,
+        args: crate::StackTraceArguments,
+    ) -> impl Future<Output = Result<crate::StackTraceResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`StepBack`] request.
+    fn step_back(&mut self, args: crate::StepBackArguments) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`StepIn`] request.
+    fn step_in(&mut self, args: crate::StepInArguments) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`StepInTargets`] request.
+    fn step_in_targets(
+        &mut self,
+        args: crate::StepInTargetsArguments,
+    ) -> impl Future<Output = Result<crate::StepInTargetsResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`StepOut`] request.
+    fn step_out(&mut self, args: crate::StepOutArguments) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Terminate`] request.
+    fn terminate(&mut self, args: crate::TerminateArguments) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`TerminateThreads`] request.
+    fn terminate_threads(
+        &mut self,
+        args: crate::TerminateThreadsArguments,
+    ) -> impl Future<Output = Result<(), RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Threads`] request.
+    fn threads(&mut self) -> impl Future<Output = Result<crate::ThreadsResponse, RequestError>> + Send {
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`Variables`] request.
+    fn variables(
+        &mut self,
+        args: crate::VariablesArguments,
+    ) -> impl Future<Output = Result<crate::VariablesResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+
+    /// Handles the [`WriteMemory`] request.
+    fn write_memory(
+        &mut self,
+        args: crate::WriteMemoryArguments,
+    ) -> impl Future<Output = Result<crate::WriteMemoryResponse, RequestError>> + Send {
+        let _ = args;
+        async { Err(RequestError::unsupported()) }
+    }
+}
+
+/// Dispatches requests to the methods of a [`DebugAdapter`].
+pub(crate) struct Dispatcher<H>(pub(crate) H);
+
+impl<H: DebugAdapter> Dispatch for Dispatcher<H> {
+    async fn dispatch(&mut self, request: &crate::Request) -> Result<serde_json::Value, RequestError> {
+        match request.command.as_str() {
+            Attach::COMMAND => {
+                let args = arguments::<Attach>(request)?;
+                respond::<Attach>(self.0.attach(args).await)
+            }
+            BreakpointLocations::COMMAND => {
+                let args = arguments::<BreakpointLocations>(request)?;
+                respond::<BreakpointLocations>(self.0.breakpoint_locations(args).await)
+            }
+            Cancel::COMMAND => {
+                let args = arguments::<Cancel>(request)?;
+                respond::<Cancel>(self.0.cancel(args).await)
+            }
+            Completions::COMMAND => {
+                let args = arguments::<Completions>(request)?;
+                respond::<Completions>(self.0.completions(args).await)
+            }
+            ConfigurationDone::COMMAND => {
+                let args = arguments::<ConfigurationDone>(request)?;
+                respond::<ConfigurationDone>(self.0.configuration_done(args).await)
+            }
+            Continue::COMMAND => {
+                let args = arguments::<Continue>(request)?;
+                respond::<Continue>(self.0.r#continue(args).await)
+            }
+            DataBreakpointInfo::COMMAND => {
+                let args = arguments::<DataBreakpointInfo>(request)?;
+                respond::<DataBreakpointInfo>(self.0.data_breakpoint_info(args).await)
+            }
+            Disassemble::COMMAND => {
+                let args = arguments::<Disassemble>(request)?;
+                respond::<Disassemble>(self.0.disassemble(args).await)
+            }
+            Disconnect::COMMAND => {
+                let args = arguments::<Disconnect>(request)?;
+                respond::<Disconnect>(self.0.disconnect(args).await)
+            }
+            Evaluate::COMMAND => {
+                let args = arguments::<Evaluate>(request)?;
+                respond::<Evaluate>(self.0.evaluate(args).await)
+            }
+            ExceptionInfo::COMMAND => {
+                let args = arguments::<ExceptionInfo>(request)?;
+                respond::<ExceptionInfo>(self.0.exception_info(args).await)
+            }
+            Goto::COMMAND => {
+                let args = arguments::<Goto>(request)?;
+                respond::<Goto>(self.0.goto(args).await)
+            }
+            GotoTargets::COMMAND => {
+                let args = arguments::<GotoTargets>(request)?;
+                respond::<GotoTargets>(self.0.goto_targets(args).await)
+            }
+            Initialize::COMMAND => {
+                let args = arguments::<Initialize>(request)?;
+                respond::<Initialize>(self.0.initialize(args).await)
+            }
+            Launch::COMMAND => {
+                let args = arguments::<Launch>(request)?;
+                respond::<Launch>(self.0.launch(args).await)
+            }
+            LoadedSources::COMMAND => {
+                let args = arguments::<LoadedSources>(request)?;
+                respond::<LoadedSources>(self.0.loaded_sources(args).await)
+            }
+            Locations::COMMAND => {
+                let args = arguments::<Locations>(request)?;
+                respond::<Locations>(self.0.locations(args).await)
+            }
+            Modules::COMMAND => {
+                let args = arguments::<Modules>(request)?;
+                respond::<Modules>(self.0.modules(args).await)
+            }
+            Next::COMMAND => {
+                let args = arguments::<Next>(request)?;
+                respond::<Next>(self.0.next(args).await)
+            }
+            Pause::COMMAND => {
+                let args = arguments::<Pause>(request)?;
+                respond::<Pause>(self.0.pause(args).await)
+            }
+            ReadMemory::COMMAND => {
+                let args = arguments::<ReadMemory>(request)?;
+                respond::<ReadMemory>(self.0.read_memory(args).await)
+            }
+            RestartFrame::COMMAND => {
+                let args = arguments::<RestartFrame>(request)?;
+                respond::<RestartFrame>(self.0.restart_frame(args).await)
+            }
+            Restart::COMMAND => {
+                let args = arguments::<Restart>(request)?;
+                respond::<Restart>(self.0.restart(args).await)
+            }
+            ReverseContinue::COMMAND => {
+                let args = arguments::<ReverseContinue>(request)?;
+                respond::<ReverseContinue>(self.0.reverse_continue(args).await)
+            }
+            Scopes::COMMAND => {
+                let args = arguments::<Scopes>(request)?;
+                respond::<Scopes>(self.0.scopes(args).await)
+            }
+            SetBreakpoints::COMMAND => {
+                let args = arguments::<SetBreakpoints>(request)?;
+                respond::<SetBreakpoints>(self.0.set_breakpoints(args).await)
+            }
+            SetDataBreakpoints::COMMAND => {
+                let args = arguments::<SetDataBreakpoints>(request)?;
+                respond::<SetDataBreakpoints>(self.0.set_data_breakpoints(args).await)
+            }
+            SetExceptionBreakpoints::COMMAND => {
+                let args = arguments::<SetExceptionBreakpoints>(request)?;
+                respond::<SetExceptionBreakpoints>(self.0.set_exception_breakpoints(args).await)
+            }
+            SetExpression::COMMAND => {
+                let args = arguments::<SetExpression>(request)?;
+                respond::<SetExpression>(self.0.set_expression(args).await)
+            }
+            SetFunctionBreakpoints::COMMAND => {
+                let args = arguments::<SetFunctionBreakpoints>(request)?;
+                respond::<SetFunctionBreakpoints>(self.0.set_function_breakpoints(args).await)
+            }
+            SetInstructionBreakpoints::COMMAND => {
+                let args = arguments::<SetInstructionBreakpoints>(request)?;
+                respond::<SetInstructionBreakpoints>(self.0.set_instruction_breakpoints(args).await)
+            }
+            SetVariable::COMMAND => {
+                let args = arguments::<SetVariable>(request)?;
+                respond::<SetVariable>(self.0.set_variable(args).await)
+            }
+            Source::COMMAND => {
+                let args = arguments::<Source>(request)?;
+                respond::<Source>(self.0.source(args).await)
+            }
+            StackTrace::COMMAND => {
+                let args = arguments::<StackTrace>(request)?;
+                respond::<StackTrace>(self.0.stack_trace(args).await)
+            }
+            StepBack::COMMAND => {
+                let args = arguments::<StepBack>(request)?;
+                respond::<StepBack>(self.0.step_back(args).await)
+            }
+            StepIn::COMMAND => {
+                let args = arguments::<StepIn>(request)?;
+                respond::<StepIn>(self.0.step_in(args).await)
+            }
+            StepInTargets::COMMAND => {
+                let args = arguments::<StepInTargets>(request)?;
+                respond::<StepInTargets>(self.0.step_in_targets(args).await)
+            }
+            StepOut::COMMAND => {
+                let args = arguments::<StepOut>(request)?;
+                respond::<StepOut>(self.0.step_out(args).await)
+            }
+            Terminate::COMMAND => {
+                let args = arguments::<Terminate>(request)?;
+                respond::<Terminate>(self.0.terminate(args).await)
+            }
+            TerminateThreads::COMMAND => {
+                let args = arguments::<TerminateThreads>(request)?;
+                respond::<TerminateThreads>(self.0.terminate_threads(args).await)
+            }
+            Threads::COMMAND => respond::<Threads>(self.0.threads().await),
+            Variables::COMMAND => {
+                let args = arguments::<Variables>(request)?;
+                respond::<Variables>(self.0.variables(args).await)
+            }
+            WriteMemory::COMMAND => {
+                let args = arguments::<WriteMemory>(request)?;
+                respond::<WriteMemory>(self.0.write_memory(args).await)
+            }
+            _ => Err(RequestError::unsupported()),
+        }
+    }
+}
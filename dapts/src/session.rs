@@ -0,0 +1,603 @@
+//! The lifecycle of a debug session, as seen from either side of a
+//! connection.
+//!
+//! A [`Session`] observes the messages exchanged by the client and the debug
+//! adapter, follows the [initialization sequence] of the protocol, and reports
+//! the messages sent out of order as [`Violation`]s:
+//!
+//! 1. the client sends `initialize`, and waits for its response before sending
+//!    any other request;
+//! 2. the adapter sends the `initialized` event, after which the client sends
+//!    its configuration, e.g. `setBreakpoints`, ending with
+//!    `configurationDone`;
+//! 3. the client sends `launch` or `attach`, before any request about the
+//!    debuggee, e.g. `stackTrace`;
+//! 4. the client sends `disconnect`, after which no more requests are sent,
+//!    and no message at all once it is answered.
+//!
+//! [initialization sequence]: https://microsoft.github.io/debug-adapter-protocol/overview#initialization
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{event, request, Capabilities, Event, IEvent, IRequest, ProtocolMessage, Request, Response};
+
+/// A side of a connection, which sends a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    /// The development tool.
+    Client,
+    /// The debug adapter.
+    Adapter,
+}
+
+impl Side {
+    /// The other side of the connection.
+    pub fn peer(self) -> Side {
+        match self {
+            Side::Client => Side::Adapter,
+            Side::Adapter => Side::Client,
+        }
+    }
+}
+
+/// The state of a debug session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum State {
+    /// No `initialize` request was sent yet, or it failed.
+    Uninitialized,
+    /// The `initialize` request was sent, and waits for its response.
+    Initializing,
+    /// The adapter answered `initialize`; the client configures the session,
+    /// and launches or attaches to the debuggee.
+    Configuring,
+    /// The adapter answered `launch` or `attach`.
+    Running,
+    /// The `disconnect` request was sent, and waits for its response.
+    Disconnecting,
+    /// The adapter answered `disconnect`, which ends the session.
+    Disconnected,
+}
+
+/// A message sent out of order, reported by [`Session::observe`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The `seq` of the message.
+    pub seq: i64,
+    /// The side which sent the message.
+    pub side: Side,
+    /// What is wrong with the message.
+    pub kind: ViolationKind,
+}
+
+/// The kind of a [`Violation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ViolationKind {
+    /// A request was sent before the response to `initialize`.
+    NotInitialized {
+        /// The command of the request.
+        command: String,
+    },
+    /// `initialize` was sent again.
+    AlreadyInitialized,
+    /// The `initialized` event was sent before the response to `initialize`.
+    EarlyInitializedEvent,
+    /// A configuration request was sent before the `initialized` event.
+    NotConfigurable {
+        /// The command of the request.
+        command: String,
+    },
+    /// A request about the debuggee was sent before `launch` or `attach`.
+    NotLaunched {
+        /// The command of the request.
+        command: String,
+    },
+    /// `launch` or `attach` was sent again.
+    AlreadyLaunched {
+        /// The command of the request.
+        command: String,
+    },
+    /// A request was sent after `disconnect`, or a message after its
+    /// response.
+    Disconnected,
+    /// A response answers no request waiting for one.
+    UnknownRequest {
+        /// The `request_seq` of the response.
+        request_seq: i64,
+    },
+    /// The client sent an event.
+    ClientEvent {
+        /// The type of the event.
+        event: String,
+    },
+    /// The body of the response to `initialize` is not capabilities, or the
+    /// body of a `capabilities` event has none.
+    BadCapabilities {
+        /// Why the body failed to deserialize.
+        error: String,
+    },
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Side::Client => f.write_str("client"),
+            Side::Adapter => f.write_str("adapter"),
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "message {} of the {}: {}", self.seq, self.side, self.kind)
+    }
+}
+
+impl fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ViolationKind::NotInitialized { command } => write!(f, "{command} sent before initialize is answered"),
+            ViolationKind::AlreadyInitialized => f.write_str("initialize sent again"),
+            ViolationKind::EarlyInitializedEvent => f.write_str("initialized event sent before initialize is answered"),
+            ViolationKind::NotConfigurable { command } => write!(f, "{command} sent before the initialized event"),
+            ViolationKind::NotLaunched { command } => write!(f, "{command} sent before launch or attach"),
+            ViolationKind::AlreadyLaunched { command } => write!(f, "{command} sent after launch or attach"),
+            ViolationKind::Disconnected => f.write_str("message sent after disconnect"),
+            ViolationKind::UnknownRequest { request_seq } => write!(f, "response to unknown request {request_seq}"),
+            ViolationKind::ClientEvent { event } => write!(f, "event {event} sent by the client"),
+            ViolationKind::BadCapabilities { error } => write!(f, "bad capabilities: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Violation {}
+
+/// Requests configuring the session, which are sent after the `initialized`
+/// event.
+const CONFIGURATION_REQUESTS: &[&str] = &[
+    request::SetBreakpoints::COMMAND,
+    request::SetFunctionBreakpoints::COMMAND,
+    request::SetExceptionBreakpoints::COMMAND,
+    request::SetDataBreakpoints::COMMAND,
+    request::SetInstructionBreakpoints::COMMAND,
+    request::ConfigurationDone::COMMAND,
+];
+
+/// Requests which don't need a debuggee, besides the configuration ones.
+const SESSION_REQUESTS: &[&str] = &[
+    request::Initialize::COMMAND,
+    request::Launch::COMMAND,
+    request::Attach::COMMAND,
+    request::Disconnect::COMMAND,
+    request::Cancel::COMMAND,
+    request::BreakpointLocations::COMMAND,
+    request::DataBreakpointInfo::COMMAND,
+];
+
+/// The progress of a request of the lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Progress {
+    NotSent,
+    Sent,
+    Answered,
+}
+
+/// Tracks the lifecycle of a debug session.
+///
+/// Violations are reported, but don't stop the tracking: the state follows
+/// the messages as if they were sent in order.
+#[derive(Debug, Clone)]
+pub struct Session {
+    initialize: Progress,
+    initialized_event: bool,
+    launch: Progress,
+    disconnect: Progress,
+    /// The requests waiting for a response, by side and `seq`.
+    pending: HashMap<(Side, i64), String>,
+    capabilities: Option<Capabilities>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session::new()
+    }
+}
+
+impl Session {
+    /// Creates a session in the [`State::Uninitialized`] state.
+    pub fn new() -> Session {
+        Session {
+            initialize: Progress::NotSent,
+            initialized_event: false,
+            launch: Progress::NotSent,
+            disconnect: Progress::NotSent,
+            pending: HashMap::new(),
+            capabilities: None,
+        }
+    }
+
+    /// The current state of the session.
+    pub fn state(&self) -> State {
+        match (self.disconnect, self.initialize, self.launch) {
+            (Progress::Answered, _, _) => State::Disconnected,
+            (Progress::Sent, _, _) => State::Disconnecting,
+            (_, Progress::NotSent, _) => State::Uninitialized,
+            (_, Progress::Sent, _) => State::Initializing,
+            (_, Progress::Answered, Progress::Answered) => State::Running,
+            (_, Progress::Answered, _) => State::Configuring,
+        }
+    }
+
+    /// Whether the adapter sent the `initialized` event, after which the
+    /// client may configure the session.
+    pub fn is_configurable(&self) -> bool {
+        self.initialized_event
+    }
+
+    /// The capabilities of the adapter, as answered to `initialize` and
+    /// updated by `capabilities` events.
+    pub fn capabilities(&self) -> Option<&Capabilities> {
+        self.capabilities.as_ref()
+    }
+
+    /// Observes a message sent by `side`, and updates the state of the
+    /// session.
+    pub fn observe(&mut self, side: Side, message: &ProtocolMessage) -> Result<(), Violation> {
+        let result = if self.disconnect == Progress::Answered {
+            Err(ViolationKind::Disconnected)
+        } else {
+            match message {
+                ProtocolMessage::Request(request) => self.request(side, request),
+                ProtocolMessage::Response(response) => self.response(side, response),
+                ProtocolMessage::Event(event) => self.event(side, event),
+            }
+        };
+        result.map_err(|kind| Violation {
+            seq: message.seq(),
+            side,
+            kind,
+        })
+    }
+
+    fn request(&mut self, side: Side, request: &Request) -> Result<(), ViolationKind> {
+        self.pending.insert((side, request.seq), request.command.clone());
+        let command = request.command.as_str();
+        if side == Side::Adapter {
+            // Reverse requests need the capabilities of the client.
+            return match self.initialize {
+                Progress::Answered => Ok(()),
+                _ => Err(ViolationKind::NotInitialized {
+                    command: command.to_owned(),
+                }),
+            };
+        }
+
+        if self.disconnect == Progress::Sent {
+            return Err(ViolationKind::Disconnected);
+        }
+        if command == request::Initialize::COMMAND {
+            if self.initialize != Progress::NotSent {
+                return Err(ViolationKind::AlreadyInitialized);
+            }
+            self.initialize = Progress::Sent;
+            return Ok(());
+        }
+        if self.initialize != Progress::Answered {
+            return Err(ViolationKind::NotInitialized {
+                command: command.to_owned(),
+            });
+        }
+        match command {
+            request::Disconnect::COMMAND => self.disconnect = Progress::Sent,
+            request::Launch::COMMAND | request::Attach::COMMAND => {
+                if self.launch != Progress::NotSent {
+                    return Err(ViolationKind::AlreadyLaunched {
+                        command: command.to_owned(),
+                    });
+                }
+                self.launch = Progress::Sent;
+            }
+            _ if CONFIGURATION_REQUESTS.contains(&command) => {
+                if !self.initialized_event {
+                    return Err(ViolationKind::NotConfigurable {
+                        command: command.to_owned(),
+                    });
+                }
+            }
+            _ if SESSION_REQUESTS.contains(&command) => {}
+            _ => {
+                if self.launch == Progress::NotSent {
+                    return Err(ViolationKind::NotLaunched {
+                        command: command.to_owned(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn response(&mut self, side: Side, response: &Response) -> Result<(), ViolationKind> {
+        let Some(command) = self.pending.remove(&(side.peer(), response.request_seq)) else {
+            return Err(ViolationKind::UnknownRequest {
+                request_seq: response.request_seq,
+            });
+        };
+        if side == Side::Client {
+            return Ok(());
+        }
+
+        // A failed request may be sent again.
+        let progress = match response.success {
+            true => Progress::Answered,
+            false => Progress::NotSent,
+        };
+        match command.as_str() {
+            request::Initialize::COMMAND => {
+                self.initialize = progress;
+                if response.success {
+                    let body = response.body.clone().unwrap_or_default();
+                    // The capabilities stay unknown rather than all unsupported.
+                    let capabilities = crate::body_from_value(&body)
+                        .map_err(|err| ViolationKind::BadCapabilities { error: err.to_string() })?;
+                    self.capabilities = Some(capabilities);
+                }
+            }
+            request::Launch::COMMAND | request::Attach::COMMAND => self.launch = progress,
+            request::Disconnect::COMMAND => self.disconnect = progress,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn event(&mut self, side: Side, event: &Event) -> Result<(), ViolationKind> {
+        if side == Side::Client {
+            return Err(ViolationKind::ClientEvent {
+                event: event.event.clone(),
+            });
+        }
+        match event.event.as_str() {
+            event::Initialized::EVENT => {
+                self.initialized_event = true;
+                if self.initialize != Progress::Answered {
+                    return Err(ViolationKind::EarlyInitializedEvent);
+                }
+            }
+            event::Capabilities::EVENT => {
+                let delta: crate::CapabilitiesEvent = crate::body_from_value(&event.body)
+                    .map_err(|err| ViolationKind::BadCapabilities { error: err.to_string() })?;
+                if let Some(capabilities) = &mut self.capabilities {
+                    capabilities.merge(&delta.capabilities);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays the messages of a session, and returns the violations by index.
+    fn play(messages: &[(Side, ProtocolMessage)]) -> (Session, Vec<(usize, ViolationKind)>) {
+        let mut session = Session::new();
+        let violations = messages
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (side, message))| session.observe(*side, message).err().map(|v| (i, v.kind)))
+            .collect();
+        (session, violations)
+    }
+
+    fn request(seq: i64, command: &str) -> (Side, ProtocolMessage) {
+        let request = Request::new(seq, command.to_owned(), serde_json::Value::Null);
+        (Side::Client, ProtocolMessage::Request(request))
+    }
+
+    fn response(seq: i64, request_seq: i64, command: &str, body: serde_json::Value) -> (Side, ProtocolMessage) {
+        let response = Response::new(seq, request_seq, command.to_owned(), true, None, Some(body));
+        (Side::Adapter, ProtocolMessage::Response(response))
+    }
+
+    fn event(seq: i64, event: &str, body: serde_json::Value) -> (Side, ProtocolMessage) {
+        (
+            Side::Adapter,
+            ProtocolMessage::Event(Event::new(seq, event.to_owned(), body)),
+        )
+    }
+
+    #[test]
+    fn test_initialization_sequence() {
+        let null = serde_json::Value::Null;
+        let mut session = Session::new();
+        let mut states = Vec::new();
+        for (side, message) in [
+            request(1, "initialize"),
+            response(1, 1, "initialize", serde_json::json!({ "supportsStepBack": true })),
+            request(2, "launch"),
+            event(2, "initialized", null.clone()),
+            request(3, "setBreakpoints"),
+            response(3, 3, "setBreakpoints", serde_json::json!({ "breakpoints": [] })),
+            request(4, "configurationDone"),
+            response(4, 4, "configurationDone", null.clone()),
+            response(5, 2, "launch", null.clone()),
+            request(5, "stackTrace"),
+            response(6, 5, "stackTrace", serde_json::json!({ "stackFrames": [] })),
+            request(6, "disconnect"),
+            event(7, "terminated", null.clone()),
+            response(8, 6, "disconnect", null.clone()),
+        ] {
+            session.observe(side, &message).unwrap();
+            states.push(session.state());
+        }
+        use State::*;
+        assert_eq!(
+            states,
+            [
+                Initializing,
+                Configuring,
+                Configuring,
+                Configuring,
+                Configuring,
+                Configuring,
+                Configuring,
+                Configuring,
+                Running,
+                Running,
+                Running,
+                Disconnecting,
+                Disconnecting,
+                Disconnected
+            ]
+        );
+        assert_eq!(session.capabilities().unwrap().supports_step_back, Some(true));
+    }
+
+    #[test]
+    fn test_violations() {
+        let null = serde_json::Value::Null;
+        let (session, violations) = play(&[
+            request(1, "threads"),
+            event(1, "initialized", null.clone()),
+            request(2, "initialize"),
+            request(3, "initialize"),
+            response(2, 2, "initialize", null.clone()),
+            response(3, 9, "threads", null.clone()),
+            request(4, "stackTrace"),
+            request(5, "attach"),
+            request(6, "launch"),
+            (
+                Side::Client,
+                ProtocolMessage::Event(Event::new(7, "output".to_owned(), null.clone())),
+            ),
+        ]);
+        let command = |command: &str| command.to_owned();
+        assert_eq!(
+            violations,
+            [
+                (
+                    0,
+                    ViolationKind::NotInitialized {
+                        command: command("threads")
+                    }
+                ),
+                (1, ViolationKind::EarlyInitializedEvent),
+                (3, ViolationKind::AlreadyInitialized),
+                (5, ViolationKind::UnknownRequest { request_seq: 9 }),
+                (
+                    6,
+                    ViolationKind::NotLaunched {
+                        command: command("stackTrace")
+                    }
+                ),
+                (
+                    8,
+                    ViolationKind::AlreadyLaunched {
+                        command: command("launch")
+                    }
+                ),
+                (
+                    9,
+                    ViolationKind::ClientEvent {
+                        event: command("output")
+                    }
+                ),
+            ]
+        );
+        assert_eq!(session.state(), State::Configuring);
+    }
+
+    #[test]
+    fn test_configuration_before_initialized_event() {
+        let (_, violations) = play(&[
+            request(1, "initialize"),
+            response(1, 1, "initialize", serde_json::Value::Null),
+            request(2, "setExceptionBreakpoints"),
+        ]);
+        let command = "setExceptionBreakpoints".to_owned();
+        assert_eq!(violations, [(2, ViolationKind::NotConfigurable { command })]);
+    }
+
+    #[test]
+    fn test_after_disconnect() {
+        let null = serde_json::Value::Null;
+        let (session, violations) = play(&[
+            request(1, "initialize"),
+            response(1, 1, "initialize", null.clone()),
+            request(2, "disconnect"),
+            request(3, "threads"),
+            response(2, 2, "disconnect", null.clone()),
+            event(3, "output", null.clone()),
+        ]);
+        assert_eq!(
+            violations,
+            [(3, ViolationKind::Disconnected), (5, ViolationKind::Disconnected)]
+        );
+        assert_eq!(session.state(), State::Disconnected);
+    }
+
+    #[test]
+    fn test_capabilities_event() {
+        let (session, violations) = play(&[
+            request(1, "initialize"),
+            response(
+                1,
+                1,
+                "initialize",
+                serde_json::json!({ "supportsModulesRequest": true }),
+            ),
+            event(
+                2,
+                "capabilities",
+                serde_json::json!({ "capabilities": { "supportsStepBack": true } }),
+            ),
+        ]);
+        assert!(violations.is_empty());
+        let capabilities = session.capabilities().unwrap();
+        assert_eq!(capabilities.supports_modules_request, Some(true));
+        assert_eq!(capabilities.supports_step_back, Some(true));
+    }
+
+    #[test]
+    fn test_bad_capabilities() {
+        let (session, violations) = play(&[
+            request(1, "initialize"),
+            response(1, 1, "initialize", serde_json::json!({ "supportsStepBack": "yes" })),
+        ]);
+        assert!(matches!(violations[..], [(1, ViolationKind::BadCapabilities { .. })]));
+        assert_eq!(session.state(), State::Configuring);
+        assert!(session.capabilities().is_none());
+
+        let (session, violations) = play(&[
+            request(1, "initialize"),
+            response(1, 1, "initialize", serde_json::json!({ "supportsStepBack": true })),
+            event(
+                2,
+                "capabilities",
+                serde_json::json!({ "capabilities": { "supportsStepBack": "no" } }),
+            ),
+        ]);
+        assert!(matches!(violations[..], [(2, ViolationKind::BadCapabilities { .. })]));
+        assert_eq!(session.capabilities().unwrap().supports_step_back, Some(true));
+    }
+
+    #[test]
+    fn test_display() {
+        let violation = Violation {
+            seq: 4,
+            side: Side::Client,
+            kind: ViolationKind::NotLaunched {
+                command: "stackTrace".to_owned(),
+            },
+        };
+        assert_eq!(
+            violation.to_string(),
+            "message 4 of the client: stackTrace sent before launch or attach"
+        );
+    }
+}
@@ -29,6 +29,31 @@ pub struct RestartArguments {
     pub raw: serde_json::Value,
 }
 
+/// The id of a breakpoint, set by the debug adapter.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct BreakpointId(pub u64);
+
+/// The id of a stack frame, unique across all threads.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct FrameId(pub u64);
+
+/// A reference to the contents of a source, retrieved with the `source` request. The value 0 means the source is available from its path.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct SourceReference(pub u32);
+
+/// The id of a thread.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ThreadId(pub u64);
+
+/// A reference to the children of a variable or scope. The value 0 means there are none.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct VariablesReference(pub u32);
+
 /// Information about a breakpoint created in `setBreakpoints`, `setFunctionBreakpoints`, `setInstructionBreakpoints`, or `setDataBreakpoints` requests.
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -45,7 +70,7 @@ pub struct Breakpoint {
     pub end_line: Option<u32>,
     /// The identifier for the breakpoint. It is needed if breakpoint events are used to update or remove breakpoints.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub id: Option<u64>,
+    pub id: Option<BreakpointId>,
     /// A memory reference to where the breakpoint is set.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub instruction_reference: Option<String>,
@@ -59,7 +84,7 @@ pub struct Breakpoint {
     /// The offset from the instruction reference.
     /// This can be negative.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub offset: Option<u32>,
+    pub offset: Option<i64>,
     /// A machine-readable explanation of why a breakpoint may not be verified. If a breakpoint is verified or a specific reason is not known, the adapter should omit this property. Possible values include:
     ///
     /// - `pending`: Indicates a breakpoint might be verified in the future, but the adapter cannot verify it in the current state.
@@ -229,7 +254,7 @@ pub struct Capabilities {
     /// The debug adapter supports ANSI escape sequences in styling of `OutputEvent.output` and `Variable.value` fields.
     #[serde(rename = "supportsANSIStyling")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub supports_ansistyling: Option<bool>,
+    pub supports_ansi_styling: Option<bool>,
     /// The debug adapter supports the `breakpointLocations` request.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub supports_breakpoint_locations_request: Option<bool>,
@@ -334,6 +359,359 @@ pub struct Capabilities {
     pub supports_write_memory_request: Option<bool>,
 }
 
+impl Capabilities {
+    #[deprecated(note = "renamed to `supports_ansi_styling`")]
+    pub fn supports_ansistyling(&self) -> &Option<bool> {
+        &self.supports_ansi_styling
+    }
+
+    #[deprecated(note = "renamed to `supports_ansi_styling`")]
+    pub fn supports_ansistyling_mut(&mut self) -> &mut Option<bool> {
+        &mut self.supports_ansi_styling
+    }
+}
+
+impl Capabilities {
+    /// Whether the boolean capability of the given protocol name is true.
+    ///
+    /// Unset and unknown capabilities are not supported.
+    pub fn supports(&self, capability: &str) -> bool {
+        match capability {
+            "supportSuspendDebuggee" => self.support_suspend_debuggee == Some(true),
+            "supportTerminateDebuggee" => self.support_terminate_debuggee == Some(true),
+            "supportsANSIStyling" => self.supports_ansi_styling == Some(true),
+            "supportsBreakpointLocationsRequest" => self.supports_breakpoint_locations_request == Some(true),
+            "supportsCancelRequest" => self.supports_cancel_request == Some(true),
+            "supportsClipboardContext" => self.supports_clipboard_context == Some(true),
+            "supportsCompletionsRequest" => self.supports_completions_request == Some(true),
+            "supportsConditionalBreakpoints" => self.supports_conditional_breakpoints == Some(true),
+            "supportsConfigurationDoneRequest" => self.supports_configuration_done_request == Some(true),
+            "supportsDataBreakpointBytes" => self.supports_data_breakpoint_bytes == Some(true),
+            "supportsDataBreakpoints" => self.supports_data_breakpoints == Some(true),
+            "supportsDelayedStackTraceLoading" => self.supports_delayed_stack_trace_loading == Some(true),
+            "supportsDisassembleRequest" => self.supports_disassemble_request == Some(true),
+            "supportsEvaluateForHovers" => self.supports_evaluate_for_hovers == Some(true),
+            "supportsExceptionFilterOptions" => self.supports_exception_filter_options == Some(true),
+            "supportsExceptionInfoRequest" => self.supports_exception_info_request == Some(true),
+            "supportsExceptionOptions" => self.supports_exception_options == Some(true),
+            "supportsFunctionBreakpoints" => self.supports_function_breakpoints == Some(true),
+            "supportsGotoTargetsRequest" => self.supports_goto_targets_request == Some(true),
+            "supportsHitConditionalBreakpoints" => self.supports_hit_conditional_breakpoints == Some(true),
+            "supportsInstructionBreakpoints" => self.supports_instruction_breakpoints == Some(true),
+            "supportsLoadedSourcesRequest" => self.supports_loaded_sources_request == Some(true),
+            "supportsLogPoints" => self.supports_log_points == Some(true),
+            "supportsModulesRequest" => self.supports_modules_request == Some(true),
+            "supportsReadMemoryRequest" => self.supports_read_memory_request == Some(true),
+            "supportsRestartFrame" => self.supports_restart_frame == Some(true),
+            "supportsRestartRequest" => self.supports_restart_request == Some(true),
+            "supportsSetExpression" => self.supports_set_expression == Some(true),
+            "supportsSetVariable" => self.supports_set_variable == Some(true),
+            "supportsSingleThreadExecutionRequests" => self.supports_single_thread_execution_requests == Some(true),
+            "supportsStepBack" => self.supports_step_back == Some(true),
+            "supportsStepInTargetsRequest" => self.supports_step_in_targets_request == Some(true),
+            "supportsSteppingGranularity" => self.supports_stepping_granularity == Some(true),
+            "supportsTerminateRequest" => self.supports_terminate_request == Some(true),
+            "supportsTerminateThreadsRequest" => self.supports_terminate_threads_request == Some(true),
+            "supportsValueFormattingOptions" => self.supports_value_formatting_options == Some(true),
+            "supportsWriteMemoryRequest" => self.supports_write_memory_request == Some(true),
+            _ => false,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Updates the capabilities set in `delta`, e.g. the body of a `capabilities` event.
+    pub fn merge(&mut self, delta: &Capabilities) {
+        if let Some(value) = &delta.additional_module_columns {
+            self.additional_module_columns = Some(value.clone());
+        }
+        if let Some(value) = &delta.breakpoint_modes {
+            self.breakpoint_modes = Some(value.clone());
+        }
+        if let Some(value) = &delta.completion_trigger_characters {
+            self.completion_trigger_characters = Some(value.clone());
+        }
+        if let Some(value) = &delta.exception_breakpoint_filters {
+            self.exception_breakpoint_filters = Some(value.clone());
+        }
+        if let Some(value) = &delta.support_suspend_debuggee {
+            self.support_suspend_debuggee = Some(*value);
+        }
+        if let Some(value) = &delta.support_terminate_debuggee {
+            self.support_terminate_debuggee = Some(*value);
+        }
+        if let Some(value) = &delta.supported_checksum_algorithms {
+            self.supported_checksum_algorithms = Some(value.clone());
+        }
+        if let Some(value) = &delta.supports_ansi_styling {
+            self.supports_ansi_styling = Some(*value);
+        }
+        if let Some(value) = &delta.supports_breakpoint_locations_request {
+            self.supports_breakpoint_locations_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_cancel_request {
+            self.supports_cancel_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_clipboard_context {
+            self.supports_clipboard_context = Some(*value);
+        }
+        if let Some(value) = &delta.supports_completions_request {
+            self.supports_completions_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_conditional_breakpoints {
+            self.supports_conditional_breakpoints = Some(*value);
+        }
+        if let Some(value) = &delta.supports_configuration_done_request {
+            self.supports_configuration_done_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_data_breakpoint_bytes {
+            self.supports_data_breakpoint_bytes = Some(*value);
+        }
+        if let Some(value) = &delta.supports_data_breakpoints {
+            self.supports_data_breakpoints = Some(*value);
+        }
+        if let Some(value) = &delta.supports_delayed_stack_trace_loading {
+            self.supports_delayed_stack_trace_loading = Some(*value);
+        }
+        if let Some(value) = &delta.supports_disassemble_request {
+            self.supports_disassemble_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_evaluate_for_hovers {
+            self.supports_evaluate_for_hovers = Some(*value);
+        }
+        if let Some(value) = &delta.supports_exception_filter_options {
+            self.supports_exception_filter_options = Some(*value);
+        }
+        if let Some(value) = &delta.supports_exception_info_request {
+            self.supports_exception_info_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_exception_options {
+            self.supports_exception_options = Some(*value);
+        }
+        if let Some(value) = &delta.supports_function_breakpoints {
+            self.supports_function_breakpoints = Some(*value);
+        }
+        if let Some(value) = &delta.supports_goto_targets_request {
+            self.supports_goto_targets_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_hit_conditional_breakpoints {
+            self.supports_hit_conditional_breakpoints = Some(*value);
+        }
+        if let Some(value) = &delta.supports_instruction_breakpoints {
+            self.supports_instruction_breakpoints = Some(*value);
+        }
+        if let Some(value) = &delta.supports_loaded_sources_request {
+            self.supports_loaded_sources_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_log_points {
+            self.supports_log_points = Some(*value);
+        }
+        if let Some(value) = &delta.supports_modules_request {
+            self.supports_modules_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_read_memory_request {
+            self.supports_read_memory_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_restart_frame {
+            self.supports_restart_frame = Some(*value);
+        }
+        if let Some(value) = &delta.supports_restart_request {
+            self.supports_restart_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_set_expression {
+            self.supports_set_expression = Some(*value);
+        }
+        if let Some(value) = &delta.supports_set_variable {
+            self.supports_set_variable = Some(*value);
+        }
+        if let Some(value) = &delta.supports_single_thread_execution_requests {
+            self.supports_single_thread_execution_requests = Some(*value);
+        }
+        if let Some(value) = &delta.supports_step_back {
+            self.supports_step_back = Some(*value);
+        }
+        if let Some(value) = &delta.supports_step_in_targets_request {
+            self.supports_step_in_targets_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_stepping_granularity {
+            self.supports_stepping_granularity = Some(*value);
+        }
+        if let Some(value) = &delta.supports_terminate_request {
+            self.supports_terminate_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_terminate_threads_request {
+            self.supports_terminate_threads_request = Some(*value);
+        }
+        if let Some(value) = &delta.supports_value_formatting_options {
+            self.supports_value_formatting_options = Some(*value);
+        }
+        if let Some(value) = &delta.supports_write_memory_request {
+            self.supports_write_memory_request = Some(*value);
+        }
+    }
+
+    /// Returns the protocol names of the capabilities whose value differs in `other`.
+    ///
+    /// An unset flag is unsupported, so it doesn't differ from `false`.
+    pub fn diff(&self, other: &Capabilities) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+        if self.additional_module_columns != other.additional_module_columns {
+            changed.push("additionalModuleColumns");
+        }
+        if self.breakpoint_modes != other.breakpoint_modes {
+            changed.push("breakpointModes");
+        }
+        if self.completion_trigger_characters != other.completion_trigger_characters {
+            changed.push("completionTriggerCharacters");
+        }
+        if self.exception_breakpoint_filters != other.exception_breakpoint_filters {
+            changed.push("exceptionBreakpointFilters");
+        }
+        if self.support_suspend_debuggee.unwrap_or(false) != other.support_suspend_debuggee.unwrap_or(false) {
+            changed.push("supportSuspendDebuggee");
+        }
+        if self.support_terminate_debuggee.unwrap_or(false) != other.support_terminate_debuggee.unwrap_or(false) {
+            changed.push("supportTerminateDebuggee");
+        }
+        if self.supported_checksum_algorithms != other.supported_checksum_algorithms {
+            changed.push("supportedChecksumAlgorithms");
+        }
+        if self.supports_ansi_styling.unwrap_or(false) != other.supports_ansi_styling.unwrap_or(false) {
+            changed.push("supportsANSIStyling");
+        }
+        if self.supports_breakpoint_locations_request.unwrap_or(false)
+            != other.supports_breakpoint_locations_request.unwrap_or(false)
+        {
+            changed.push("supportsBreakpointLocationsRequest");
+        }
+        if self.supports_cancel_request.unwrap_or(false) != other.supports_cancel_request.unwrap_or(false) {
+            changed.push("supportsCancelRequest");
+        }
+        if self.supports_clipboard_context.unwrap_or(false) != other.supports_clipboard_context.unwrap_or(false) {
+            changed.push("supportsClipboardContext");
+        }
+        if self.supports_completions_request.unwrap_or(false) != other.supports_completions_request.unwrap_or(false) {
+            changed.push("supportsCompletionsRequest");
+        }
+        if self.supports_conditional_breakpoints.unwrap_or(false)
+            != other.supports_conditional_breakpoints.unwrap_or(false)
+        {
+            changed.push("supportsConditionalBreakpoints");
+        }
+        if self.supports_configuration_done_request.unwrap_or(false)
+            != other.supports_configuration_done_request.unwrap_or(false)
+        {
+            changed.push("supportsConfigurationDoneRequest");
+        }
+        if self.supports_data_breakpoint_bytes.unwrap_or(false) != other.supports_data_breakpoint_bytes.unwrap_or(false)
+        {
+            changed.push("supportsDataBreakpointBytes");
+        }
+        if self.supports_data_breakpoints.unwrap_or(false) != other.supports_data_breakpoints.unwrap_or(false) {
+            changed.push("supportsDataBreakpoints");
+        }
+        if self.supports_delayed_stack_trace_loading.unwrap_or(false)
+            != other.supports_delayed_stack_trace_loading.unwrap_or(false)
+        {
+            changed.push("supportsDelayedStackTraceLoading");
+        }
+        if self.supports_disassemble_request.unwrap_or(false) != other.supports_disassemble_request.unwrap_or(false) {
+            changed.push("supportsDisassembleRequest");
+        }
+        if self.supports_evaluate_for_hovers.unwrap_or(false) != other.supports_evaluate_for_hovers.unwrap_or(false) {
+            changed.push("supportsEvaluateForHovers");
+        }
+        if self.supports_exception_filter_options.unwrap_or(false)
+            != other.supports_exception_filter_options.unwrap_or(false)
+        {
+            changed.push("supportsExceptionFilterOptions");
+        }
+        if self.supports_exception_info_request.unwrap_or(false)
+            != other.supports_exception_info_request.unwrap_or(false)
+        {
+            changed.push("supportsExceptionInfoRequest");
+        }
+        if self.supports_exception_options.unwrap_or(false) != other.supports_exception_options.unwrap_or(false) {
+            changed.push("supportsExceptionOptions");
+        }
+        if self.supports_function_breakpoints.unwrap_or(false) != other.supports_function_breakpoints.unwrap_or(false) {
+            changed.push("supportsFunctionBreakpoints");
+        }
+        if self.supports_goto_targets_request.unwrap_or(false) != other.supports_goto_targets_request.unwrap_or(false) {
+            changed.push("supportsGotoTargetsRequest");
+        }
+        if self.supports_hit_conditional_breakpoints.unwrap_or(false)
+            != other.supports_hit_conditional_breakpoints.unwrap_or(false)
+        {
+            changed.push("supportsHitConditionalBreakpoints");
+        }
+        if self.supports_instruction_breakpoints.unwrap_or(false)
+            != other.supports_instruction_breakpoints.unwrap_or(false)
+        {
+            changed.push("supportsInstructionBreakpoints");
+        }
+        if self.supports_loaded_sources_request.unwrap_or(false)
+            != other.supports_loaded_sources_request.unwrap_or(false)
+        {
+            changed.push("supportsLoadedSourcesRequest");
+        }
+        if self.supports_log_points.unwrap_or(false) != other.supports_log_points.unwrap_or(false) {
+            changed.push("supportsLogPoints");
+        }
+        if self.supports_modules_request.unwrap_or(false) != other.supports_modules_request.unwrap_or(false) {
+            changed.push("supportsModulesRequest");
+        }
+        if self.supports_read_memory_request.unwrap_or(false) != other.supports_read_memory_request.unwrap_or(false) {
+            changed.push("supportsReadMemoryRequest");
+        }
+        if self.supports_restart_frame.unwrap_or(false) != other.supports_restart_frame.unwrap_or(false) {
+            changed.push("supportsRestartFrame");
+        }
+        if self.supports_restart_request.unwrap_or(false) != other.supports_restart_request.unwrap_or(false) {
+            changed.push("supportsRestartRequest");
+        }
+        if self.supports_set_expression.unwrap_or(false) != other.supports_set_expression.unwrap_or(false) {
+            changed.push("supportsSetExpression");
+        }
+        if self.supports_set_variable.unwrap_or(false) != other.supports_set_variable.unwrap_or(false) {
+            changed.push("supportsSetVariable");
+        }
+        if self.supports_single_thread_execution_requests.unwrap_or(false)
+            != other.supports_single_thread_execution_requests.unwrap_or(false)
+        {
+            changed.push("supportsSingleThreadExecutionRequests");
+        }
+        if self.supports_step_back.unwrap_or(false) != other.supports_step_back.unwrap_or(false) {
+            changed.push("supportsStepBack");
+        }
+        if self.supports_step_in_targets_request.unwrap_or(false)
+            != other.supports_step_in_targets_request.unwrap_or(false)
+        {
+            changed.push("supportsStepInTargetsRequest");
+        }
+        if self.supports_stepping_granularity.unwrap_or(false) != other.supports_stepping_granularity.unwrap_or(false) {
+            changed.push("supportsSteppingGranularity");
+        }
+        if self.supports_terminate_request.unwrap_or(false) != other.supports_terminate_request.unwrap_or(false) {
+            changed.push("supportsTerminateRequest");
+        }
+        if self.supports_terminate_threads_request.unwrap_or(false)
+            != other.supports_terminate_threads_request.unwrap_or(false)
+        {
+            changed.push("supportsTerminateThreadsRequest");
+        }
+        if self.supports_value_formatting_options.unwrap_or(false)
+            != other.supports_value_formatting_options.unwrap_or(false)
+        {
+            changed.push("supportsValueFormattingOptions");
+        }
+        if self.supports_write_memory_request.unwrap_or(false) != other.supports_write_memory_request.unwrap_or(false) {
+            changed.push("supportsWriteMemoryRequest");
+        }
+        changed
+    }
+}
+
 /// The event indicates that one or more capabilities have changed.
 /// Since the capabilities are dependent on the client and its UI, it might not be possible to change that at random times (or too late).
 /// Consequently this event has a hint characteristic: a client can only be expected to make a 'best effort' in honoring individual capabilities but there are no guarantees.
@@ -424,7 +802,7 @@ pub struct CompletionItem {
     pub sort_text: Option<String>,
     /// Start position (within the `text` attribute of the `completions` request) where the completion text is added. The position is measured in UTF-16 code units and the client capability `columnsStartAt1` determines whether it is 0- or 1-based. If the start position is omitted the text is added at the location specified by the `column` attribute of the `completions` request.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub start: Option<u64>,
+    pub start: Option<u32>,
     /// If text is returned and not an empty string, then it is inserted instead of the label.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
@@ -467,7 +845,7 @@ pub struct CompletionsArguments {
     pub column: u32,
     /// Returns completions in the scope of this stack frame. If not specified, the completions are returned for the global scope.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub frame_id: Option<u64>,
+    pub frame_id: Option<FrameId>,
     /// A line for which to determine the completion proposals. If missing the first line of the text is assumed.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub line: Option<u32>,
@@ -495,7 +873,7 @@ pub struct ContinueArguments {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub single_thread: Option<bool>,
     /// Specifies the active thread. If the debug adapter supports single thread execution (see `supportsSingleThreadExecutionRequests`) and the argument `singleThread` is true, only the thread with this ID is resumed.
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// Response to `continue` request.
@@ -517,7 +895,7 @@ pub struct ContinuedEvent {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub all_threads_continued: Option<bool>,
     /// The thread which was continued.
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// Properties of a data breakpoint passed to the `setDataBreakpoints` request.
@@ -564,7 +942,7 @@ pub struct DataBreakpointInfoArguments {
     pub bytes: Option<u64>,
     /// When `name` is an expression, evaluate it in the scope of this stack frame. If not specified, the expression is evaluated in the global scope. When `variablesReference` is specified, this property has no effect.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub frame_id: Option<u64>,
+    pub frame_id: Option<FrameId>,
     /// The mode of the desired breakpoint. If defined, this must be one of the `breakpointModes` the debug adapter advertised in its `Capabilities`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mode: Option<String>,
@@ -573,7 +951,7 @@ pub struct DataBreakpointInfoArguments {
     pub name: String,
     /// Reference to the variable container if the data breakpoint is requested for a child of the container. The `variablesReference` must have been obtained in the current suspended state. See 'Lifetime of Object References' in the Overview section for details.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub variables_reference: Option<u32>,
+    pub variables_reference: Option<VariablesReference>,
 }
 
 /// Response to `dataBreakpointInfo` request.
@@ -601,12 +979,12 @@ pub struct DisassembleArguments {
     pub instruction_count: u64,
     /// Offset (in instructions) to be applied after the byte offset (if any) before disassembling. Can be negative.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub instruction_offset: Option<u64>,
+    pub instruction_offset: Option<i64>,
     /// Memory reference to the base location containing the instructions to disassemble.
     pub memory_reference: String,
     /// Offset (in bytes) to be applied to the reference location before disassembling. Can be negative.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub offset: Option<u32>,
+    pub offset: Option<i64>,
     /// If true, the adapter should attempt to resolve memory addresses and other values to symbolic names.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub resolve_symbols: Option<bool>,
@@ -719,7 +1097,7 @@ pub struct EvaluateArguments {
     pub format: Option<ValueFormat>,
     /// Evaluate the expression in the scope of this stack frame. If not specified, the expression is evaluated in the global scope.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub frame_id: Option<u64>,
+    pub frame_id: Option<FrameId>,
     /// The contextual line where the expression should be evaluated. In the 'hover' context, this should be set to the start of the expression being hovered.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub line: Option<u32>,
@@ -785,7 +1163,7 @@ pub struct EvaluateResponse {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub value_location_reference: Option<u64>,
     /// If `variablesReference` is > 0, the evaluate result is structured and its children can be retrieved by passing `variablesReference` to the `variables` request as long as execution remains suspended. See 'Lifetime of Object References' in the Overview section for details.
-    pub variables_reference: u32,
+    pub variables_reference: VariablesReference,
 }
 
 /// This enumeration defines all possible conditions when a thrown exception should result in a break.
@@ -868,7 +1246,7 @@ pub struct ExceptionFilterOptions {
 #[serde(rename_all = "camelCase")]
 pub struct ExceptionInfoArguments {
     /// Thread for which exception information should be retrieved.
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// Response to `exceptionInfo` request.
@@ -916,7 +1294,7 @@ pub struct ExceptionPathSegment {
 #[serde(rename_all = "camelCase")]
 pub struct ExitedEvent {
     /// The exit code returned from the debuggee.
-    pub exit_code: u64,
+    pub exit_code: i64,
 }
 
 /// Properties of a breakpoint passed to the `setFunctionBreakpoints` request.
@@ -943,7 +1321,7 @@ pub struct GotoArguments {
     /// The location where the debuggee will continue to run.
     pub target_id: u64,
     /// Set the goto target for this thread.
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// A `GotoTarget` describes a code location that can be used as a target in the `goto` request.
@@ -1021,7 +1399,7 @@ pub struct InitializeRequestArguments {
     /// The client will interpret ANSI escape sequences in the display of `OutputEvent.output` and `Variable.value` fields when `Capabilities.supportsANSIStyling` is also enabled.
     #[serde(rename = "supportsANSIStyling")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub supports_ansistyling: Option<bool>,
+    pub supports_ansi_styling: Option<bool>,
     /// Client supports the `argsCanBeInterpretedByShell` attribute on the `runInTerminal` request.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub supports_args_can_be_interpreted_by_shell: Option<bool>,
@@ -1051,6 +1429,18 @@ pub struct InitializeRequestArguments {
     pub supports_variable_type: Option<bool>,
 }
 
+impl InitializeRequestArguments {
+    #[deprecated(note = "renamed to `supports_ansi_styling`")]
+    pub fn supports_ansistyling(&self) -> &Option<bool> {
+        &self.supports_ansi_styling
+    }
+
+    #[deprecated(note = "renamed to `supports_ansi_styling`")]
+    pub fn supports_ansistyling_mut(&mut self) -> &mut Option<bool> {
+        &mut self.supports_ansi_styling
+    }
+}
+
 /// Determines in what format paths are specified. The default is `path`, which is the native format.
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[non_exhaustive]
@@ -1063,6 +1453,29 @@ pub enum InitializeRequestArgumentsPathFormat {
     Unknown,
 }
 
+impl InitializeRequestArguments {
+    /// Whether the boolean capability of the given protocol name is true.
+    ///
+    /// Unset and unknown capabilities are not supported.
+    pub fn supports(&self, capability: &str) -> bool {
+        match capability {
+            "columnsStartAt1" => self.columns_start_at1 == Some(true),
+            "linesStartAt1" => self.lines_start_at1 == Some(true),
+            "supportsANSIStyling" => self.supports_ansi_styling == Some(true),
+            "supportsArgsCanBeInterpretedByShell" => self.supports_args_can_be_interpreted_by_shell == Some(true),
+            "supportsInvalidatedEvent" => self.supports_invalidated_event == Some(true),
+            "supportsMemoryEvent" => self.supports_memory_event == Some(true),
+            "supportsMemoryReferences" => self.supports_memory_references == Some(true),
+            "supportsProgressReporting" => self.supports_progress_reporting == Some(true),
+            "supportsRunInTerminalRequest" => self.supports_run_in_terminal_request == Some(true),
+            "supportsStartDebuggingRequest" => self.supports_start_debugging_request == Some(true),
+            "supportsVariablePaging" => self.supports_variable_paging == Some(true),
+            "supportsVariableType" => self.supports_variable_type == Some(true),
+            _ => false,
+        }
+    }
+}
+
 /// Properties of a breakpoint passed to the `setInstructionBreakpoints` request
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -1085,7 +1498,7 @@ pub struct InstructionBreakpoint {
     /// The offset from the instruction reference in bytes.
     /// This can be negative.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub offset: Option<u32>,
+    pub offset: Option<i64>,
 }
 
 /// Logical areas that can be invalidated by the `invalidated` event.
@@ -1117,10 +1530,10 @@ pub struct InvalidatedEvent {
     pub areas: Option<Vec<InvalidatedAreas>>,
     /// If specified, the client only needs to refetch data related to this stack frame (and the `threadId` is ignored).
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub stack_frame_id: Option<u64>,
+    pub stack_frame_id: Option<FrameId>,
     /// If specified, the client only needs to refetch data related to this thread.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub thread_id: Option<u64>,
+    pub thread_id: Option<ThreadId>,
 }
 
 /// The event indicates that some source has been added, changed, or removed from the set of all loaded sources.
@@ -1192,7 +1605,7 @@ pub struct MemoryEvent {
     /// Memory reference of a memory range that has been updated.
     pub memory_reference: String,
     /// Starting offset in bytes where memory has been updated. Can be negative.
-    pub offset: u32,
+    pub offset: i64,
 }
 
 /// A structured message object. Used to return errors from requests.
@@ -1314,7 +1727,7 @@ pub struct NextArguments {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub single_thread: Option<bool>,
     /// Specifies the thread for which to resume execution for one step (of the given granularity).
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// The event indicates that the target has produced some output.
@@ -1352,7 +1765,7 @@ pub struct OutputEvent {
     pub source: Option<Source>,
     /// If an attribute `variablesReference` exists and its value is > 0, the output contains objects which can be retrieved by passing `variablesReference` to the `variables` request as long as execution remains suspended. See 'Lifetime of Object References' in the Overview section for details.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub variables_reference: Option<u32>,
+    pub variables_reference: Option<VariablesReference>,
 }
 
 /// The output category. If not specified or if the category is not understood by the client, `console` is assumed.
@@ -1395,7 +1808,7 @@ pub enum OutputEventGroup {
 #[serde(rename_all = "camelCase")]
 pub struct PauseArguments {
     /// Pause execution for this thread.
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// The event indicates that the debugger has begun debugging a new process. Either one that it has launched, or one that it has attached to.
@@ -1486,6 +1899,44 @@ pub struct ProgressUpdateEvent {
     pub progress_id: String,
 }
 
+/// Base class of requests, responses, and events.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ProtocolMessage {
+    Request(crate::Request),
+    Response(crate::Response),
+    Event(crate::Event),
+}
+
+impl ProtocolMessage {
+    /// Sequence number of the message.
+    pub fn seq(&self) -> i64 {
+        match self {
+            ProtocolMessage::Request(m) => m.seq,
+            ProtocolMessage::Response(m) => m.seq,
+            ProtocolMessage::Event(m) => m.seq,
+        }
+    }
+}
+
+impl From<crate::Request> for ProtocolMessage {
+    fn from(m: crate::Request) -> Self {
+        ProtocolMessage::Request(m)
+    }
+}
+
+impl From<crate::Response> for ProtocolMessage {
+    fn from(m: crate::Response) -> Self {
+        ProtocolMessage::Response(m)
+    }
+}
+
+impl From<crate::Event> for ProtocolMessage {
+    fn from(m: crate::Event) -> Self {
+        ProtocolMessage::Event(m)
+    }
+}
+
 /// Arguments for `readMemory` request.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -1496,7 +1947,7 @@ pub struct ReadMemoryArguments {
     pub memory_reference: String,
     /// Offset (in bytes) to be applied to the reference location before reading data. Can be negative.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub offset: Option<u32>,
+    pub offset: Option<i64>,
 }
 
 /// Response to `readMemory` request.
@@ -1520,7 +1971,7 @@ pub struct ReadMemoryResponse {
 #[serde(rename_all = "camelCase")]
 pub struct RestartFrameArguments {
     /// Restart the stack frame identified by `frameId`. The `frameId` must have been obtained in the current suspended state. See 'Lifetime of Object References' in the Overview section for details.
-    pub frame_id: u64,
+    pub frame_id: FrameId,
 }
 
 /// Arguments for `reverseContinue` request.
@@ -1531,7 +1982,7 @@ pub struct ReverseContinueArguments {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub single_thread: Option<bool>,
     /// Specifies the active thread. If the debug adapter supports single thread execution (see `supportsSingleThreadExecutionRequests`) and the `singleThread` argument is true, only the thread with this ID is resumed.
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// Arguments for `runInTerminal` request.
@@ -1611,7 +2062,7 @@ pub struct Scope {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source: Option<Source>,
     /// The variables of this scope can be retrieved by passing the value of `variablesReference` to the `variables` request as long as execution remains suspended. See 'Lifetime of Object References' in the Overview section for details.
-    pub variables_reference: u32,
+    pub variables_reference: VariablesReference,
 }
 
 /// A hint for how to present this scope in the UI. If this attribute is missing, the scope is shown with a generic UI.
@@ -1637,7 +2088,7 @@ pub enum ScopePresentationHint {
 #[serde(rename_all = "camelCase")]
 pub struct ScopesArguments {
     /// Retrieve the scopes for the stack frame identified by `frameId`. The `frameId` must have been obtained in the current suspended state. See 'Lifetime of Object References' in the Overview section for details.
-    pub frame_id: u64,
+    pub frame_id: FrameId,
 }
 
 /// Response to `scopes` request.
@@ -1657,7 +2108,7 @@ pub struct SetBreakpointsArguments {
     pub breakpoints: Option<Vec<SourceBreakpoint>>,
     /// Deprecated: The code locations of the breakpoints.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub lines: Option<Vec<u64>>,
+    pub lines: Option<Vec<u32>>,
     /// The source location of the breakpoints; either `source.path` or `source.sourceReference` must be specified.
     pub source: Source,
     /// A value of true indicates that the underlying source has been modified which results in new breakpoint locations.
@@ -1734,7 +2185,7 @@ pub struct SetExpressionArguments {
     pub format: Option<ValueFormat>,
     /// Evaluate the expressions in the scope of this stack frame. If not specified, the expressions are evaluated in the global scope.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub frame_id: Option<u64>,
+    pub frame_id: Option<FrameId>,
     /// The value expression to assign to the l-value expression.
     pub value: String,
 }
@@ -1775,7 +2226,7 @@ pub struct SetExpressionResponse {
     pub value_location_reference: Option<u64>,
     /// If `variablesReference` is > 0, the evaluate result is structured and its children can be retrieved by passing `variablesReference` to the `variables` request as long as execution remains suspended. See 'Lifetime of Object References' in the Overview section for details.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub variables_reference: Option<u32>,
+    pub variables_reference: Option<VariablesReference>,
 }
 
 /// Arguments for `setFunctionBreakpoints` request.
@@ -1823,7 +2274,7 @@ pub struct SetVariableArguments {
     /// The value of the variable.
     pub value: String,
     /// The reference of the variable container. The `variablesReference` must have been obtained in the current suspended state. See 'Lifetime of Object References' in the Overview section for details.
-    pub variables_reference: u32,
+    pub variables_reference: VariablesReference,
 }
 
 /// Response to `setVariable` request.
@@ -1860,7 +2311,7 @@ pub struct SetVariableResponse {
     ///
     /// If this property is included in the response, any `variablesReference` previously associated with the updated variable, and those of its children, are no longer valid.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub variables_reference: Option<u32>,
+    pub variables_reference: Option<VariablesReference>,
 }
 
 /// A `Source` is a descriptor for source code.
@@ -1894,7 +2345,7 @@ pub struct Source {
     /// Since a `sourceReference` is only valid for a session, it can not be used to persist a source.
     /// The value should be less than or equal to 2147483647 (2^31-1).
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub source_reference: Option<u32>,
+    pub source_reference: Option<SourceReference>,
     /// A list of sources that are related to this source. These may be the source that generated this source.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sources: Option<Vec<Source>>,
@@ -1922,7 +2373,7 @@ pub struct SourceArguments {
     pub source: Option<Source>,
     /// The reference to the source. This is the same as `source.sourceReference`.
     /// This is provided for backward compatibility since old clients do not understand the `source` attribute.
-    pub source_reference: u32,
+    pub source_reference: SourceReference,
 }
 
 /// Properties of a breakpoint or logpoint passed to the `setBreakpoints` request.
@@ -1983,7 +2434,7 @@ pub struct StackFrame {
     pub end_line: Option<u32>,
     /// An identifier for the stack frame. It must be unique across all threads.
     /// This id can be used to retrieve the scopes of the frame with the `scopes` request or to restart the execution of a stack frame.
-    pub id: u64,
+    pub id: FrameId,
     /// A memory reference for the current instruction pointer in this frame.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub instruction_pointer_reference: Option<String>,
@@ -2063,7 +2514,7 @@ pub struct StackTraceArguments {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub start_frame: Option<u64>,
     /// Retrieve the stacktrace for this thread.
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// Response to `stackTrace` request.
@@ -2107,7 +2558,7 @@ pub struct StepBackArguments {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub single_thread: Option<bool>,
     /// Specifies the thread for which to resume execution for one step backwards (of the given granularity).
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// Arguments for `stepIn` request.
@@ -2124,7 +2575,7 @@ pub struct StepInArguments {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub target_id: Option<u64>,
     /// Specifies the thread for which to resume execution for one step-into (of the given granularity).
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// A `StepInTarget` can be used in the `stepIn` request and determines into which single target the `stepIn` request should step.
@@ -2154,7 +2605,7 @@ pub struct StepInTarget {
 #[serde(rename_all = "camelCase")]
 pub struct StepInTargetsArguments {
     /// The stack frame for which to retrieve the possible step-in targets.
-    pub frame_id: u64,
+    pub frame_id: FrameId,
 }
 
 /// Response to `stepInTargets` request.
@@ -2176,7 +2627,7 @@ pub struct StepOutArguments {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub single_thread: Option<bool>,
     /// Specifies the thread for which to resume execution for one step-out (of the given granularity).
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// The granularity of one 'step' in the stepping requests `next`, `stepIn`, `stepOut`, and `stepBack`.
@@ -2211,7 +2662,7 @@ pub struct StoppedEvent {
     /// - Multiple source breakpoints get collapsed to the same instruction by the compiler/runtime.
     /// - Multiple function breakpoints with different function names map to the same location.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub hit_breakpoint_ids: Option<Vec<u64>>,
+    pub hit_breakpoint_ids: Option<Vec<BreakpointId>>,
     /// A value of true hints to the client that this event should not change the focus.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub preserve_focus_hint: Option<bool>,
@@ -2223,7 +2674,7 @@ pub struct StoppedEvent {
     pub text: Option<String>,
     /// The thread which was stopped.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub thread_id: Option<u64>,
+    pub thread_id: Option<ThreadId>,
 }
 
 /// The reason for the event.
@@ -2264,7 +2715,7 @@ pub struct TerminateArguments {
 pub struct TerminateThreadsArguments {
     /// Ids of threads to be terminated.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub thread_ids: Option<Vec<u64>>,
+    pub thread_ids: Option<Vec<ThreadId>>,
 }
 
 /// The event indicates that debugging of the debuggee has terminated. This does **not** mean that the debuggee itself has exited.
@@ -2282,7 +2733,7 @@ pub struct TerminatedEvent {
 #[serde(rename_all = "camelCase")]
 pub struct Thread {
     /// Unique identifier for the thread.
-    pub id: u64,
+    pub id: ThreadId,
     /// The name of the thread.
     pub name: String,
 }
@@ -2294,7 +2745,7 @@ pub struct ThreadEvent {
     /// The reason for the event.
     pub reason: ThreadEventReason,
     /// The identifier of the thread.
-    pub thread_id: u64,
+    pub thread_id: ThreadId,
 }
 
 /// The reason for the event.
@@ -2378,7 +2829,7 @@ pub struct Variable {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub value_location_reference: Option<u64>,
     /// If `variablesReference` is > 0, the variable is structured and its children can be retrieved by passing `variablesReference` to the `variables` request as long as execution remains suspended. See 'Lifetime of Object References' in the Overview section for details.
-    pub variables_reference: u32,
+    pub variables_reference: VariablesReference,
 }
 
 /// Properties of a variable that can be used to determine how to render the variable in the UI.
@@ -2493,7 +2944,7 @@ pub struct VariablesArguments {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub start: Option<u64>,
     /// The variable for which to retrieve its children. The `variablesReference` must have been obtained in the current suspended state. See 'Lifetime of Object References' in the Overview section for details.
-    pub variables_reference: u32,
+    pub variables_reference: VariablesReference,
 }
 
 /// Filter to limit the child variables to either named or indexed. If omitted, both types are fetched.
@@ -2526,7 +2977,7 @@ pub struct WriteMemoryArguments {
     pub memory_reference: String,
     /// Offset (in bytes) to be applied to the reference location before writing data. Can be negative.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub offset: Option<u32>,
+    pub offset: Option<i64>,
 }
 
 /// Response to `writeMemory` request.
@@ -2538,5 +2989,5 @@ pub struct WriteMemoryResponse {
     pub bytes_written: Option<u64>,
     /// Property that should be returned when `allowPartial` is true to indicate the offset of the first byte of data successfully written. Can be negative.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub offset: Option<u32>,
+    pub offset: Option<i64>,
 }
@@ -6,54 +6,108 @@ use indexmap::IndexMap;
 use serde_json::{Map, Value};
 
 mod case;
+mod overrides;
+mod positions;
 
 const SPEC_URL: &str = "https://microsoft.github.io/debug-adapter-protocol/specification";
 const DOC_CONT: &str = "///\n/// ";
+/// The `max_width` of `rustfmt.toml`, which generated code should respect.
+const MAX_WIDTH: usize = 120;
 
 fn main() {
     let GenResult {
         types,
         requests,
         events,
+        adapter,
+        handler,
+        positions,
     } = gen();
 
     write_file("types.rs", &types);
     write_file("request.rs", &requests);
     write_file("event.rs", &events);
+    write_file("server/adapter.rs", &adapter);
+    write_file("client/handler.rs", &handler);
+    write_file("position/impls.rs", &positions);
 }
 
 struct GenResult {
     types: String,
     requests: String,
     events: String,
+    adapter: String,
+    handler: String,
+    positions: String,
 }
 
 fn gen() -> GenResult {
-    let schema = {
+    let schema = schema();
+    let overrides = {
         let workspace_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
-        let contents = std::fs::read_to_string(workspace_dir.join("assets/debugAdapterProtocol.json")).unwrap();
-        serde_json::from_str(&contents).unwrap()
+        let contents = std::fs::read_to_string(workspace_dir.join("assets/overrides.toml")).unwrap();
+        overrides::Overrides::parse(&contents)
     };
-    let protocol_types = generate_protocol_types(&schema);
-    let types = write_types(&protocol_types);
-    let requests = write_requests(&protocol_types);
-    let events = write_events(&protocol_types);
+    let mut protocol_types = generate_protocol_types(&schema);
+    let newtypes = overrides.apply(&mut protocol_types);
+    let types = write_types(&protocol_types, &newtypes);
+    let Requests {
+        requests,
+        adapter,
+        handler,
+        any_requests,
+        any_responses,
+    } = write_requests(&protocol_types);
+    let (events, any_events) = write_events(&protocol_types);
+    let positions = positions::Positions::new(&protocol_types).write(&any_requests, &any_responses, &any_events);
 
     GenResult {
         types,
         requests,
         events,
+        adapter,
+        handler,
+        positions,
     }
 }
 
+fn schema() -> Value {
+    let workspace_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let contents = std::fs::read_to_string(workspace_dir.join("assets/debugAdapterProtocol.json")).unwrap();
+    serde_json::from_str(&contents).unwrap()
+}
+
 fn write_file(file: &str, contents: &str) {
     let contents = with_disclaimer(contents);
     std::fs::write(dst_path(file), contents).unwrap();
 }
 
+/// Returns the contents of a generated file, formatted by rustfmt.
 fn with_disclaimer(contents: &str) -> String {
     let disclaimer = "// This file is autogenerated. Do not edit by hand.\n// To regenerate from schema, run `cargo run -p generator`.\n\n";
-    disclaimer.to_owned() + contents
+    rustfmt(&(disclaimer.to_owned() + contents))
+}
+
+fn rustfmt(contents: &str) -> String {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let workspace_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let mut child = Command::new(std::env::var_os("RUSTFMT").unwrap_or("rustfmt".into()))
+        .args(["--edition", "2021", "--emit", "stdout", "--config-path"])
+        .arg(workspace_dir.join("rustfmt.toml"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("rustfmt is installed");
+    let mut stdin = child.stdin.take().unwrap();
+    // Written in another thread, as rustfmt may output before reading all.
+    let contents = contents.to_owned();
+    let writer = std::thread::spawn(move || stdin.write_all(contents.as_bytes()));
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap().unwrap();
+    assert!(output.status.success(), "rustfmt failed on the generated code");
+    String::from_utf8(output.stdout).unwrap()
 }
 
 fn dst_path(file: &str) -> PathBuf {
@@ -62,7 +116,6 @@ fn dst_path(file: &str) -> PathBuf {
 }
 
 const BLACKLISTED_TYPES: &[&str] = &[
-    "ProtocolMessage",
     "Request",
     "Event",
     "Response",
@@ -73,12 +126,119 @@ const BLACKLISTED_TYPES: &[&str] = &[
 
 const DERIVE_DEFAULT_TYPES: &[&str] = &["InitializeRequestArguments", "Capabilities", "Source", "Breakpoint"];
 
-fn write_requests(types: &[ProtocolType]) -> String {
+/// The types whose boolean fields are the capabilities required by requests,
+/// of the debug adapter and of the client.
+const CAPABILITY_TYPES: &[&str] = &["Capabilities", "InitializeRequestArguments"];
+
+/// Requests sent by the debug adapter to the client, also known as reverse
+/// requests.
+const REVERSE_REQUESTS: &[&str] = &["runInTerminal", "startDebugging"];
+
+/// Extracts the capability a request requires from its doc, which reads e.g.
+/// "Clients should only call this request if the corresponding capability
+/// `supportsStepBack` is true".
+fn required_capability(doc: &str) -> Option<&str> {
+    doc.split(['.', '\n'])
+        .filter(|sentence| sentence.contains("should only"))
+        .find_map(|sentence| {
+            let (_, rest) = sentence.split_once("capability `")?;
+            let (name, rest) = rest.split_once('`')?;
+            rest.starts_with(" is true").then_some(name)
+        })
+}
+
+/// The boolean fields of a type listing capabilities.
+fn capability_fields(o: &Object) -> impl Iterator<Item = &Field> {
+    o.fields
+        .iter()
+        .filter(|f| matches!(&f.ty, Type::Basic(ty) if ty == "bool"))
+}
+
+/// Writes the `supports` method of a type listing capabilities.
+fn write_supports(name: &str, o: &Object, dst: &mut Writer) {
+    dst.line(format!("impl {name} {{"));
+    dst.indented_doc("Whether the boolean capability of the given protocol name is true.\n\nUnset and unknown capabilities are not supported.");
+    dst.indented("pub fn supports(&self, capability: &str) -> bool {");
+    dst.indented("    match capability {");
+    for field in capability_fields(o) {
+        let value = if field.required { "" } else { " == Some(true)" };
+        dst.arm(
+            3,
+            format!("{:?}", field.name),
+            format!("self.{}{value}", to_rs_field_name(&field.name)),
+        );
+    }
+    dst.indented("        _ => false,");
+    dst.indented("    }");
+    dst.indented("}");
+    dst.line("}");
+    dst.finished_object();
+}
+
+/// Writes the `merge` and `diff` methods of `Capabilities`, which is sent in
+/// part by the `capabilities` event.
+fn write_capabilities_delta(o: &Object, dst: &mut Writer) {
+    assert!(o.fields.iter().all(|f| !f.required), "capabilities are all optional");
+    dst.line("impl Capabilities {");
+    dst.indented_doc("Updates the capabilities set in `delta`, e.g. the body of a `capabilities` event.");
+    dst.indented("pub fn merge(&mut self, delta: &Capabilities) {");
+    for field in &o.fields {
+        let name = to_rs_field_name(&field.name);
+        let value = match &field.ty {
+            Type::Basic(ty) if ty == "bool" => "*value",
+            _ => "value.clone()",
+        };
+        dst.indented(format!("    if let Some(value) = &delta.{name} {{"));
+        dst.indented(format!("        self.{name} = Some({value});"));
+        dst.indented("    }");
+    }
+    dst.indented("}");
+    dst.line("");
+    dst.indented_doc("Returns the protocol names of the capabilities whose value differs in `other`.");
+    dst.indented("///");
+    dst.indented_doc("An unset flag is unsupported, so it doesn't differ from `false`.");
+    dst.indented("pub fn diff(&self, other: &Capabilities) -> Vec<&'static str> {");
+    dst.indented("    let mut changed = Vec::new();");
+    for field in &o.fields {
+        let name = to_rs_field_name(&field.name);
+        let condition = match &field.ty {
+            Type::Basic(ty) if ty == "bool" => {
+                format!("self.{name}.unwrap_or(false) != other.{name}.unwrap_or(false)")
+            }
+            _ => format!("self.{name} != other.{name}"),
+        };
+        dst.indented(format!("    if {condition} {{"));
+        dst.indented(format!("        changed.push({:?});", field.name));
+        dst.indented("    }");
+    }
+    dst.indented("    changed");
+    dst.indented("}");
+    dst.line("}");
+    dst.finished_object();
+}
+
+/// The generated code of requests.
+struct Requests {
+    requests: String,
+    adapter: String,
+    handler: String,
+    /// The variants of `AnyRequest`, as pairs of variant and arguments type.
+    any_requests: Vec<(String, String)>,
+    /// The variants of `AnyResponse`, as pairs of variant and body type.
+    any_responses: Vec<(String, String)>,
+}
+
+/// Writes the request types, and the traits handling them on either side.
+fn write_requests(types: &[ProtocolType]) -> Requests {
     let mut writer = Writer::default();
     writer.line("#![allow(clippy::doc_lazy_continuation)]");
     writer.line("");
-    writer.line("pub use crate::IRequest;");
+    writer.line("pub use crate::{AdapterToClient, ClientToAdapter, IRequest};");
     writer.finished_object();
+    let mut all = Vec::new();
+    let mut responses = Vec::new();
+    let mut handlers = Vec::new();
+    let mut reverse_handlers = Vec::new();
     for ty in types {
         let Type::Object(o) = &ty.ty else {
             continue;
@@ -108,7 +268,27 @@ fn write_requests(types: &[ProtocolType]) -> String {
             Type::Object(_) => format!("crate::{response}"),
             _ => panic!("bad response body for {}", ty.name),
         };
-        writer.doc(o.doc.as_ref().unwrap());
+        let (direction, handlers) = if REVERSE_REQUESTS.contains(&command) {
+            ("AdapterToClient", &mut reverse_handlers)
+        } else {
+            ("ClientToAdapter", &mut handlers)
+        };
+        let doc = o.doc.as_ref().unwrap();
+        let capability = required_capability(doc);
+        if let Some(capability) = capability {
+            // Reverse requests depend on the capabilities of the client.
+            let owner = if direction == "AdapterToClient" {
+                "InitializeRequestArguments"
+            } else {
+                "Capabilities"
+            };
+            let owner = types.iter().find(|t| t.name == owner).unwrap().ty.as_object();
+            assert!(
+                capability_fields(owner).any(|f| f.name == capability),
+                "capability {capability} required by {request} is not a boolean field"
+            );
+        }
+        writer.doc(doc);
         writer.line(format!(
             "{DOC_CONT}See [{request}Request.]({SPEC_URL}#Requests_{request})"
         ));
@@ -116,18 +296,483 @@ fn write_requests(types: &[ProtocolType]) -> String {
         writer.finished_object();
         writer.line(format!("impl IRequest for {request} {{"));
         writer.indented(format!("const COMMAND: &'static str = {command:?};"));
+        // Requests are sent by the client unless stated otherwise.
+        if direction == "AdapterToClient" {
+            writer.indented(format!(
+                "const DIRECTION: crate::Direction = crate::Direction::{direction};"
+            ));
+        }
+        if let Some(capability) = capability {
+            writer.indented(format!(
+                "const REQUIRED_CAPABILITY: Option<&'static str> = Some({capability:?});"
+            ));
+        }
         writer.indented(format!("type Arguments = {arguments};"));
         writer.indented(format!("type Response = {response_body};"));
         writer.line("}");
         writer.finished_object();
+        writer.line(format!("impl {direction} for {request} {{}}"));
+        writer.finished_object();
+        handlers.push((
+            request.to_owned(),
+            command.to_owned(),
+            arguments.clone(),
+            response_body.clone(),
+        ));
+        responses.push((request.to_owned(), response_body.clone()));
+        all.push((request.to_owned(), arguments));
     }
-    writer.output
+    for command in REVERSE_REQUESTS {
+        assert!(
+            reverse_handlers.iter().any(|(_, c, _, _)| c == command),
+            "reverse request {command} is not in the schema"
+        );
+    }
+    write_any(&ANY_REQUEST, &all, &mut writer);
+    write_any_response(&responses, &mut writer);
+    write_direction(&all, &mut writer);
+    Requests {
+        requests: writer.output,
+        adapter: write_handler(&DEBUG_ADAPTER, &handlers),
+        handler: write_handler(&REVERSE_REQUEST_HANDLER, &reverse_handlers),
+        any_requests: all,
+        any_responses: responses,
+    }
+}
+
+/// Describes one of the generated traits handling requests.
+struct HandlerKind {
+    /// The name of the trait.
+    name: &'static str,
+    /// The doc of the trait.
+    doc: &'static str,
+}
+
+const DEBUG_ADAPTER: HandlerKind = HandlerKind {
+    name: "DebugAdapter",
+    doc: "A debug adapter, handling the requests of a client.",
+};
+
+const REVERSE_REQUEST_HANDLER: HandlerKind = HandlerKind {
+    name: "ReverseRequestHandler",
+    doc: "A handler of the reverse requests, sent by a debug adapter to the client.",
+};
+
+/// Writes a trait which has a method for each request, given as tuples of
+/// request name, command, arguments type and response body type, and the
+/// `Dispatcher` calling it.
+fn write_handler(kind: &HandlerKind, handlers: &[(String, String, String, String)]) -> String {
+    let HandlerKind { name, doc } = kind;
+    let method_name = |command: &str| match to_rs_field_name(command) {
+        name if name == "continue" => "r#continue".to_owned(),
+        name => name,
+    };
+
+    let mut dst = Writer::default();
+    dst.line("use std::future::Future;");
+    dst.line("");
+    dst.line("use crate::connection::{arguments, respond, Dispatch};");
+    dst.line("use crate::request::*;");
+    dst.line("use crate::RequestError;");
+    dst.finished_object();
+
+    dst.doc(format!("{doc}\n\nEach request is handled by a method returning the response body, or a [`RequestError`] to answer with an error response. Requests whose method is not implemented fail with [`RequestError::unsupported`]."));
+    dst.line(format!("pub trait {name}: Send {{"));
+    for (i, (request, command, arguments, response)) in handlers.iter().enumerate() {
+        if i > 0 {
+            dst.line("");
+        }
+        let method = method_name(command);
+        let params = if arguments == "()" {
+            "&mut self".to_owned()
+        } else {
+            format!("&mut self, args: {arguments}")
+        };
+        let ret = format!("impl Future<Output = Result<{response}, RequestError>> + Send");
+        dst.indented(format!("/// Handles the [`{request}`] request."));
+        let signature = format!("fn {method}({params}) -> {ret} {{");
+        if 4 + signature.len() <= MAX_WIDTH {
+            dst.indented(signature);
+        } else {
+            dst.indented(format!("fn {method}("));
+            for param in params.split(", ") {
+                dst.indented(format!("    {param},"));
+            }
+            dst.indented(format!(") -> {ret} {{"));
+        }
+        if arguments != "()" {
+            dst.indented("    let _ = args;");
+        }
+        dst.indented("    async { Err(RequestError::unsupported()) }");
+        dst.indented("}");
+    }
+    dst.line("}");
+    dst.finished_object();
+
+    dst.doc(format!("Dispatches requests to the methods of a [`{name}`]."));
+    dst.line("pub(crate) struct Dispatcher<H>(pub(crate) H);");
+    dst.finished_object();
+    dst.line(format!("impl<H: {name}> Dispatch for Dispatcher<H> {{"));
+    dst.indented("async fn dispatch(&mut self, request: &crate::Request) -> Result<serde_json::Value, RequestError> {");
+    dst.indented("    match request.command.as_str() {");
+    for (request, command, arguments, _) in handlers {
+        let method = method_name(command);
+        let pat = format!("{request}::COMMAND");
+        if arguments == "()" {
+            dst.arm(3, pat, format!("respond::<{request}>(self.0.{method}().await)"));
+        } else {
+            dst.indented(format!("        {pat} => {{"));
+            dst.indented(format!("            let args = arguments::<{request}>(request)?;"));
+            dst.indented(format!("            respond::<{request}>(self.0.{method}(args).await)"));
+            dst.indented("        }");
+        }
+    }
+    dst.indented("        _ => Err(RequestError::unsupported()),");
+    dst.indented("    }");
+    dst.indented("}");
+    dst.line("}");
+    dst.output
+}
+
+/// Writes the `direction` function, looking up the direction of a request by
+/// its command at runtime.
+fn write_direction(requests: &[(String, String)], dst: &mut Writer) {
+    dst.doc(
+        "Returns the direction of requests with the given command, or `None` if the command is unknown to this crate.",
+    );
+    dst.line("pub fn direction(command: &str) -> Option<crate::Direction> {");
+    dst.indented("match command {");
+    for (request, _) in requests {
+        dst.arm(2, format!("{request}::COMMAND"), format!("Some({request}::DIRECTION)"));
+    }
+    dst.indented("    _ => None,");
+    dst.indented("}");
+    dst.line("}");
+    dst.finished_object();
+}
+
+/// Writes the `AnyResponse` enum, which has a variant for each request, given
+/// as pairs of request name and response body type.
+fn write_any_response(responses: &[(String, String)], dst: &mut Writer) {
+    let unit = |ty: &str| ty == "()";
+
+    dst.doc("Any response, keyed by the command of the request it answers.\n\nThe body type of a response depends on the command of its request, so responses are decoded by [`AnyResponse::decode`] given that command.");
+    dst.line("#[derive(Debug, Clone)]");
+    dst.line("pub enum AnyResponse {");
+    for (request, body) in responses {
+        dst.indented(format!("/// See [`{request}`]."));
+        if unit(body) {
+            dst.indented(format!("{request},"));
+        } else {
+            dst.indented(format!("{request}({body}),"));
+        }
+    }
+    dst.indented("/// A failed response.");
+    dst.indented("Error {");
+    dst.indented("    /// The command requested.");
+    dst.indented("    command: String,");
+    dst.indented("    /// Contains the raw error in short form.");
+    dst.indented("    message: Option<String>,");
+    dst.indented("    /// Contains the structured error, if any.");
+    dst.indented("    body: crate::ErrorResponse,");
+    dst.indented("},");
+    dst.indented("/// A successful response to a command unknown to this crate.");
+    dst.indented("Other {");
+    dst.indented("    /// The command requested.");
+    dst.indented("    command: String,");
+    dst.indented("    /// Contains the request result.");
+    dst.indented("    body: serde_json::Value,");
+    dst.indented("},");
+    dst.line("}");
+    dst.finished_object();
+
+    dst.line("impl AnyResponse {");
+    dst.indented("/// Decodes a response to a request with the given command.");
+    dst.indented("///");
+    dst.indented("/// The command is usually looked up by `request_seq` from the table of pending");
+    dst.indented("/// requests, rather than trusted from the response itself.");
+    dst.indented("pub fn decode(command: &str, response: crate::Response) -> Result<AnyResponse, crate::Error> {");
+    dst.indented("    let body = response.body.unwrap_or_default();");
+    dst.indented("    if !response.success {");
+    dst.indented("        return Ok(AnyResponse::Error {");
+    dst.indented("            command: command.to_owned(),");
+    dst.indented("            message: response.message,");
+    dst.indented("            body: crate::body_from_value(&body)?,");
+    dst.indented("        });");
+    dst.indented("    }");
+    dst.indented("    Ok(match command {");
+    for (request, body) in responses {
+        let pat = format!("{request}::COMMAND");
+        if unit(body) {
+            dst.arm(3, pat, format!("AnyResponse::{request}"));
+        } else {
+            dst.arm(
+                3,
+                pat,
+                format!("AnyResponse::{request}(crate::body_from_value(&body)?)"),
+            );
+        }
+    }
+    dst.indented("        _ => AnyResponse::Other {");
+    dst.indented("            command: command.to_owned(),");
+    dst.indented("            body,");
+    dst.indented("        },");
+    dst.indented("    })");
+    dst.indented("}");
+    dst.finished_object();
+    dst.indented("/// Returns the command of the request answered.");
+    dst.indented("pub fn command(&self) -> &str {");
+    dst.indented("    match self {");
+    for (request, body) in responses {
+        let pat = if unit(body) { "" } else { "(_)" };
+        dst.arm(3, format!("AnyResponse::{request}{pat}"), format!("{request}::COMMAND"));
+    }
+    dst.arm(
+        3,
+        "AnyResponse::Error { command, .. } | AnyResponse::Other { command, .. }",
+        "command",
+    );
+    dst.indented("    }");
+    dst.indented("}");
+    dst.finished_object();
+    dst.indented("/// Converts into a response with the given sequence numbers.");
+    dst.indented("pub fn into_response(self, seq: i64, request_seq: i64) -> crate::Response {");
+    dst.indented("    match self {");
+    for (request, body) in responses {
+        if unit(body) {
+            let expr = format!("crate::Response::success::<{request}>(seq, request_seq, ())");
+            dst.arm(3, format!("AnyResponse::{request}"), expr);
+        } else {
+            let expr = format!("crate::Response::success::<{request}>(seq, request_seq, v)");
+            dst.arm(3, format!("AnyResponse::{request}(v)"), expr);
+        }
+    }
+    dst.indented("        AnyResponse::Error { command, message, body } => {");
+    dst.indented("            let body = body.error.is_some().then_some(body);");
+    dst.indented("            crate::Response::new(seq, request_seq, command, false, message, body)");
+    dst.indented("        }");
+    dst.indented("        AnyResponse::Other { command, body } => {");
+    dst.indented("            let body = Some(body).filter(|b| !b.is_null());");
+    dst.indented("            crate::Response::new(seq, request_seq, command, true, None, body)");
+    dst.indented("        }");
+    dst.indented("    }");
+    dst.indented("}");
+    dst.line("}");
+    dst.finished_object();
+}
+
+/// Describes one of the generated `Any*` enums.
+struct AnyKind {
+    /// The name of the enum, e.g. `AnyRequest`.
+    name: &'static str,
+    /// The message struct in the crate root, e.g. `Request`.
+    message: &'static str,
+    /// The tag field and the associated constant of the trait holding it.
+    tag: (&'static str, &'static str),
+    /// What the tag field holds, in docs, e.g. `type` for events.
+    tag_noun: &'static str,
+    /// The content field.
+    content: &'static str,
+    /// Docs of the tag and content fields of the fallback variant.
+    docs: (&'static str, &'static str),
+    /// The function deserializing the content.
+    parse: &'static str,
+}
+
+const ANY_REQUEST: AnyKind = AnyKind {
+    name: "AnyRequest",
+    message: "Request",
+    tag: ("command", "COMMAND"),
+    tag_noun: "command",
+    content: "arguments",
+    docs: (
+        "The command to execute.",
+        "Object containing arguments for the command.",
+    ),
+    parse: "arguments_from_value",
+};
+
+const ANY_EVENT: AnyKind = AnyKind {
+    name: "AnyEvent",
+    message: "Event",
+    tag: ("event", "EVENT"),
+    tag_noun: "type",
+    content: "body",
+    docs: ("Type of event.", "Event-specific information."),
+    parse: "body_from_value",
+};
+
+/// Writes an `Any*` enum, which has a variant for each of `items`, given as
+/// pairs of name and content type.
+fn write_any(kind: &AnyKind, items: &[(String, String)], dst: &mut Writer) {
+    let AnyKind {
+        name: any,
+        message,
+        tag: (tag, tag_const),
+        tag_noun,
+        content,
+        docs: (tag_doc, content_doc),
+        parse,
+    } = kind;
+    let lower = message.to_lowercase();
+    let article = if lower.starts_with(['a', 'e', 'i', 'o', 'u']) {
+        "an"
+    } else {
+        "a"
+    };
+    let capital_article = to_pascal_case(article);
+    let unit = |ty: &str| ty == "()";
+
+    dst.doc(format!(
+        "Any {lower}, keyed by its `{tag}` field.\n\n{message}s whose `{tag}` is unknown to this crate are kept as [`{any}::Other`]."
+    ));
+    dst.line("#[derive(Debug, Clone)]");
+    dst.line(format!("pub enum {any} {{"));
+    for (item, ty) in items {
+        dst.indented(format!("/// See [`{item}`]."));
+        if unit(ty) {
+            dst.indented(format!("{item},"));
+        } else {
+            dst.indented(format!("{item}({ty}),"));
+        }
+    }
+    dst.indented(format!(
+        "/// {capital_article} {lower} whose `{tag}` is unknown to this crate."
+    ));
+    dst.indented("Other {");
+    dst.indented(format!("    /// {tag_doc}"));
+    dst.indented(format!("    {tag}: String,"));
+    dst.indented(format!("    /// {content_doc}"));
+    dst.indented(format!("    {content}: serde_json::Value,"));
+    dst.indented("},");
+    dst.line("}");
+    dst.finished_object();
+
+    dst.line(format!("impl {any} {{"));
+    dst.indented(format!(
+        "/// Parses {article} {lower} from its {tag_noun} and {content}."
+    ));
+    dst.indented(format!(
+        "pub fn from_parts({tag}: String, {content}: serde_json::Value) -> Result<{any}, crate::Error> {{"
+    ));
+    dst.indented(format!("    Ok(match {tag}.as_str() {{"));
+    for (item, ty) in items {
+        let pat = format!("{item}::{tag_const}");
+        if unit(ty) {
+            dst.arm(3, pat, format!("{any}::{item}"));
+        } else {
+            dst.arm(3, pat, format!("{any}::{item}(crate::{parse}(&{content})?)"));
+        }
+    }
+    dst.arm(3, "_", format!("{any}::Other {{ {tag}, {content} }}"));
+    dst.indented("    })");
+    dst.indented("}");
+    dst.finished_object();
+    dst.indented(format!("/// Returns the {tag_noun} of the {lower}."));
+    dst.indented(format!("pub fn {tag}(&self) -> &str {{"));
+    dst.indented("    match self {");
+    for (item, ty) in items {
+        let pat = if unit(ty) { "" } else { "(_)" };
+        dst.arm(3, format!("{any}::{item}{pat}"), format!("{item}::{tag_const}"));
+    }
+    dst.arm(3, format!("{any}::Other {{ {tag}, .. }}"), *tag);
+    dst.indented("    }");
+    dst.indented("}");
+    dst.finished_object();
+    dst.indented(format!(
+        "/// Converts into {article} {lower} with the given sequence number."
+    ));
+    dst.indented(format!("pub fn into_{lower}(self, seq: i64) -> crate::{message} {{"));
+    dst.indented("    match self {");
+    for (item, ty) in items {
+        if unit(ty) {
+            let expr = format!("crate::{message}::from_typed::<{item}>(seq, ())");
+            dst.arm(3, format!("{any}::{item}"), expr);
+        } else {
+            let expr = format!("crate::{message}::from_typed::<{item}>(seq, v)");
+            dst.arm(3, format!("{any}::{item}(v)"), expr);
+        }
+    }
+    dst.arm(
+        3,
+        format!("{any}::Other {{ {tag}, {content} }}"),
+        format!("crate::{message}::new(seq, {tag}, {content})"),
+    );
+    dst.indented("    }");
+    dst.indented("}");
+    dst.line("}");
+    dst.finished_object();
+
+    dst.line(format!("impl TryFrom<crate::{message}> for {any} {{"));
+    dst.indented("type Error = crate::Error;");
+    dst.finished_object();
+    dst.indented(format!(
+        "fn try_from(m: crate::{message}) -> Result<Self, Self::Error> {{"
+    ));
+    dst.indented(format!("    {any}::from_parts(m.{tag}, m.{content})"));
+    dst.indented("}");
+    dst.line("}");
+    dst.finished_object();
+
+    dst.line(format!("impl From<{any}> for crate::{message} {{"));
+    dst.indented("/// Converts with a zero sequence number, which is expected to be assigned");
+    dst.indented("/// when the message is sent.");
+    dst.indented(format!("fn from(m: {any}) -> Self {{"));
+    dst.indented(format!("    m.into_{lower}(0)"));
+    dst.indented("}");
+    dst.line("}");
+    dst.finished_object();
+
+    dst.line(format!("impl serde::Serialize for {any} {{"));
+    dst.indented("fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {");
+    dst.indented(format!("    const TAG: (&str, &str) = (\"{tag}\", \"{content}\");"));
+    dst.indented("    match self {");
+    for (item, ty) in items {
+        if unit(ty) {
+            let expr = format!("crate::serialize_tagged::<_, ()>(serializer, TAG, {item}::{tag_const}, None)");
+            dst.arm(3, format!("{any}::{item}"), expr);
+        } else {
+            let expr = format!("crate::serialize_tagged(serializer, TAG, {item}::{tag_const}, Some(v))");
+            dst.arm(3, format!("{any}::{item}(v)"), expr);
+        }
+    }
+    dst.arm(
+        3,
+        format!("{any}::Other {{ {tag}, {content} }}"),
+        format!("crate::serialize_tagged(serializer, TAG, {tag}, Some({content}).filter(|v| !v.is_null()))"),
+    );
+    dst.indented("    }");
+    dst.indented("}");
+    dst.line("}");
+    dst.finished_object();
+
+    dst.line(format!("impl<'de> serde::Deserialize<'de> for {any} {{"));
+    dst.indented("fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {");
+    dst.indented("    #[derive(serde::Deserialize)]");
+    dst.indented("    struct Parts {");
+    dst.indented(format!("        {tag}: String,"));
+    dst.indented("        #[serde(default)]");
+    dst.indented(format!("        {content}: serde_json::Value,"));
+    dst.indented("    }");
+    dst.finished_object();
+    dst.indented(format!(
+        "    let Parts {{ {tag}, {content} }} = Parts::deserialize(deserializer)?;"
+    ));
+    dst.indented(format!(
+        "    {any}::from_parts({tag}, {content}).map_err(serde::de::Error::custom)"
+    ));
+    dst.indented("}");
+    dst.line("}");
+    dst.finished_object();
 }
 
-fn write_events(types: &[ProtocolType]) -> String {
+/// Writes the event types, and returns them with the variants of `AnyEvent`,
+/// as pairs of variant and body type.
+fn write_events(types: &[ProtocolType]) -> (String, Vec<(String, String)>) {
     let mut writer = Writer::default();
     writer.line("pub use crate::IEvent;");
     writer.finished_object();
+    let mut all = Vec::new();
     for ty in types {
         let Type::Object(o) = &ty.ty else {
             continue;
@@ -164,33 +809,28 @@ fn write_events(types: &[ProtocolType]) -> String {
         writer.indented(format!("type Body = {body};"));
         writer.line("}");
         writer.finished_object();
+        all.push((event.to_owned(), body));
     }
-    writer.output
+    write_any(&ANY_EVENT, &all, &mut writer);
+    (writer.output, all)
 }
 
-fn write_types(types: &[ProtocolType]) -> String {
+fn write_types(types: &[ProtocolType], newtypes: &[overrides::Newtype]) -> String {
     let mut writer = Writer::default();
     writer.line("use serde::{Deserialize, Serialize};");
     writer.code(CUSTOM_TYPES);
     writer.finished_object();
+    for newtype in newtypes {
+        newtype.write(&mut writer);
+    }
     for ty in types {
         if ty.name.ends_with("Request") {
             continue;
         }
         eprintln!("\x1b[1;32m Cooking\x1b[0m {} ...", ty.name);
-        if ty.name.ends_with("Response") {
-            let body = &ty.ty.as_object().find_field("body").unwrap().ty;
-            match body {
-                Type::Any => continue,
-                Type::Object(o) => {
-                    let mut o = o.clone();
-                    o.doc = o.doc.or(ty.ty.doc());
-                    o.write(&ty.name, &mut writer);
-                }
-                Type::Basic(_) => continue,
-                _ => panic!(),
-            }
-        } else if ty.name.ends_with("Event") {
+        if ty.name == "ProtocolMessage" {
+            write_protocol_message(ty.ty.as_object(), &mut writer);
+        } else if ty.name.ends_with("Response") || ty.name.ends_with("Event") {
             let body = &ty.ty.as_object().find_field("body").unwrap().ty;
             match body {
                 Type::Any => continue,
@@ -204,11 +844,58 @@ fn write_types(types: &[ProtocolType]) -> String {
             }
         } else {
             ty.write(&mut writer);
+            if CAPABILITY_TYPES.contains(&ty.name.as_str()) {
+                write_supports(&ty.name, ty.ty.as_object(), &mut writer);
+            }
+            if ty.name == "Capabilities" {
+                write_capabilities_delta(ty.ty.as_object(), &mut writer);
+            }
         }
     }
     writer.output
 }
 
+/// Writes the `ProtocolMessage` envelope, which is internally tagged on `type`
+/// and wraps the hand-written `Request`, `Response` and `Event` structs.
+fn write_protocol_message(o: &Object, dst: &mut Writer) {
+    let kinds = &o.find_field("type").unwrap().ty.as_enum().variants;
+    if let Some(doc) = &o.doc {
+        dst.doc(doc);
+    }
+    dst.line("#[derive(Debug, Clone, Deserialize, Serialize)]");
+    dst.line("#[serde(tag = \"type\", rename_all = \"camelCase\")]");
+    dst.line("pub enum ProtocolMessage {");
+    for kind in kinds {
+        let variant = to_pascal_case(kind);
+        dst.indented(format!("{variant}(crate::{variant}),"));
+    }
+    dst.line("}");
+    dst.finished_object();
+
+    dst.line("impl ProtocolMessage {");
+    dst.indented("/// Sequence number of the message.");
+    dst.indented("pub fn seq(&self) -> i64 {");
+    dst.indented("    match self {");
+    for kind in kinds {
+        let variant = to_pascal_case(kind);
+        dst.indented(format!("        ProtocolMessage::{variant}(m) => m.seq,"));
+    }
+    dst.indented("    }");
+    dst.indented("}");
+    dst.line("}");
+    dst.finished_object();
+
+    for kind in kinds {
+        let variant = to_pascal_case(kind);
+        dst.line(format!("impl From<crate::{variant}> for ProtocolMessage {{"));
+        dst.indented(format!("fn from(m: crate::{variant}) -> Self {{"));
+        dst.indented(format!("    ProtocolMessage::{variant}(m)"));
+        dst.indented("}");
+        dst.line("}");
+        dst.finished_object();
+    }
+}
+
 fn generate_protocol_types(schema: &Value) -> Vec<ProtocolType> {
     let defs = schema.get("definitions").unwrap().as_object().unwrap();
     let mut types = Vec::new();
@@ -219,7 +906,7 @@ fn generate_protocol_types(schema: &Value) -> Vec<ProtocolType> {
         eprintln!("\x1b[1;32mChecking\x1b[0m {name} ...");
         types.push(ProtocolType {
             name: name.to_owned(),
-            ty: translate_type(defs, Some(name), def),
+            ty: translate_type(defs, def),
         });
     }
     types
@@ -233,7 +920,7 @@ fn translate_all_of(defs: &Map<String, Value>, def: &Value) -> Object {
     for subobject in members {
         let subobject = if let Some(r) = subobject.get("$ref") {
             let r = r.as_str().unwrap().strip_prefix("#/definitions/").unwrap();
-            match translate_type(defs, None, &defs[r]) {
+            match translate_type(defs, &defs[r]) {
                 Type::Object(o) => o,
                 _ => todo!(),
             }
@@ -283,12 +970,13 @@ fn generate_field(defs: &Map<String, Value>, name: &str, def: &Value, required:
     Field {
         doc: def.get("description").map(|x| x.as_str().unwrap().to_owned()),
         name: name.to_owned(),
-        ty: translate_type(defs, Some(name), def),
+        ty: translate_type(defs, def),
         required,
+        with: None,
     }
 }
 
-fn translate_type(defs: &Map<String, Value>, name: Option<&str>, t: &Value) -> Type {
+fn translate_type(defs: &Map<String, Value>, t: &Value) -> Type {
     if is_any(t) {
         return Type::Any;
     }
@@ -310,23 +998,10 @@ fn translate_type(defs: &Map<String, Value>, name: Option<&str>, t: &Value) -> T
     });
     match ty {
         "integer" | "number" => {
-            const U32_FIELDS: &[&str] = &[
-                "startLine",
-                "endLine",
-                "startColumn",
-                "endColumn",
-                "line",
-                "column",
-                "offset",
-                "indexedVariables",
-                "namedVariables",
-                "variablesReference",
-                "sourceReference",
-                "processId",
-                "shellProcessId",
-            ];
-            if name.is_some_and(|name| U32_FIELDS.contains(&name)) {
-                return "u32".into();
+            // Other integer widths are set in `assets/overrides.toml`.
+            let description = t.get("description").and_then(|x| x.as_str()).unwrap_or_default();
+            if description.contains("be negative") {
+                return "i64".into();
             }
 
             "u64".into()
@@ -377,7 +1052,7 @@ fn translate_type(defs: &Map<String, Value>, name: Option<&str>, t: &Value) -> T
             }
         }
         "array" => {
-            let item = translate_type(defs, name, t.get("items").unwrap());
+            let item = translate_type(defs, t.get("items").unwrap());
             Type::Vec(Box::new(item))
         }
         other => other.into(),
@@ -418,6 +1093,29 @@ fn to_rs_field_name(raw: &str) -> String {
     result[1..].to_owned()
 }
 
+/// The name of a field before `words` split acronyms, if it differs from the
+/// current one.
+///
+/// Such fields keep deprecated accessors under their former name.
+fn legacy_field_name(raw: &str) -> Option<String> {
+    let mut words = Vec::new();
+    let mut last = String::new();
+    let mut prev_upper = false;
+    for c in raw.chars() {
+        if c == '_' || c == ' ' || (c.is_uppercase() && !prev_upper) {
+            words.push(std::mem::take(&mut last));
+        }
+        if c != '_' && c != ' ' {
+            last.extend(c.to_lowercase());
+        }
+        prev_upper = c.is_uppercase();
+    }
+    words.push(last);
+    words.retain(|x| !x.is_empty());
+    let legacy = words.join("_");
+    (legacy != to_rs_field_name(raw) && raw != "type").then_some(legacy)
+}
+
 fn to_pascal_case(raw: &str) -> String {
     words(raw)
         .into_iter()
@@ -431,20 +1129,28 @@ fn to_pascal_case(raw: &str) -> String {
         .collect()
 }
 
+/// Splits a camelCase, PascalCase or snake_case name into lowercase words.
+///
+/// A run of capitals is an acronym, whose last capital starts the next word if
+/// it is followed by a lowercase letter: `supportsANSIStyling` is split into
+/// `supports`, `ansi` and `styling`. Digits belong to the word before them.
 fn words(raw: &str) -> Vec<String> {
+    let chars: Vec<char> = raw.chars().collect();
     let mut result = Vec::new();
     let mut last = String::new();
-    let mut prev_upper = false;
-    for c in raw.chars() {
+    for (i, &c) in chars.iter().enumerate() {
         if c == '_' || c == ' ' {
             result.push(std::mem::take(&mut last));
-        } else if c.is_uppercase() && !prev_upper {
-            result.push(std::mem::take(&mut last));
-            last.extend(c.to_lowercase());
-        } else {
-            last.extend(c.to_lowercase());
+            continue;
         }
-        prev_upper = c.is_uppercase();
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if !prev.is_uppercase() || next_lower {
+                result.push(std::mem::take(&mut last));
+            }
+        }
+        last.extend(c.to_lowercase());
     }
     result.push(last);
     result.retain(|x| !x.is_empty());
@@ -478,6 +1184,21 @@ impl Writer {
         self.output.push('\n');
     }
 
+    /// Writes a match arm indented by `depth` levels, moving the expression into
+    /// a block if the arm does not fit in a line, as rustfmt does.
+    fn arm(&mut self, depth: usize, pat: impl AsRef<str>, expr: impl AsRef<str>) {
+        let (pat, expr) = (pat.as_ref(), expr.as_ref());
+        let pad = "    ".repeat(depth);
+        let line = format!("{pad}{pat} => {expr},");
+        if line.len() <= MAX_WIDTH {
+            self.line(line);
+        } else {
+            self.line(format!("{pad}{pat} => {{"));
+            self.line(format!("{pad}    {expr}"));
+            self.line(format!("{pad}}}"));
+        }
+    }
+
     fn finished_object(&mut self) {
         self.finished_object = true;
     }
@@ -585,6 +1306,8 @@ struct Field {
     name: String,
     ty: Type,
     required: bool,
+    /// The serde adapter module of the field.
+    with: Option<String>,
 }
 
 impl ProtocolType {
@@ -613,32 +1336,44 @@ impl Object {
 
         dst.line(format!("#[derive({derivings})]"));
         let mut pending = Vec::new();
+        let mut legacy = Vec::new();
         if self.fields.is_empty() {
             dst.line(format!("pub struct {name};"));
         } else {
             dst.line("#[serde(rename_all = \"camelCase\")]");
             dst.line(format!("pub struct {name} {{"));
             for field in &self.fields {
-                let inline_name = format!("{}{}", name, to_pascal_case(&field.name));
-                let ty = field.ty.stringify(inline_name, &mut pending);
+                let ty = field.ty.stringify(inline_name(name, &field.name), &mut pending);
                 if let Some(doc) = &field.doc {
                     dst.indented_doc(doc);
                 }
                 let clean_name = to_rs_field_name(&field.name);
-                let camel_back = case::RenameRule::CamelCase.apply_to_field(&clean_name);
-                if field.name != camel_back {
+                if needs_rename(&field.name) {
                     dst.indented(format!("#[serde(rename = \"{}\")]", field.name));
                 }
+                let with = field.with.as_ref().map(|with| format!("with = \"{with}\""));
                 if field.required {
+                    if let Some(with) = with {
+                        dst.indented(format!("#[serde({with})]"));
+                    }
                     dst.indented(format!("pub {clean_name}: {ty},"));
                 } else {
-                    dst.indented("#[serde(default, skip_serializing_if = \"Option::is_none\")]");
+                    let attrs = ["default", "skip_serializing_if = \"Option::is_none\""].map(str::to_owned);
+                    let attrs: Vec<_> = attrs.into_iter().chain(with).collect();
+                    dst.indented(format!("#[serde({})]", attrs.join(", ")));
                     dst.indented(format!("pub {clean_name}: Option<{ty}>,"));
                 }
+                if let Some(legacy_name) = legacy_field_name(&field.name) {
+                    let ty = if field.required { ty } else { format!("Option<{ty}>") };
+                    legacy.push((legacy_name, clean_name, ty));
+                }
             }
             dst.line("}");
         }
         dst.finished_object();
+        if !legacy.is_empty() {
+            write_legacy_accessors(name, &legacy, dst);
+        }
         for p in pending {
             p.write(dst);
         }
@@ -649,6 +1384,34 @@ impl Object {
     }
 }
 
+/// Whether a field needs a `#[serde(rename)]`, because `rename_all =
+/// "camelCase"` doesn't give back its protocol name from its Rust name.
+fn needs_rename(raw: &str) -> bool {
+    raw != case::RenameRule::CamelCase.apply_to_field(&to_rs_field_name(raw))
+}
+
+/// Writes deprecated accessors of fields under their former names, given as
+/// triples of former name, name and type.
+fn write_legacy_accessors(name: &str, fields: &[(String, String, String)], dst: &mut Writer) {
+    dst.line(format!("impl {name} {{"));
+    for (i, (legacy, field, ty)) in fields.iter().enumerate() {
+        if i > 0 {
+            dst.line("");
+        }
+        dst.indented(format!("#[deprecated(note = \"renamed to `{field}`\")]"));
+        dst.indented(format!("pub fn {legacy}(&self) -> &{ty} {{"));
+        dst.indented(format!("    &self.{field}"));
+        dst.indented("}");
+        dst.line("");
+        dst.indented(format!("#[deprecated(note = \"renamed to `{field}`\")]"));
+        dst.indented(format!("pub fn {legacy}_mut(&mut self) -> &mut {ty} {{"));
+        dst.indented(format!("    &mut self.{field}"));
+        dst.indented("}");
+    }
+    dst.line("}");
+    dst.finished_object();
+}
+
 impl Enum {
     fn write(&self, name: &str, dst: &mut Writer) {
         if let Some(doc) = &self.doc {
@@ -697,6 +1460,11 @@ impl Enum {
     }
 }
 
+/// The name of the type generated for the inline enum or object of a field.
+fn inline_name(parent: &str, field: &str) -> String {
+    format!("{parent}{}", to_pascal_case(field))
+}
+
 impl Type {
     fn stringify(&self, inline_name: String, pending: &mut Vec<PendingInline>) -> String {
         match self {
@@ -770,6 +1538,8 @@ pub struct RestartArguments {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use super::*;
 
     #[test]
@@ -783,11 +1553,125 @@ mod tests {
             types,
             requests,
             events,
+            adapter,
+            handler,
+            positions,
         } = gen();
 
         check_file("types.rs", &types);
         check_file("request.rs", &requests);
         check_file("event.rs", &events);
+        check_file("server/adapter.rs", &adapter);
+        check_file("client/handler.rs", &handler);
+        check_file("position/impls.rs", &positions);
+    }
+
+    #[test]
+    fn required_capability_from_doc() {
+        let doc = "Restarts a stack frame.\nClients should only call this request if the corresponding capability `supportsRestartFrame` is true.";
+        assert_eq!(required_capability(doc), Some("supportsRestartFrame"));
+        let doc = "This request should only be sent if the corresponding client capability `supportsStartDebuggingRequest` is true.";
+        assert_eq!(required_capability(doc), Some("supportsStartDebuggingRequest"));
+        let doc = "Clients should only call this request if the corresponding capability `exceptionBreakpointFilters` returns one or more filters.";
+        assert_eq!(required_capability(doc), None);
+        let doc = "The `terminateDebuggee` argument is only supported if the corresponding capability `supportTerminateDebuggee` is true.";
+        assert_eq!(required_capability(doc), None);
+    }
+
+    #[test]
+    fn property_names_round_trip() {
+        fn property_names<'a>(value: &'a Value, names: &mut BTreeSet<&'a str>) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::Object(properties)) = map.get("properties") {
+                        names.extend(properties.keys().map(String::as_str));
+                    }
+                    map.values().for_each(|value| property_names(value, names));
+                }
+                Value::Array(values) => values.iter().for_each(|value| property_names(value, names)),
+                _ => {}
+            }
+        }
+        /// Collects the names the fields of the emitted structs are serialized
+        /// as, from their Rust names and `rename` attributes.
+        fn serde_names(code: &str, names: &mut BTreeSet<String>) {
+            let mut rename_all = false;
+            let mut in_struct = false;
+            let mut rename = None;
+            for line in code.lines() {
+                if line.starts_with("pub struct ") && line.ends_with('{') {
+                    in_struct = rename_all;
+                } else if line == "}" {
+                    in_struct = false;
+                } else if let Some(name) = line.strip_prefix("    #[serde(rename = \"") {
+                    rename = Some(name.trim_end_matches("\")]").to_owned());
+                } else if let Some(field) = line.strip_prefix("    pub ").filter(|_| in_struct) {
+                    let (field, _) = field.split_once(':').unwrap();
+                    let name = rename.take();
+                    names.insert(name.unwrap_or_else(|| case::RenameRule::CamelCase.apply_to_field(field)));
+                }
+                rename_all = line == "#[serde(rename_all = \"camelCase\")]";
+                if !line.trim_start().starts_with("#[") && !line.trim_start().starts_with("///") {
+                    rename = None;
+                }
+            }
+        }
+
+        let schema = schema();
+        let mut names = BTreeSet::new();
+        property_names(&schema, &mut names);
+        for name in &names {
+            let rs_name = to_rs_field_name(name);
+            let valid = rs_name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+            assert!(
+                valid && !rs_name.starts_with('_'),
+                "{name} is not snake case: {rs_name}"
+            );
+        }
+
+        // Every property is emitted under a name serde maps back to it.
+        let GenResult {
+            types,
+            requests,
+            events,
+            ..
+        } = gen();
+        let mut emitted = BTreeSet::new();
+        for code in [&types, &requests, &events] {
+            serde_names(code, &mut emitted);
+        }
+        // Properties of the base messages, written by hand, and of the raw
+        // `launch` and `attach` arguments.
+        let unemitted = [
+            "__restart",
+            "arguments",
+            "body",
+            "command",
+            "event",
+            "noDebug",
+            "request_seq",
+            "seq",
+            "success",
+        ];
+        let names: BTreeSet<_> = names
+            .into_iter()
+            .filter(|name| !unemitted.contains(name))
+            .map(str::to_owned)
+            .collect();
+        let missing: Vec<_> = names.difference(&emitted).collect();
+        let unknown: Vec<_> = emitted.difference(&names).collect();
+        assert!(missing.is_empty(), "properties without a field: {missing:?}");
+        assert!(unknown.is_empty(), "fields without a property: {unknown:?}");
+        assert_eq!(to_rs_field_name("supportsANSIStyling"), "supports_ansi_styling");
+        assert_eq!(to_rs_field_name("adapterID"), "adapter_id");
+        assert_eq!(to_pascal_case("SHA256"), "Sha256");
+        assert_eq!(
+            legacy_field_name("supportsANSIStyling").as_deref(),
+            Some("supports_ansistyling")
+        );
+        assert_eq!(legacy_field_name("adapterID"), None);
     }
 
     #[cfg(not(unix))]
@@ -0,0 +1,316 @@
+//! Overrides of the Rust types of generated fields, read from
+//! `assets/overrides.toml`. See that file for the format.
+
+use indexmap::IndexMap;
+
+use crate::{inline_name, Object, ProtocolType, Type, Writer};
+
+/// The override of a single field.
+struct FieldOverride {
+    /// The Rust type replacing the translated one.
+    ty: Option<String>,
+    /// The newtype to wrap the type in.
+    newtype: Option<String>,
+    /// The serde adapter module of the field.
+    with: Option<String>,
+    /// Whether the override matched a field.
+    used: bool,
+}
+
+/// A newtype declared in the overrides.
+pub struct Newtype {
+    name: String,
+    doc: String,
+    /// The wrapped type, known once a field uses the newtype.
+    inner: Option<String>,
+}
+
+pub struct Overrides {
+    fields: IndexMap<String, FieldOverride>,
+    newtypes: IndexMap<String, Newtype>,
+}
+
+impl Overrides {
+    pub fn parse(contents: &str) -> Overrides {
+        let table: toml::Table = contents
+            .parse()
+            .unwrap_or_else(|err| panic!("invalid overrides: {err}"));
+        let mut fields = IndexMap::new();
+        let mut newtypes = IndexMap::new();
+        for (section, entries) in table {
+            let toml::Value::Table(entries) = entries else {
+                panic!("section {section} of overrides is not a table");
+            };
+            match section.as_str() {
+                "fields" => {
+                    for (key, entry) in entries {
+                        assert!(key.contains('.'), "override {key} is not of the form `Type.field`");
+                        let mut entry = Entry::new(&key, entry);
+                        let field = FieldOverride {
+                            ty: entry.take("type"),
+                            newtype: entry.take("newtype"),
+                            with: entry.take("with"),
+                            used: false,
+                        };
+                        entry.finish();
+                        assert!(
+                            field.ty.is_some() || field.newtype.is_some() || field.with.is_some(),
+                            "override {key} is empty"
+                        );
+                        fields.insert(key, field);
+                    }
+                }
+                "newtypes" => {
+                    for (name, entry) in entries {
+                        let mut entry = Entry::new(&name, entry);
+                        let doc = entry.take("doc").unwrap_or_else(|| panic!("newtype {name} has no doc"));
+                        entry.finish();
+                        newtypes.insert(name.clone(), Newtype { name, doc, inner: None });
+                    }
+                }
+                _ => panic!("unknown section {section} of overrides"),
+            }
+        }
+        Overrides { fields, newtypes }
+    }
+
+    /// Applies the overrides to the fields of `types`, and returns the
+    /// newtypes to generate.
+    ///
+    /// Panics if an override matches no field, so that stale overrides are
+    /// noticed when the schema changes.
+    pub fn apply(mut self, types: &mut [ProtocolType]) -> Vec<Newtype> {
+        for ty in types.iter_mut() {
+            let Type::Object(o) = &mut ty.ty else {
+                continue;
+            };
+            // Mirrors `write_types`, which skips requests and names the body of
+            // responses and events after them.
+            if ty.name.ends_with("Request") {
+                continue;
+            }
+            if ty.name.ends_with("Response") || ty.name.ends_with("Event") {
+                if let Some(Type::Object(body)) = o.fields.iter_mut().find(|f| f.name == "body").map(|f| &mut f.ty) {
+                    self.apply_object(&ty.name, body);
+                }
+            } else {
+                self.apply_object(&ty.name, o);
+            }
+        }
+
+        let stale: Vec<_> = self
+            .fields
+            .iter()
+            .filter(|(_, f)| !f.used)
+            .map(|(key, _)| key)
+            .collect();
+        assert!(stale.is_empty(), "overrides match no field: {stale:?}");
+        let unused: Vec<_> = self
+            .newtypes
+            .values()
+            .filter(|n| n.inner.is_none())
+            .map(|n| &n.name)
+            .collect();
+        assert!(unused.is_empty(), "newtypes are not used by any field: {unused:?}");
+        self.newtypes.into_values().collect()
+    }
+
+    fn apply_object(&mut self, name: &str, o: &mut Object) {
+        for field in &mut o.fields {
+            let key = format!("{name}.{}", field.name);
+            if let Some(entry) = self.fields.get_mut(&key) {
+                entry.used = true;
+                let basic = basic_mut(&mut field.ty)
+                    .unwrap_or_else(|| panic!("cannot override {key}, which is not a basic type"));
+                if let Some(ty) = &entry.ty {
+                    *basic = ty.clone();
+                }
+                if let Some(newtype) = &entry.newtype {
+                    let decl = self
+                        .newtypes
+                        .get_mut(newtype)
+                        .unwrap_or_else(|| panic!("newtype {newtype} of {key} is not declared"));
+                    match &decl.inner {
+                        Some(inner) => assert!(inner == basic, "newtype {newtype} wraps both {inner} and {basic}"),
+                        None => decl.inner = Some(basic.clone()),
+                    }
+                    *basic = newtype.clone();
+                }
+                field.with = entry.with.clone();
+            }
+            if let Some(inline) = inline_object(&mut field.ty) {
+                self.apply_object(&inline_name(name, &field.name), inline);
+            }
+        }
+    }
+}
+
+impl Newtype {
+    pub fn write(&self, dst: &mut Writer) {
+        let inner = self.inner.as_ref().unwrap();
+        dst.doc(&self.doc);
+        if inner == "String" {
+            dst.line("#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]");
+        } else {
+            dst.line(
+                "#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]",
+            );
+        }
+        dst.line("#[serde(transparent)]");
+        dst.line(format!("pub struct {}(pub {inner});", self.name));
+        dst.finished_object();
+    }
+}
+
+/// The string values of an entry, which must all be consumed.
+struct Entry<'a> {
+    key: &'a str,
+    values: toml::Table,
+}
+
+impl<'a> Entry<'a> {
+    fn new(key: &'a str, value: toml::Value) -> Self {
+        let toml::Value::Table(values) = value else {
+            panic!("override {key} is not a table");
+        };
+        Entry { key, values }
+    }
+
+    fn take(&mut self, name: &str) -> Option<String> {
+        let value = self.values.remove(name)?;
+        match value {
+            toml::Value::String(value) => Some(value),
+            _ => panic!("{name} of override {} is not a string", self.key),
+        }
+    }
+
+    fn finish(self) {
+        let unknown: Vec<_> = self.values.keys().collect();
+        assert!(unknown.is_empty(), "unknown keys of override {}: {unknown:?}", self.key);
+    }
+}
+
+fn basic_mut(ty: &mut Type) -> Option<&mut String> {
+    match ty {
+        Type::Basic(basic) => Some(basic),
+        Type::Vec(item) | Type::Option(item) => basic_mut(item),
+        _ => None,
+    }
+}
+
+fn inline_object(ty: &mut Type) -> Option<&mut Object> {
+    match ty {
+        Type::Object(o) => Some(o),
+        Type::Vec(item) | Type::Option(item) => inline_object(item),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Field;
+
+    fn types() -> Vec<ProtocolType> {
+        let field = |name: &str, ty: Type| Field {
+            doc: None,
+            name: name.to_owned(),
+            ty,
+            required: true,
+            with: None,
+        };
+        let thread = Object {
+            doc: None,
+            fields: vec![field("id", "u64".into()), field("name", "String".into())],
+        };
+        let body = Object {
+            doc: None,
+            fields: vec![field("threads", Type::Vec(Box::new(Type::Object(thread))))],
+        };
+        let response = Object {
+            doc: None,
+            fields: vec![field("body", Type::Object(body))],
+        };
+        vec![ProtocolType {
+            name: "ThreadsResponse".to_owned(),
+            ty: Type::Object(response),
+        }]
+    }
+
+    fn thread_fields(types: &[ProtocolType]) -> Vec<(String, Option<String>)> {
+        let Type::Object(response) = &types[0].ty else { panic!() };
+        let Type::Object(body) = &response.fields[0].ty else {
+            panic!()
+        };
+        let Type::Vec(item) = &body.fields[0].ty else { panic!() };
+        let Type::Object(thread) = &**item else { panic!() };
+        thread
+            .fields
+            .iter()
+            .map(|f| match &f.ty {
+                Type::Basic(ty) => (ty.clone(), f.with.clone()),
+                _ => panic!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn apply_to_inline_object() {
+        let overrides = Overrides::parse(
+            r#"
+            [fields]
+            "ThreadsResponseThreads.id" = { type = "u32", newtype = "ThreadId" }
+            "ThreadsResponseThreads.name" = { with = "crate::lossy" }
+
+            [newtypes.ThreadId]
+            doc = "A thread id."
+            "#,
+        );
+        let mut types = types();
+        let newtypes = overrides.apply(&mut types);
+        assert_eq!(
+            thread_fields(&types),
+            [
+                ("ThreadId".to_owned(), None),
+                ("String".to_owned(), Some("crate::lossy".to_owned()))
+            ]
+        );
+        assert_eq!(newtypes.len(), 1);
+        assert_eq!(newtypes[0].inner.as_deref(), Some("u32"));
+    }
+
+    #[test]
+    #[should_panic(expected = "overrides match no field: [\"Thread.id\"]")]
+    fn stale_field() {
+        let overrides = Overrides::parse(
+            r#"
+            [fields]
+            "Thread.id" = { type = "u32" }
+            "#,
+        );
+        overrides.apply(&mut types());
+    }
+
+    #[test]
+    #[should_panic(expected = "newtypes are not used by any field: [\"ThreadId\"]")]
+    fn stale_newtype() {
+        let overrides = Overrides::parse(
+            r#"
+            [newtypes.ThreadId]
+            doc = "A thread id."
+            "#,
+        );
+        overrides.apply(&mut types());
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown keys of override ThreadsResponseThreads.id: [\"typ\"]")]
+    fn unknown_key() {
+        Overrides::parse(
+            r#"
+            [fields]
+            "ThreadsResponseThreads.id" = { typ = "u32" }
+            "#,
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! Implementations of the `Positions` trait of `dapts::position`, which
+//! shifts the line and column numbers of messages between 0- and 1-based.
+//!
+//! A field holds line numbers if it is named `line`, `endLine` or `lines`, or
+//! if its doc refers to `linesStartAt1`, and column numbers likewise. Types
+//! are positioned if they have such fields, or fields of positioned types.
+//!
+//! Numbers which are 0 without a source, like those of `StackFrame`, aren't
+//! shifted then, so that they stay 0 in any base.
+
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+
+use crate::{inline_name, to_rs_field_name, Field, Object, ProtocolType, Type, Writer};
+
+/// The generated structs, by name, and which of them are positioned.
+pub struct Positions<'a> {
+    structs: IndexMap<String, &'a Object>,
+    positioned: HashSet<String>,
+}
+
+impl<'a> Positions<'a> {
+    pub fn new(types: &'a [ProtocolType]) -> Positions<'a> {
+        let mut structs = IndexMap::new();
+        for ty in types {
+            let Type::Object(o) = &ty.ty else {
+                continue;
+            };
+            // Mirrors `write_types`, which skips requests and names the body of
+            // responses and events after them.
+            if ty.name.ends_with("Request") {
+                continue;
+            }
+            if ty.name.ends_with("Response") || ty.name.ends_with("Event") {
+                if let Some(Type::Object(body)) = o.find_field("body").map(|f| &f.ty) {
+                    add_struct(&mut structs, &ty.name, body);
+                }
+            } else {
+                add_struct(&mut structs, &ty.name, o);
+            }
+        }
+
+        let mut positioned: HashSet<String> = structs
+            .iter()
+            .filter(|(_, o)| o.fields.iter().any(|f| numbers(f).is_some()))
+            .map(|(name, _)| name.clone())
+            .collect();
+        loop {
+            let more: Vec<_> = structs
+                .iter()
+                .filter(|(name, o)| {
+                    !positioned.contains(*name)
+                        && o.fields
+                            .iter()
+                            .any(|f| nested(name, f).is_some_and(|n| positioned.contains(&n)))
+                })
+                .map(|(name, _)| name.clone())
+                .collect();
+            if more.is_empty() {
+                break;
+            }
+            positioned.extend(more);
+        }
+        Positions { structs, positioned }
+    }
+
+    /// Whether `ty`, a type name, is positioned.
+    fn is_positioned(&self, ty: &str) -> bool {
+        self.positioned.contains(ty.strip_prefix("crate::").unwrap_or(ty))
+    }
+
+    /// Writes the implementations for the positioned structs, and for the
+    /// `Any*` enums given as pairs of variant and content type.
+    pub fn write(
+        &self,
+        requests: &[(String, String)],
+        responses: &[(String, String)],
+        events: &[(String, String)],
+    ) -> String {
+        let mut dst = Writer::default();
+        dst.line("use super::{Positions, Shift};");
+        dst.line("use crate::event::AnyEvent;");
+        dst.line("use crate::request::{AnyRequest, AnyResponse};");
+        dst.finished_object();
+
+        for (name, o) in &self.structs {
+            if !self.positioned.contains(name) {
+                continue;
+            }
+            dst.line(format!("impl Positions for crate::{name} {{"));
+            dst.indented("fn shift_positions(&mut self, shift: Shift) {");
+            for field in &o.fields {
+                let rs_name = to_rs_field_name(&field.name);
+                if let Some(numbers) = numbers(field) {
+                    let method = numbers.method();
+                    let stmt = match layers(field) {
+                        0 => format!("shift.{method}(&mut self.{rs_name});"),
+                        1 => format!("self.{rs_name}.iter_mut().for_each(|{method}| shift.{method}({method}));"),
+                        _ => format!(
+                            "self.{rs_name}.iter_mut().flatten().for_each(|{method}| shift.{method}({method}));"
+                        ),
+                    };
+                    if absent_without_source(field) {
+                        dst.indented("    if self.source.is_some() {");
+                        dst.indented(format!("        {stmt}"));
+                        dst.indented("    }");
+                    } else {
+                        dst.indented(format!("    {stmt}"));
+                    }
+                } else if nested(name, field).is_some_and(|n| self.positioned.contains(&n)) {
+                    dst.indented(format!("    self.{rs_name}.shift_positions(shift);"));
+                }
+            }
+            dst.indented("}");
+            dst.line("}");
+            dst.finished_object();
+        }
+
+        for (any, variants) in [
+            ("AnyRequest", requests),
+            ("AnyResponse", responses),
+            ("AnyEvent", events),
+        ] {
+            dst.line(format!("impl Positions for {any} {{"));
+            dst.indented("fn shift_positions(&mut self, shift: Shift) {");
+            dst.indented("    match self {");
+            for (variant, ty) in variants {
+                if self.is_positioned(ty) {
+                    dst.arm(3, format!("{any}::{variant}(v)"), "v.shift_positions(shift)");
+                }
+            }
+            dst.indented("        _ => {}");
+            dst.indented("    }");
+            dst.indented("}");
+            dst.line("}");
+            dst.finished_object();
+        }
+        dst.output
+    }
+}
+
+fn add_struct<'a>(structs: &mut IndexMap<String, &'a Object>, name: &str, o: &'a Object) {
+    structs.insert(name.to_owned(), o);
+    for field in &o.fields {
+        if let Some(inline) = inline_object(&field.ty) {
+            add_struct(structs, &inline_name(name, &field.name), inline);
+        }
+    }
+}
+
+fn inline_object(ty: &Type) -> Option<&Object> {
+    match ty {
+        Type::Object(o) => Some(o),
+        Type::Vec(item) | Type::Option(item) => inline_object(item),
+        _ => None,
+    }
+}
+
+/// The name of the struct a field of `parent` holds, if any.
+fn nested(parent: &str, field: &Field) -> Option<String> {
+    fn inner(ty: &Type) -> &Type {
+        match ty {
+            Type::Vec(item) | Type::Option(item) => inner(item),
+            ty => ty,
+        }
+    }
+    match inner(&field.ty) {
+        Type::Basic(name) => Some(name.clone()),
+        Type::Object(_) => Some(inline_name(parent, &field.name)),
+        _ => None,
+    }
+}
+
+/// The number of `Option` and `Vec` layers around the type of a field.
+fn layers(field: &Field) -> usize {
+    fn count(ty: &Type) -> usize {
+        match ty {
+            Type::Vec(item) | Type::Option(item) => 1 + count(item),
+            _ => 0,
+        }
+    }
+    count(&field.ty) + usize::from(!field.required)
+}
+
+/// The kind of numbers a field holds.
+enum Numbers {
+    Line,
+    Column,
+}
+
+impl Numbers {
+    /// The method of `Shift` shifting the numbers.
+    fn method(&self) -> &'static str {
+        match self {
+            Numbers::Line => "line",
+            Numbers::Column => "column",
+        }
+    }
+}
+
+/// Whether the field is 0, to be ignored, if the `source` of its struct is
+/// missing.
+fn absent_without_source(field: &Field) -> bool {
+    let doc = field.doc.as_deref().unwrap_or_default();
+    doc.contains("is 0 and should be ignored")
+}
+
+fn numbers(field: &Field) -> Option<Numbers> {
+    let doc = field.doc.as_deref().unwrap_or_default();
+    let numbers = match field.name.as_str() {
+        "line" | "endLine" | "lines" => Numbers::Line,
+        "column" | "endColumn" => Numbers::Column,
+        _ if doc.contains("`linesStartAt1`") => Numbers::Line,
+        _ if doc.contains("`columnsStartAt1`") => Numbers::Column,
+        _ => return None,
+    };
+    fn basic(ty: &Type) -> Option<&str> {
+        match ty {
+            Type::Basic(ty) => Some(ty),
+            Type::Vec(item) | Type::Option(item) => basic(item),
+            _ => None,
+        }
+    }
+    match basic(&field.ty) {
+        Some("u32") => Some(numbers),
+        // e.g. `StackFrameFormat.line`, a flag.
+        Some("bool") => None,
+        ty => panic!("position field {} is of type {ty:?}, not u32", field.name),
+    }
+}